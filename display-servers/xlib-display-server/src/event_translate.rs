@@ -34,9 +34,11 @@ impl<'a> From<XEvent<'a>> for Option<DisplayEvent> {
             // Mouse motion notify.
             xlib::MotionNotify => from_motion_notify(x_event),
             // Mouse button pressed.
-            xlib::ButtonPress => Some(from_button_press(raw_event)),
+            xlib::ButtonPress => from_button_press(x_event),
             // Mouse button released.
             xlib::ButtonRelease if !normal_mode => Some(from_button_release(x_event)),
+            // A tab bar needs to be redrawn.
+            xlib::Expose => from_expose(&x_event),
             _other => None,
         }
     }
@@ -203,12 +205,33 @@ fn from_motion_notify(x_event: XEvent) -> Option<DisplayEvent> {
     None
 }
 
-fn from_button_press(raw_event: xlib::XEvent) -> DisplayEvent {
-    let event = xlib::XButtonPressedEvent::from(raw_event);
+fn from_button_press(x_event: XEvent) -> Option<DisplayEvent> {
+    let xw = x_event.0;
+    let event = xlib::XButtonPressedEvent::from(x_event.1);
+    // Clicking a tab focuses its window.
+    if xw.is_tab_bar(event.window) {
+        let handle = xw.get_tab_bar_window_at(event.window, event.x, event.y)?;
+        return Some(DisplayEvent::WindowTakeFocus(handle));
+    }
     let h = event.window.into();
     let mut mod_mask = event.state;
     mod_mask &= !(xlib::Mod2Mask | xlib::LockMask);
-    DisplayEvent::MouseCombo(mod_mask, event.button, h, event.x, event.y)
+    Some(DisplayEvent::MouseCombo(
+        mod_mask,
+        event.button,
+        h,
+        event.x,
+        event.y,
+    ))
+}
+
+fn from_expose(x_event: &XEvent) -> Option<DisplayEvent> {
+    let event = xlib::XExposeEvent::from(x_event.1);
+    // Only redraw once the last of a series of exposures arrived.
+    if event.count == 0 {
+        x_event.0.redraw_tab_bar(event.window);
+    }
+    None
 }
 
 fn from_button_release(x_event: XEvent) -> DisplayEvent {
@@ -1,4 +1,5 @@
 use super::{DisplayEvent, XWrap};
+use crate::xwrap::ICONIC_STATE;
 use leftwm_core::{models::WindowChange, Command};
 use std::convert::TryFrom;
 use std::os::raw::c_long;
@@ -16,11 +17,7 @@ pub fn from_event(xw: &XWrap, event: xlib::XClientMessageEvent) -> Option<Displa
         let value = event.data.get_long(0);
         match usize::try_from(value) {
             Ok(index) => {
-                let event = DisplayEvent::SendCommand(Command::GoToTag {
-                    tag: index + 1,
-                    swap: false,
-                });
-                return Some(event);
+                return Some(DisplayEvent::ViewDesktop(index + 1));
             }
             Err(err) => {
                 tracing::debug!(
@@ -53,34 +50,44 @@ pub fn from_event(xw: &XWrap, event: xlib::XClientMessageEvent) -> Option<Displa
         }
     }
     if event.message_type == xw.atoms.NetActiveWindow {
-        xw.set_window_urgency(event.window, true);
-        return None;
+        // Pagers ask on behalf of the user. Applications may not take the focus from a window the
+        // user worked in after the request.
+        let from_pager = event.data.get_long(0) == 2;
+        let time = match event.data.get_long(1) as xlib::Time {
+            0 => xw.get_window_user_time(event.window).unwrap_or(0),
+            time => time,
+        };
+        let focused_time = match xw.focused_window {
+            focused if focused == event.window || focused == xw.get_default_root() => 0,
+            focused => xw.get_window_user_time(focused).unwrap_or(0),
+        };
+        let may_focus = from_pager || time == 0 || is_not_before(time, focused_time);
+        let event = DisplayEvent::ActivateWindow(event.window.into(), may_focus);
+        return Some(event);
     }
 
-    //if the client is trying to toggle fullscreen without changing the window state, change it too
-    if event.message_type == xw.atoms.NetWMState
-        && (event.data.get_long(1) == xw.atoms.NetWMStateFullscreen as c_long
-            || event.data.get_long(2) == xw.atoms.NetWMStateFullscreen as c_long)
-    {
-        let set_fullscreen = event.data.get_long(0) == 1;
-        let toggle_fullscreen = event.data.get_long(0) == 2;
-        let mut states = xw.get_window_states_atoms(event.window);
-        //determine what to change the state to
-        let fullscreen = if toggle_fullscreen {
-            !states.contains(&xw.atoms.NetWMStateFullscreen)
-        } else {
-            set_fullscreen
-        };
-        //update the list of states
-        if fullscreen {
-            states.push(xw.atoms.NetWMStateFullscreen);
-        } else {
-            states.retain(|x| x != &xw.atoms.NetWMStateFullscreen);
+    //a client asking to be iconified is minimized
+    if event.message_type == xw.atoms.WMChangeState && event.data.get_long(0) == ICONIC_STATE {
+        change_state_atom(xw, event.window, xw.atoms.NetWMStateHidden, 1);
+        let mut change = WindowChange::new(event.window.into());
+        change.states = Some(xw.get_window_states(event.window));
+        return Some(DisplayEvent::WindowChange(change));
+    }
+
+    //if the client is trying to toggle a state leftwm acts on without changing the window state, change it too
+    if event.message_type == xw.atoms.NetWMState {
+        let atoms = [
+            xw.atoms.NetWMStateFullscreen,
+            xw.atoms.NetWMStateHidden,
+            xw.atoms.NetWMStateMaximizedVert,
+            xw.atoms.NetWMStateMaximizedHorz,
+        ];
+        for atom in atoms {
+            if event.data.get_long(1) == atom as c_long || event.data.get_long(2) == atom as c_long
+            {
+                change_state_atom(xw, event.window, atom, event.data.get_long(0));
+            }
         }
-        states.sort_unstable();
-        states.dedup();
-        //set the windows state
-        xw.set_window_states_atoms(event.window, &states);
     }
 
     //update the window states
@@ -94,3 +101,30 @@ pub fn from_event(xw: &XWrap, event: xlib::XClientMessageEvent) -> Option<Displa
 
     None
 }
+
+/// Removes (0), adds (1) or toggles (2) a state of the window, as asked for by `_NET_WM_STATE`.
+fn change_state_atom(xw: &XWrap, window: xlib::Window, atom: xlib::Atom, action: c_long) {
+    let mut states = xw.get_window_states_atoms(window);
+    //determine what to change the state to
+    let set = if action == 2 {
+        !states.contains(&atom)
+    } else {
+        action == 1
+    };
+    //update the list of states
+    if set {
+        states.push(atom);
+    } else {
+        states.retain(|x| x != &atom);
+    }
+    states.sort_unstable();
+    states.dedup();
+    //set the windows state
+    xw.set_window_states_atoms(window, &states);
+}
+
+/// Whether the X server time `time` is not before `other`. The times are 32 bit milliseconds
+/// and wrap around after about 49 days.
+fn is_not_before(time: xlib::Time, other: xlib::Time) -> bool {
+    other == 0 || (time as u32).wrapping_sub(other as u32) as i32 >= 0
+}
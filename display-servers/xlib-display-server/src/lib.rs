@@ -11,7 +11,9 @@ use self::xwrap::ICONIC_STATE;
 use event_translate::XEvent;
 use futures::prelude::*;
 use leftwm_core::config::Config;
-use leftwm_core::models::{Mode, Screen, TagId, Window, WindowHandle, WindowState, Workspace};
+use leftwm_core::models::{
+    Mode, Screen, TabBar, TagId, Window, WindowHandle, WindowState, Workspace,
+};
 use leftwm_core::utils;
 use leftwm_core::{DisplayAction, DisplayEvent, DisplayServer};
 use std::os::raw::c_uint;
@@ -66,6 +68,10 @@ impl DisplayServer for XlibDisplayServer {
         }
     }
 
+    fn update_tab_bars(&mut self, tab_bars: Vec<TabBar>) {
+        self.xw.update_tab_bars(tab_bars);
+    }
+
     fn get_next_events(&mut self) -> Vec<DisplayEvent> {
         let mut events = std::mem::take(&mut self.initial_events);
 
@@ -105,7 +111,11 @@ impl DisplayServer for XlibDisplayServer {
             DisplayAction::ReadyToMoveWindow(h) => from_ready_to_move_window(xw, h),
             DisplayAction::ReadyToResizeWindow(h) => from_ready_to_resize_window(xw, h),
             DisplayAction::SetCurrentTags(t) => from_set_current_tags(xw, t),
+            DisplayAction::SetDesktopNames(n) => from_set_desktop_names(xw, n),
             DisplayAction::SetWindowTag(h, t) => from_set_window_tag(xw, h, t),
+            DisplayAction::SetWindowTags(h, t) => from_set_window_tags(xw, h, &t),
+            DisplayAction::SetWindowUrgency(h, u) => from_set_window_urgency(xw, h, u),
+            DisplayAction::SetWindowBorderColor(h, c) => from_set_window_border_color(xw, h, c),
             DisplayAction::ConfigureXlibWindow(w) => from_configure_xlib_window(xw, &w),
 
             DisplayAction::WindowTakeFocus {
@@ -328,6 +338,22 @@ fn from_set_current_tags(xw: &mut XWrap, tag: Option<TagId>) -> Option<DisplayEv
     None
 }
 
+fn from_set_desktop_names(xw: &mut XWrap, names: Vec<String>) -> Option<DisplayEvent> {
+    xw.tag_labels = names;
+    xw.set_desktop_names_hints();
+    None
+}
+
+fn from_set_window_tags(
+    xw: &mut XWrap,
+    handle: WindowHandle,
+    tags: &[TagId],
+) -> Option<DisplayEvent> {
+    let window = handle.xlib_handle()?;
+    xw.set_window_desktops(window, tags);
+    None
+}
+
 fn from_set_window_tag(
     xw: &mut XWrap,
     handle: WindowHandle,
@@ -339,6 +365,27 @@ fn from_set_window_tag(
     None
 }
 
+fn from_set_window_urgency(
+    xw: &mut XWrap,
+    handle: WindowHandle,
+    urgent: bool,
+) -> Option<DisplayEvent> {
+    let window = handle.xlib_handle()?;
+    xw.set_window_urgency(window, urgent);
+    None
+}
+
+fn from_set_window_border_color(
+    xw: &mut XWrap,
+    handle: WindowHandle,
+    color: String,
+) -> Option<DisplayEvent> {
+    let window = handle.xlib_handle()?;
+    let color = xw.get_color(color);
+    xw.set_unfocused_border_color(window, color);
+    None
+}
+
 fn from_configure_xlib_window(xw: &mut XWrap, window: &Window) -> Option<DisplayEvent> {
     xw.configure_window(window);
     None
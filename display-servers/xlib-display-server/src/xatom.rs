@@ -10,7 +10,9 @@ pub struct XAtom {
     pub WMProtocols: xlib::Atom,
     pub WMDelete: xlib::Atom,
     pub WMState: xlib::Atom,
+    pub WMChangeState: xlib::Atom,
     pub WMClass: xlib::Atom,
+    pub WMWindowRole: xlib::Atom,
     pub WMTakeFocus: xlib::Atom,
     pub NetActiveWindow: xlib::Atom,
     pub NetSupported: xlib::Atom,
@@ -18,6 +20,8 @@ pub struct XAtom {
     pub NetWMState: xlib::Atom,
     pub NetWMAction: xlib::Atom,
     pub NetWMPid: xlib::Atom,
+    pub NetWMUserTime: xlib::Atom,
+    pub NetWMUserTimeWindow: xlib::Atom,
 
     pub NetWMActionMove: xlib::Atom,
     pub NetWMActionResize: xlib::Atom,
@@ -74,6 +78,8 @@ impl XAtom {
             self.NetWMState,
             self.NetWMAction,
             self.NetWMPid,
+            self.NetWMUserTime,
+            self.NetWMUserTimeWindow,
             self.NetWMStateModal,
             self.NetWMStateSticky,
             self.NetWMStateMaximizedVert,
@@ -121,7 +127,9 @@ impl XAtom {
             a if a == self.WMProtocols => "WM_PROTOCOLS",
             a if a == self.WMDelete => "WM_DELETE_WINDOW",
             a if a == self.WMState => "WM_STATE",
+            a if a == self.WMChangeState => "WM_CHANGE_STATE",
             a if a == self.WMClass => "WM_CLASS",
+            a if a == self.WMWindowRole => "WM_WINDOW_ROLE",
             a if a == self.WMTakeFocus => "WM_TAKE_FOCUS",
             a if a == self.NetActiveWindow => "_NET_ACTIVE_WINDOW",
             a if a == self.NetSupported => "_NET_SUPPORTED",
@@ -129,6 +137,8 @@ impl XAtom {
             a if a == self.NetWMState => "_NET_WM_STATE",
             a if a == self.NetWMAction => "_NET_WM_ALLOWED_ACTIONS",
             a if a == self.NetWMPid => "_NET_WM_PID",
+            a if a == self.NetWMUserTime => "_NET_WM_USER_TIME",
+            a if a == self.NetWMUserTimeWindow => "_NET_WM_USER_TIME_WINDOW",
 
             a if a == self.NetWMStateModal => "NetWMStateModal",
             a if a == self.NetWMStateSticky => "NetWMStateSticky",
@@ -175,12 +185,16 @@ impl XAtom {
             WMProtocols: from(xlib, dpy, "WM_PROTOCOLS"),
             WMDelete: from(xlib, dpy, "WM_DELETE_WINDOW"),
             WMState: from(xlib, dpy, "WM_STATE"),
+            WMChangeState: from(xlib, dpy, "WM_CHANGE_STATE"),
             WMClass: from(xlib, dpy, "WM_CLASS"),
+            WMWindowRole: from(xlib, dpy, "WM_WINDOW_ROLE"),
             WMTakeFocus: from(xlib, dpy, "WM_TAKE_FOCUS"),
             NetActiveWindow: from(xlib, dpy, "_NET_ACTIVE_WINDOW"),
             NetSupported: from(xlib, dpy, "_NET_SUPPORTED"),
             NetWMName: from(xlib, dpy, "_NET_WM_NAME"),
             NetWMPid: from(xlib, dpy, "_NET_WM_PID"),
+            NetWMUserTime: from(xlib, dpy, "_NET_WM_USER_TIME"),
+            NetWMUserTimeWindow: from(xlib, dpy, "_NET_WM_USER_TIME_WINDOW"),
 
             NetWMState: from(xlib, dpy, "_NET_WM_STATE"),
             NetWMStateModal: from(xlib, dpy, "_NET_WM_STATE_MODAL"),
@@ -10,8 +10,9 @@ use super::xatom::XAtom;
 use super::xcursor::XCursor;
 use super::{utils, Screen, Window, WindowHandle};
 use leftwm_core::config::Config;
-use leftwm_core::models::{FocusBehaviour, Mode};
+use leftwm_core::models::{FocusBehaviour, Mode, TabBar};
 use leftwm_core::utils::modmask_lookup::ModMask;
+use std::collections::HashMap;
 use std::ffi::CString;
 use std::os::raw::{c_char, c_double, c_int, c_long, c_short, c_ulong};
 use std::sync::Arc;
@@ -25,12 +26,13 @@ use x11_dl::xrandr::Xrandr;
 mod getters;
 mod mouse;
 mod setters;
+mod tab_bar;
 mod window;
 
 type WindowStateConst = c_long;
 pub const WITHDRAWN_STATE: WindowStateConst = 0;
 pub const NORMAL_STATE: WindowStateConst = 1;
-pub const ICONIC_STATE: WindowStateConst = 2;
+pub const ICONIC_STATE: WindowStateConst = 3;
 const MAX_PROPERTY_VALUE_LEN: c_long = 4096;
 
 pub const ROOT_EVENT_MASK: c_long = xlib::SubstructureRedirectMask
@@ -90,6 +92,9 @@ pub struct Colors {
     floating: c_ulong,
     active: c_ulong,
     background: c_ulong,
+    tab: c_ulong,
+    tab_focused: c_ulong,
+    tab_text: c_ulong,
 }
 
 #[derive(Debug, Clone)]
@@ -107,6 +112,8 @@ pub struct XWrap {
     pub atoms: XAtom,
     cursors: XCursor,
     colors: Colors,
+    /// Border colours of windows which do not use the ones of the theme.
+    border_colors: HashMap<xlib::Window, c_ulong>,
     pub managed_windows: Vec<xlib::Window>,
     pub focused_window: xlib::Window,
     pub tag_labels: Vec<String>,
@@ -118,6 +125,8 @@ pub struct XWrap {
     pub task_notify: Arc<Notify>,
     pub motion_event_limiter: c_ulong,
     pub refresh_rate: c_short,
+    /// Windows of the tab bars along with the bar they show.
+    tab_bars: Vec<(xlib::Window, TabBar)>,
 }
 
 impl Default for XWrap {
@@ -185,6 +194,9 @@ impl XWrap {
             floating: 0,
             active: 0,
             background: 0,
+            tab: 0,
+            tab_focused: 0,
+            tab_text: 0,
         };
 
         let refresh_rate = match Xrandr::open() {
@@ -228,6 +240,7 @@ impl XWrap {
             atoms,
             cursors,
             colors,
+            border_colors: HashMap::new(),
             managed_windows: vec![],
             focused_window: root,
             tag_labels: vec![],
@@ -239,6 +252,7 @@ impl XWrap {
             task_notify,
             motion_event_limiter: 0,
             refresh_rate,
+            tab_bars: vec![],
         };
 
         // Check that another WM is not running.
@@ -320,20 +334,37 @@ impl XWrap {
     }
 
     /// EWMH support used for bars such as polybar.
-    ///  # Panics
-    ///
-    ///  Panics if a new Cstring cannot be formed
+    pub fn init_desktops_hints(&self) {
+        self.set_desktop_names_hints();
+        // Set a current desktop.
+        let data = vec![0_u32, xlib::CurrentTime as u32];
+        self.set_desktop_prop(&data, self.atoms.NetCurrentDesktop);
+
+        // Set the WM NAME.
+        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String);
+
+        self.set_desktop_prop_string("LeftWM", self.atoms.WMClass, xlib::XA_STRING);
+
+        self.set_desktop_prop_c_ulong(
+            self.root as c_ulong,
+            self.atoms.NetSupportingWmCheck,
+            xlib::XA_WINDOW,
+        );
+
+        // Set a viewport.
+        let data = vec![0_u32, 0_u32];
+        self.set_desktop_prop(&data, self.atoms.NetDesktopViewport);
+    }
+
+    /// Sets the number and the names of the desktops from the tag labels.
     // `Xutf8TextListToTextProperty`: https://linux.die.net/man/3/xutf8textlisttotextproperty
     // `XSetTextProperty`: https://tronche.com/gui/x/xlib/ICC/client-to-window-manager/XSetTextProperty.html
-    pub fn init_desktops_hints(&self) {
+    pub fn set_desktop_names_hints(&self) {
         let tag_labels = &self.tag_labels;
         let tag_length = tag_labels.len();
         // Set the number of desktop.
         let data = vec![tag_length as u32];
         self.set_desktop_prop(&data, self.atoms.NetNumberOfDesktops);
-        // Set a current desktop.
-        let data = vec![0_u32, xlib::CurrentTime as u32];
-        self.set_desktop_prop(&data, self.atoms.NetCurrentDesktop);
         // Set desktop names.
         let mut text: xlib::XTextProperty = unsafe { std::mem::zeroed() };
         unsafe {
@@ -357,21 +388,6 @@ impl XWrap {
                 self.atoms.NetDesktopNames,
             );
         }
-
-        // Set the WM NAME.
-        self.set_desktop_prop_string("LeftWM", self.atoms.NetWMName, self.atoms.UTF8String);
-
-        self.set_desktop_prop_string("LeftWM", self.atoms.WMClass, xlib::XA_STRING);
-
-        self.set_desktop_prop_c_ulong(
-            self.root as c_ulong,
-            self.atoms.NetSupportingWmCheck,
-            xlib::XA_WINDOW,
-        );
-
-        // Set a viewport.
-        let data = vec![0_u32, 0_u32];
-        self.set_desktop_prop(&data, self.atoms.NetDesktopViewport);
     }
 
     /// Send a xevent atom for a window to X.
@@ -430,6 +446,9 @@ impl XWrap {
             floating: self.get_color(config.floating_border_color()),
             active: self.get_color(config.focused_border_color()),
             background: self.get_color(config.background_color()),
+            tab: self.get_color(config.tab_color()),
+            tab_focused: self.get_color(config.tab_focused_color()),
+            tab_text: self.get_color(config.tab_text_color()),
         };
         // Update all the windows with the new colors.
         if let Some(windows) = windows {
@@ -439,10 +458,8 @@ impl XWrap {
                         matches!(focused, Some(&Some(focused)) if focused == window.handle);
                     let color: c_ulong = if is_focused {
                         self.colors.active
-                    } else if window.floating() {
-                        self.colors.floating
                     } else {
-                        self.colors.normal
+                        self.get_unfocused_border_color(handle, window.floating())
                     };
                     self.set_window_border_color(handle, color);
                 }
@@ -45,6 +45,15 @@ impl XWrap {
         }
     }
 
+    /// Returns the border colour of a window while it is not focused.
+    pub fn get_unfocused_border_color(&self, window: xlib::Window, floating: bool) -> c_ulong {
+        match self.border_colors.get(&window) {
+            Some(color) => *color,
+            None if floating => self.colors.floating,
+            None => self.colors.normal,
+        }
+    }
+
     /// Returns the current position of the cursor.
     /// # Errors
     ///
@@ -433,6 +442,12 @@ impl XWrap {
         None
     }
 
+    /// Returns a windows `WM_WINDOW_ROLE`.
+    #[must_use]
+    pub fn get_window_role(&self, window: xlib::Window) -> Option<String> {
+        self.get_text_prop(window, self.atoms.WMWindowRole).ok()
+    }
+
     /// Returns a windows `_NET_WM_PID`.
     #[must_use]
     pub fn get_window_pid(&self, window: xlib::Window) -> Option<u32> {
@@ -446,6 +461,33 @@ impl XWrap {
         }
     }
 
+    /// Returns a windows `_NET_WM_USER_TIME`, the time of the last user input in the window.
+    /// Clients may keep it on the window named by `_NET_WM_USER_TIME_WINDOW` instead.
+    #[must_use]
+    pub fn get_window_user_time(&self, window: xlib::Window) -> Option<xlib::Time> {
+        let window = self.get_user_time_window(window).unwrap_or(window);
+        let (prop_return, _) = self
+            .get_property(window, self.atoms.NetWMUserTime, xlib::XA_CARDINAL)
+            .ok()?;
+        unsafe {
+            #[allow(clippy::cast_ptr_alignment)]
+            let time = *prop_return.cast::<xlib::Time>();
+            Some(time)
+        }
+    }
+
+    /// Returns a windows `_NET_WM_USER_TIME_WINDOW`.
+    fn get_user_time_window(&self, window: xlib::Window) -> Option<xlib::Window> {
+        let (prop_return, _) = self
+            .get_property(window, self.atoms.NetWMUserTimeWindow, xlib::XA_WINDOW)
+            .ok()?;
+        unsafe {
+            #[allow(clippy::cast_ptr_alignment)]
+            let time_window = *prop_return.cast::<xlib::Window>();
+            Some(time_window).filter(|&time_window| time_window != 0)
+        }
+    }
+
     /// Returns the states of a window.
     #[must_use]
     pub fn get_window_states(&self, window: xlib::Window) -> Vec<WindowState> {
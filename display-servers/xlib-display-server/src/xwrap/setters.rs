@@ -134,7 +134,9 @@ impl XWrap {
                 }
                 states.push(atom);
             } else {
-                let Some(index) = states.iter().position(|s| s == &atom) else { return };
+                let Some(index) = states.iter().position(|s| s == &atom) else {
+                    return;
+                };
                 states.remove(index);
             }
             self.set_window_states_atoms(h, &states);
@@ -153,6 +155,15 @@ impl XWrap {
         }
     }
 
+    /// Sets the colour of the border of a window while it is not focused, instead of the one of
+    /// the theme.
+    pub fn set_unfocused_border_color(&mut self, window: xlib::Window, color: c_ulong) {
+        self.border_colors.insert(window, color);
+        if self.focused_window != window {
+            self.set_window_border_color(window, color);
+        }
+    }
+
     pub fn set_background_color(&self, mut color: c_ulong) {
         unsafe {
             // Force border opacity to 0xff.
@@ -186,6 +197,25 @@ impl XWrap {
         self.replace_property_long(window, self.atoms.NetWMDesktop, xlib::XA_CARDINAL, &indexes);
     }
 
+    /// Sets what desktop a window on several tags is on. A window on every tag is on all
+    /// desktops, otherwise it is on the desktop of its first tag.
+    pub fn set_window_desktops(&self, window: xlib::Window, tags: &[TagId]) {
+        let on_all = (1..=self.tag_labels.len()).all(|tag| tags.contains(&tag));
+        match tags.first() {
+            Some(_) if on_all => {
+                let all: Vec<c_long> = vec![0xFFFF_FFFF];
+                self.replace_property_long(
+                    window,
+                    self.atoms.NetWMDesktop,
+                    xlib::XA_CARDINAL,
+                    &all,
+                );
+            }
+            Some(tag) => self.set_window_desktop(window, tag),
+            None => {}
+        }
+    }
+
     /// Sets the atom states of a window.
     pub fn set_window_states_atoms(&self, window: xlib::Window, states: &[xlib::Atom]) {
         let data: Vec<c_long> = states.iter().map(|x| *x as c_long).collect();
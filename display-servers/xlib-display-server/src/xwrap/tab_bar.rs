@@ -0,0 +1,205 @@
+//! Xlib calls related to the tab bars of the `Tabbed` and `Stacked` layouts.
+use super::WindowHandle;
+use crate::XWrap;
+use leftwm_core::models::{Tab, TabBar};
+use std::ffi::CString;
+use std::os::raw::{c_int, c_uint};
+use std::ptr;
+use x11_dl::xlib;
+
+/// Space between the border of a tab and its title.
+const TITLE_PADDING: i32 = 6;
+
+impl XWrap {
+    // Public functions.
+
+    /// Shows the tab bars. The windows of previously shown bars are reused, the ones which are
+    /// left over are destroyed.
+    // `XDestroyWindow`: https://tronche.com/gui/x/xlib/window/XDestroyWindow.html
+    pub fn update_tab_bars(&mut self, tab_bars: Vec<TabBar>) {
+        let count = tab_bars.len();
+        for (index, bar) in tab_bars.into_iter().enumerate() {
+            match self.tab_bars.get_mut(index) {
+                Some((_, shown)) if *shown == bar => continue,
+                Some((_, shown)) => *shown = bar,
+                None => {
+                    let window = self.create_tab_bar_window();
+                    self.tab_bars.push((window, bar));
+                }
+            }
+            let (window, bar) = &self.tab_bars[index];
+            self.show_tab_bar(*window, bar);
+        }
+        if count < self.tab_bars.len() {
+            for (window, _) in self.tab_bars.drain(count..) {
+                unsafe { (self.xlib.XDestroyWindow)(self.display, window) };
+            }
+        }
+    }
+
+    /// Returns the window of the tab at a point relative to the tab bar window.
+    pub fn get_tab_bar_window_at(
+        &self,
+        window: xlib::Window,
+        x: i32,
+        y: i32,
+    ) -> Option<WindowHandle> {
+        let (_, bar) = self.tab_bars.iter().find(|(w, _)| *w == window)?;
+        bar.window_at(x, y)
+    }
+
+    /// Returns whether the window is one of the tab bars.
+    pub fn is_tab_bar(&self, window: xlib::Window) -> bool {
+        self.tab_bars.iter().any(|(w, _)| *w == window)
+    }
+
+    /// Draws the tabs of a tab bar window again, eg. after it was exposed.
+    pub fn redraw_tab_bar(&self, window: xlib::Window) {
+        if let Some((window, bar)) = self.tab_bars.iter().find(|(w, _)| *w == window) {
+            self.draw_tab_bar(*window, bar);
+        }
+    }
+
+    // Private functions.
+
+    /// Creates an unmanaged window for a tab bar.
+    // `XCreateSimpleWindow`: https://tronche.com/gui/x/xlib/window/XCreateWindow.html
+    // `XChangeWindowAttributes`: https://tronche.com/gui/x/xlib/window/XChangeWindowAttributes.html
+    fn create_tab_bar_window(&self) -> xlib::Window {
+        unsafe {
+            let window = (self.xlib.XCreateSimpleWindow)(
+                self.display,
+                self.root,
+                0,
+                0,
+                1,
+                1,
+                0,
+                self.colors.normal,
+                self.colors.normal,
+            );
+            let mut attrs: xlib::XSetWindowAttributes = std::mem::zeroed();
+            attrs.override_redirect = xlib::True;
+            attrs.event_mask = xlib::ButtonPressMask | xlib::ExposureMask;
+            (self.xlib.XChangeWindowAttributes)(
+                self.display,
+                window,
+                xlib::CWOverrideRedirect | xlib::CWEventMask,
+                &mut attrs,
+            );
+            window
+        }
+    }
+
+    /// Moves the tab bar window into place, maps and draws it.
+    // `XMoveResizeWindow`: https://tronche.com/gui/x/xlib/window/XMoveResizeWindow.html
+    // `XSetWindowBackground`: https://tronche.com/gui/x/xlib/window/XSetWindowBackground.html
+    // `XMapWindow`: https://tronche.com/gui/x/xlib/window/XMapWindow.html
+    // `XLowerWindow`: https://tronche.com/gui/x/xlib/window/XLowerWindow.html
+    fn show_tab_bar(&self, window: xlib::Window, bar: &TabBar) {
+        unsafe {
+            (self.xlib.XMoveResizeWindow)(
+                self.display,
+                window,
+                bar.xyhw.x(),
+                bar.xyhw.y(),
+                bar.xyhw.w().max(1) as c_uint,
+                bar.xyhw.h().max(1) as c_uint,
+            );
+            (self.xlib.XSetWindowBackground)(self.display, window, self.colors.normal);
+            (self.xlib.XMapWindow)(self.display, window);
+            // Keep floating windows above the bar.
+            (self.xlib.XLowerWindow)(self.display, window);
+        }
+        self.draw_tab_bar(window, bar);
+    }
+
+    /// Fills each tab with its colour and writes the title of its window on it.
+    // `XCreateGC`: https://tronche.com/gui/x/xlib/GC/XCreateGC.html
+    // `XQueryFont`: https://tronche.com/gui/x/xlib/graphics/font-metrics/XQueryFont.html
+    // `XFreeFontInfo`: https://tronche.com/gui/x/xlib/graphics/font-metrics/XFreeFontInfo.html
+    // `XFreeGC`: https://tronche.com/gui/x/xlib/GC/XFreeGC.html
+    fn draw_tab_bar(&self, window: xlib::Window, bar: &TabBar) {
+        unsafe {
+            let gc = (self.xlib.XCreateGC)(self.display, window, 0, ptr::null_mut());
+            let font = (self.xlib.XQueryFont)(self.display, (self.xlib.XGContextFromGC)(gc));
+            for tab in &bar.tabs {
+                self.draw_tab(window, gc, font, tab);
+            }
+            if !font.is_null() {
+                (self.xlib.XFreeFontInfo)(ptr::null_mut(), font, 0);
+            }
+            (self.xlib.XFreeGC)(self.display, gc);
+        }
+        self.flush();
+    }
+
+    /// Draws a single tab, leaving a gap of one pixel to the next tab.
+    // `XSetForeground`: https://tronche.com/gui/x/xlib/GC/convenience-functions/XSetForeground.html
+    // `XFillRectangle`: https://tronche.com/gui/x/xlib/graphics/filling-areas/XFillRectangle.html
+    // `XDrawString`: https://tronche.com/gui/x/xlib/graphics/drawing-text/XDrawString.html
+    fn draw_tab(
+        &self,
+        window: xlib::Window,
+        gc: xlib::GC,
+        font: *mut xlib::XFontStruct,
+        tab: &Tab,
+    ) {
+        let xyhw = tab.xyhw;
+        let color = if tab.active {
+            self.colors.tab_focused
+        } else {
+            self.colors.tab
+        };
+        unsafe {
+            (self.xlib.XSetForeground)(self.display, gc, color);
+            (self.xlib.XFillRectangle)(
+                self.display,
+                window,
+                gc,
+                xyhw.x(),
+                xyhw.y(),
+                (xyhw.w() - 1).max(0) as c_uint,
+                (xyhw.h() - 1).max(0) as c_uint,
+            );
+        }
+        if font.is_null() {
+            return;
+        }
+        let (ascent, descent) = unsafe { ((*font).ascent, (*font).descent) };
+        let title = self.fit_text(font, &tab.title, xyhw.w() - 2 * TITLE_PADDING);
+        let Ok(text) = CString::new(title) else {
+            return;
+        };
+        let len = text.as_bytes().len() as c_int;
+        let baseline = xyhw.y() + (xyhw.h() + ascent - descent) / 2;
+        unsafe {
+            (self.xlib.XSetForeground)(self.display, gc, self.colors.tab_text);
+            (self.xlib.XDrawString)(
+                self.display,
+                window,
+                gc,
+                xyhw.x() + TITLE_PADDING,
+                baseline,
+                text.as_ptr(),
+                len,
+            );
+        }
+    }
+
+    /// Shortens the text until it fits into the width.
+    // `XTextWidth`: https://tronche.com/gui/x/xlib/graphics/font-metrics/XTextWidth.html
+    fn fit_text<'a>(&self, font: *mut xlib::XFontStruct, text: &'a str, width: i32) -> &'a str {
+        let text_width = |text: &str| unsafe {
+            (self.xlib.XTextWidth)(font, text.as_ptr().cast(), text.len() as c_int)
+        };
+        let mut end = text.len();
+        while end > 0 && text_width(&text[..end]) > width {
+            end = text[..end]
+                .char_indices()
+                .next_back()
+                .map_or(0, |(index, _)| index);
+        }
+        &text[..end]
+    }
+}
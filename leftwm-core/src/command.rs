@@ -1,7 +1,7 @@
 pub use crate::handlers::command_handler::ReleaseScratchPadOption;
 use crate::{
     layouts::Layout,
-    models::{ScratchPadName, TagId, WindowHandle},
+    models::{Direction, ScratchPadName, SnapPosition, SplitAxis, TagId, WindowHandle},
 };
 use serde::{Deserialize, Serialize};
 
@@ -28,14 +28,37 @@ pub enum Command {
     ToggleScratchPad(ScratchPadName),
     ToggleFullScreen,
     ToggleSticky,
+    ToggleMaximize,
+    MinimizeWindow,
+    RestoreLastMinimized,
+    RestoreWindow(String),
     GoToTag {
         tag: TagId,
         swap: bool,
     },
     ReturnToLastTag,
+    ToggleTagView(TagId),
+    ViewOnlyTag(TagId),
+    AddTag(String),
+    RemoveTag {
+        tag: TagId,
+        target: Option<TagId>,
+    },
+    RenameTag {
+        tag: TagId,
+        label: String,
+    },
+    MoveTag {
+        tag: TagId,
+        position: usize,
+    },
     FloatingToTile,
     TileToFloating,
     ToggleFloating,
+    MoveFloatingBy(i32, i32),
+    ResizeFloatingBy(i32, i32),
+    CenterFloating,
+    SnapFloating(SnapPosition),
     MoveWindowUp,
     MoveWindowDown,
     MoveWindowTop {
@@ -44,6 +67,7 @@ pub enum Command {
     SwapWindowTop {
         swap: bool,
     },
+    MoveWindowDirection(Direction),
     FocusNextTag,
     FocusPreviousTag,
     FocusWindow(String),
@@ -52,12 +76,15 @@ pub enum Command {
     FocusWindowTop {
         swap: bool,
     },
+    FocusWindowDirection(Direction),
     FocusWorkspaceNext,
     FocusWorkspacePrevious,
     SendWindowToTag {
         window: Option<WindowHandle>,
         tag: TagId,
     },
+    ToggleWindowTag(TagId),
+    SetWindowTags(Vec<TagId>),
     MoveWindowToNextTag {
         follow: bool,
     },
@@ -70,9 +97,25 @@ pub enum Command {
     NextLayout,
     PreviousLayout,
     SetLayout(Layout),
+    ResetLayout,
     RotateTag,
     IncreaseMainWidth(i8),
     DecreaseMainWidth(i8),
+    IncreaseMainCount,
+    DecreaseMainCount,
+    IncreaseWindowWeight(f32),
+    DecreaseWindowWeight(f32),
+    PreselectSplit(Direction),
+    SetSplitRatio(u8),
+    RotateSplitTree,
+    BalanceSplitTree,
+    FlipSplitTree(SplitAxis),
+    ScrollLeft,
+    ScrollRight,
+    MoveColumnLeft,
+    MoveColumnRight,
+    IncreaseColumnWidth(i8),
+    DecreaseColumnWidth(i8),
     SetMarginMultiplier(f32),
     SendWorkspaceToTag(usize, usize),
     CloseAllOtherWindows,
@@ -1,18 +1,34 @@
+mod activation_policy;
+mod floating_placement;
 mod insert_behavior;
+mod layout_rule;
+mod tag_config;
 mod workspace_config;
 
 use crate::display_servers::DisplayServer;
-use crate::layouts::Layout;
+use crate::layouts::{CustomLayout, Layout};
 pub use crate::models::ScratchPad;
 pub use crate::models::{FocusBehaviour, Gutter, Margins, Size};
 use crate::models::{LayoutMode, Manager, Window, WindowType};
 use crate::state::State;
+pub use activation_policy::ActivationPolicy;
+pub use floating_placement::{FloatingGeometry, FloatingPlacement};
 pub use insert_behavior::InsertBehavior;
+pub use layout_rule::{LayoutRule, Orientation};
+pub use tag_config::TagConfig;
 pub use workspace_config::Workspace;
 
 pub trait Config {
     fn create_list_of_tag_labels(&self) -> Vec<String>;
 
+    /// Settings of the tags, in the same order as their labels. Tags without an entry use the
+    /// global settings.
+    fn tag_configs(&self) -> Vec<TagConfig>;
+
+    /// Whether every output in `workspaces` gets a set of tags of its own, instead of all outputs
+    /// sharing the same tags.
+    fn independent_tags(&self) -> bool;
+
     fn workspaces(&self) -> Option<Vec<Workspace>>;
 
     fn focus_behaviour(&self) -> FocusBehaviour;
@@ -23,10 +39,22 @@ pub trait Config {
 
     fn layouts(&self) -> Vec<Layout>;
 
+    /// Layouts defined in the config, they can be used through `Layout::Custom`.
+    fn custom_layouts(&self) -> Vec<CustomLayout>;
+
     fn layout_mode(&self) -> LayoutMode;
 
+    /// Rules choosing the layout of a tag automatically, the first matching rule wins.
+    fn layout_rules(&self) -> Vec<LayoutRule>;
+
     fn insert_behavior(&self) -> InsertBehavior;
 
+    /// Where new floating windows are put, window rules can override it.
+    fn floating_placement(&self) -> FloatingPlacement;
+
+    /// What happens when a client asks for a window to be activated, window rules can override it.
+    fn activation_policy(&self) -> ActivationPolicy;
+
     fn single_window_border(&self) -> bool;
 
     fn focus_new_windows(&self) -> bool;
@@ -47,11 +75,18 @@ pub trait Config {
     fn floating_border_color(&self) -> String;
     fn focused_border_color(&self) -> String;
     fn background_color(&self) -> String;
+    /// Height of a tab in the tab bar of the `Tabbed` and `Stacked` layouts.
+    fn tab_height(&self) -> i32;
+    fn tab_color(&self) -> String;
+    fn tab_focused_color(&self) -> String;
+    fn tab_text_color(&self) -> String;
     fn on_new_window_cmd(&self) -> Option<String>;
     fn get_list_of_gutters(&self) -> Vec<Gutter>;
     fn max_window_width(&self) -> Option<Size>;
     fn auto_derive_workspaces(&self) -> bool;
     fn disable_tile_drag(&self) -> bool;
+    /// Dragging a tiled window swaps it with the window it is dropped on instead of floating it.
+    fn reorder_tile_drag(&self) -> bool;
     fn disable_window_snap(&self) -> bool;
     fn sloppy_mouse_follows_focus(&self) -> bool;
 
@@ -68,10 +103,19 @@ pub trait Config {
     /// Handle window placement based on `WM_CLASS`
     fn setup_predefined_window(&self, state: &mut State, window: &mut Window) -> bool;
 
+    /// Apply the window rules which are re-evaluated after the title or `WM_CLASS` of a window
+    /// changed. `previous` is the window before the change.
+    fn reapply_predefined_window(
+        &self,
+        state: &mut State,
+        window: &mut Window,
+        previous: &Window,
+    ) -> bool;
+
     fn load_window(&self, window: &mut Window) {
         if window.r#type == WindowType::Normal {
             window.margin = self.margin();
-            window.border = self.border_width();
+            window.border = window.border_width.unwrap_or_else(|| self.border_width());
             window.must_float = self.always_float();
         } else {
             window.margin = Margins::new(0);
@@ -91,9 +135,15 @@ pub(crate) mod tests {
     #[derive(Default)]
     pub struct TestConfig {
         pub tags: Vec<String>,
+        pub tag_configs: Vec<TagConfig>,
+        pub independent_tags: bool,
         pub layouts: Vec<Layout>,
+        pub custom_layouts: Vec<CustomLayout>,
+        pub layout_rules: Vec<LayoutRule>,
         pub workspaces: Option<Vec<Workspace>>,
         pub insert_behavior: InsertBehavior,
+        pub floating_placement: FloatingPlacement,
+        pub activation_policy: ActivationPolicy,
         pub border_width: i32,
         pub single_window_border: bool,
     }
@@ -102,6 +152,12 @@ pub(crate) mod tests {
         fn create_list_of_tag_labels(&self) -> Vec<String> {
             self.tags.clone()
         }
+        fn tag_configs(&self) -> Vec<TagConfig> {
+            self.tag_configs.clone()
+        }
+        fn independent_tags(&self) -> bool {
+            self.independent_tags
+        }
         fn workspaces(&self) -> Option<Vec<Workspace>> {
             self.workspaces.clone()
         }
@@ -117,6 +173,12 @@ pub(crate) mod tests {
         fn layouts(&self) -> Vec<Layout> {
             self.layouts.clone()
         }
+        fn custom_layouts(&self) -> Vec<CustomLayout> {
+            self.custom_layouts.clone()
+        }
+        fn layout_rules(&self) -> Vec<LayoutRule> {
+            self.layout_rules.clone()
+        }
         fn layout_mode(&self) -> LayoutMode {
             LayoutMode::Workspace
         }
@@ -125,6 +187,14 @@ pub(crate) mod tests {
             self.insert_behavior
         }
 
+        fn floating_placement(&self) -> FloatingPlacement {
+            self.floating_placement
+        }
+
+        fn activation_policy(&self) -> ActivationPolicy {
+            self.activation_policy
+        }
+
         fn single_window_border(&self) -> bool {
             self.single_window_border
         }
@@ -177,6 +247,18 @@ pub(crate) mod tests {
         fn background_color(&self) -> String {
             unimplemented!()
         }
+        fn tab_height(&self) -> i32 {
+            20
+        }
+        fn tab_color(&self) -> String {
+            unimplemented!()
+        }
+        fn tab_focused_color(&self) -> String {
+            unimplemented!()
+        }
+        fn tab_text_color(&self) -> String {
+            unimplemented!()
+        }
         fn on_new_window_cmd(&self) -> Option<String> {
             None
         }
@@ -189,6 +271,9 @@ pub(crate) mod tests {
         fn disable_tile_drag(&self) -> bool {
             false
         }
+        fn reorder_tile_drag(&self) -> bool {
+            false
+        }
         fn disable_window_snap(&self) -> bool {
             false
         }
@@ -202,10 +287,21 @@ pub(crate) mod tests {
             if window.res_class == Some("ShouldGoToTag2".to_string()) {
                 window.tag = Some(2);
                 true
+            } else if window.res_class == Some("ShouldBeScratchPad".to_string()) {
+                window.spawn_scratchpad = Some("term".into());
+                true
             } else {
                 false
             }
         }
+        fn reapply_predefined_window(
+            &self,
+            state: &mut State,
+            window: &mut Window,
+            previous: &Window,
+        ) -> bool {
+            window.res_class != previous.res_class && self.setup_predefined_window(state, window)
+        }
         fn sloppy_mouse_follows_focus(&self) -> bool {
             true
         }
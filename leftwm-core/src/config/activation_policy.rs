@@ -0,0 +1,14 @@
+use serde::{Deserialize, Serialize};
+
+/// What happens when a client asks for one of its windows to be activated, eg. through
+/// `_NET_ACTIVE_WINDOW`.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ActivationPolicy {
+    /// Focus the window if its tag is shown, else mark it urgent.
+    Focus,
+    /// Show the tag of the window and focus it.
+    SwitchTag,
+    #[default]
+    Urgent,
+    Ignore,
+}
@@ -0,0 +1,181 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::{Size, Xyhw};
+
+/// Distance between the windows placed by `Cascade`.
+const CASCADE_STEP: i32 = 32;
+
+/// Where new floating windows are put on their workspace. Dialogs are centered on their parent
+/// window instead.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum FloatingPlacement {
+    #[default]
+    Center,
+    /// Centered on the mouse cursor, as far as the workspace allows.
+    UnderCursor,
+    /// Diagonally below the floating windows already on the workspace, starting over in the top
+    /// left corner when the window would not fit anymore.
+    Cascade,
+    /// Where the window overlaps least with the floating windows already on the workspace.
+    Smart,
+}
+
+impl FloatingPlacement {
+    /// Moves `window` into `area`. `others` are the floating windows already in the area.
+    #[must_use]
+    pub fn place(self, mut window: Xyhw, area: Xyhw, cursor: (i32, i32), others: &[Xyhw]) -> Xyhw {
+        let max_x = (area.x() + area.w() - window.w()).max(area.x());
+        let max_y = (area.y() + area.h() - window.h()).max(area.y());
+        let center = (
+            area.x() + (area.w() - window.w()) / 2,
+            area.y() + (area.h() - window.h()) / 2,
+        );
+        let (x, y) = match self {
+            Self::Center => center,
+            Self::UnderCursor => (
+                (cursor.0 - window.w() / 2).clamp(area.x(), max_x),
+                (cursor.1 - window.h() / 2).clamp(area.y(), max_y),
+            ),
+            Self::Cascade => {
+                let fitting =
+                    ((max_x - area.x()) / CASCADE_STEP).min((max_y - area.y()) / CASCADE_STEP);
+                let step = others.len() as i32 % (fitting + 1) * CASCADE_STEP;
+                (area.x() + step, area.y() + step)
+            }
+            Self::Smart => {
+                // Try the center, the edges of the area and the edges of the other windows.
+                let xs: Vec<i32> = [center.0, area.x(), max_x]
+                    .into_iter()
+                    .chain(others.iter().map(|o| o.x() + o.w()))
+                    .chain(others.iter().map(|o| o.x() - window.w()))
+                    .filter(|x| (area.x()..=max_x).contains(x))
+                    .collect();
+                let ys: Vec<i32> = [center.1, area.y(), max_y]
+                    .into_iter()
+                    .chain(others.iter().map(|o| o.y() + o.h()))
+                    .chain(others.iter().map(|o| o.y() - window.h()))
+                    .filter(|y| (area.y()..=max_y).contains(y))
+                    .collect();
+                let covered = |x: i32, y: i32| -> i64 {
+                    let mut candidate = window;
+                    candidate.set_x(x);
+                    candidate.set_y(y);
+                    others.iter().map(|o| overlap(&candidate, o)).sum()
+                };
+                ys.iter()
+                    .flat_map(|y| xs.iter().map(move |x| (*x, *y)))
+                    .min_by_key(|(x, y)| covered(*x, *y))
+                    .unwrap_or(center)
+            }
+        };
+        window.set_x(x);
+        window.set_y(y);
+        window
+    }
+}
+
+/// Size and position of a floating window set by a window rule. Positions are relative to the
+/// workspace, ratios are taken of the size of the workspace. Unset values are left to the
+/// placement policy.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq)]
+pub struct FloatingGeometry {
+    pub x: Option<Size>,
+    pub y: Option<Size>,
+    pub width: Option<Size>,
+    pub height: Option<Size>,
+}
+
+impl FloatingGeometry {
+    /// Changes the size of `window` to the one set.
+    #[must_use]
+    pub fn resize(&self, mut window: Xyhw, area: Xyhw) -> Xyhw {
+        if let Some(width) = self.width {
+            window.set_w(width.into_absolute(area.w()));
+        }
+        if let Some(height) = self.height {
+            window.set_h(height.into_absolute(area.h()));
+        }
+        window
+    }
+
+    /// Moves the already placed `window` to the position set.
+    #[must_use]
+    pub fn position(&self, mut window: Xyhw, area: Xyhw) -> Xyhw {
+        if let Some(x) = self.x {
+            window.set_x(area.x() + x.into_absolute(area.w()));
+        }
+        if let Some(y) = self.y {
+            window.set_y(area.y() + y.into_absolute(area.h()));
+        }
+        window
+    }
+}
+
+/// The area two rectangles have in common.
+fn overlap(a: &Xyhw, b: &Xyhw) -> i64 {
+    let w = (a.x() + a.w()).min(b.x() + b.w()) - a.x().max(b.x());
+    let h = (a.y() + a.h()).min(b.y() + b.h()) - a.y().max(b.y());
+    i64::from(w.max(0)) * i64::from(h.max(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::XyhwBuilder;
+
+    fn xyhw(x: i32, y: i32, w: i32, h: i32) -> Xyhw {
+        XyhwBuilder {
+            x,
+            y,
+            h,
+            w,
+            ..XyhwBuilder::default()
+        }
+        .into()
+    }
+
+    #[test]
+    fn windows_are_placed_by_the_policy() {
+        let area = xyhw(0, 0, 800, 600);
+        let window = xyhw(0, 0, 400, 300);
+        let place = |placement: FloatingPlacement, cursor, others: &[Xyhw]| {
+            let placed = placement.place(window, area, cursor, others);
+            (placed.x(), placed.y())
+        };
+
+        assert_eq!(place(FloatingPlacement::Center, (0, 0), &[]), (200, 150));
+        assert_eq!(
+            place(FloatingPlacement::UnderCursor, (300, 200), &[]),
+            (100, 50)
+        );
+        // The window stays inside of the area.
+        assert_eq!(
+            place(FloatingPlacement::UnderCursor, (790, 10), &[]),
+            (400, 0)
+        );
+
+        let others = [window, window];
+        assert_eq!(place(FloatingPlacement::Cascade, (0, 0), &others), (64, 64));
+        // Starts over once the window would leave the area.
+        let others = [window; 11];
+        assert_eq!(place(FloatingPlacement::Cascade, (0, 0), &others), (32, 32));
+
+        assert_eq!(place(FloatingPlacement::Smart, (0, 0), &[]), (200, 150));
+        let others = [xyhw(0, 0, 400, 600)];
+        assert_eq!(place(FloatingPlacement::Smart, (0, 0), &others), (400, 150));
+    }
+
+    #[test]
+    fn geometry_overrides_size_and_position() {
+        let area = xyhw(100, 0, 800, 600);
+        let geometry = FloatingGeometry {
+            x: Some(Size::Pixel(10)),
+            width: Some(Size::Ratio(0.5)),
+            ..FloatingGeometry::default()
+        };
+        let window = geometry.resize(xyhw(0, 0, 200, 300), area);
+        assert_eq!((window.w(), window.h()), (400, 300));
+        let window = geometry.position(xyhw(200, 150, 400, 300), area);
+        assert_eq!((window.x(), window.y()), (110, 150));
+    }
+}
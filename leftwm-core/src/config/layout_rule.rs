@@ -0,0 +1,43 @@
+use serde::{Deserialize, Serialize};
+
+use crate::layouts::Layout;
+use crate::models::Workspace;
+
+/// Picks a layout for a tag from the number of its tiled windows and the shape of the workspace
+/// showing it. Conditions which are not set always match.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq)]
+#[serde(default)]
+pub struct LayoutRule {
+    pub min_windows: Option<usize>,
+    pub max_windows: Option<usize>,
+    pub orientation: Option<Orientation>,
+    pub layout: Layout,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    /// At least as wide as high.
+    Landscape,
+    /// Higher than wide.
+    Portrait,
+}
+
+impl Orientation {
+    pub fn of(workspace: &Workspace) -> Self {
+        if workspace.xyhw.h() > workspace.xyhw.w() {
+            Self::Portrait
+        } else {
+            Self::Landscape
+        }
+    }
+}
+
+impl LayoutRule {
+    pub fn matches(&self, workspace: &Workspace, window_count: usize) -> bool {
+        self.min_windows.map_or(true, |min| window_count >= min)
+            && self.max_windows.map_or(true, |max| window_count <= max)
+            && self.orientation.map_or(true, |orientation| {
+                orientation == Orientation::of(workspace)
+            })
+    }
+}
@@ -0,0 +1,36 @@
+use serde::{Deserialize, Serialize};
+
+use crate::layouts::Layout;
+
+use super::InsertBehavior;
+
+/// Settings of a single tag. Settings which are not set fall back to the global ones.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct TagConfig {
+    pub label: String,
+    /// The layout the tag starts with, instead of the first of the allowed layouts.
+    pub layout: Option<Layout>,
+    /// The layouts `NextLayout` and `PreviousLayout` cycle through on this tag.
+    pub layouts: Option<Vec<Layout>>,
+    pub main_width: Option<u8>,
+    /// Margin multiplier of the windows opened on this tag.
+    pub margin_multiplier: Option<f32>,
+    pub insert_behavior: Option<InsertBehavior>,
+    /// The output a workspace showing this tag is preferably on, when the workspace is created.
+    pub output: Option<String>,
+}
+
+impl TagConfig {
+    /// The allowed layouts of the tag, unless they are left to the workspace.
+    pub fn layouts(&self) -> Option<&Vec<Layout>> {
+        self.layouts.as_ref().filter(|layouts| !layouts.is_empty())
+    }
+
+    /// The layout the tag starts with.
+    pub fn initial_layout(&self) -> Option<&Layout> {
+        self.layout
+            .as_ref()
+            .or_else(|| self.layouts().and_then(|layouts| layouts.first()))
+    }
+}
@@ -60,9 +60,21 @@ pub enum DisplayAction {
     /// Used to let the WM know of the current displayed tag changes.
     SetCurrentTags(Option<TagId>),
 
+    /// Used to let the WM know of the labels of the tags after they changed.
+    SetDesktopNames(Vec<String>),
+
     /// Used to let the WM know of the tag for a given window.
     SetWindowTag(WindowHandle, Option<TagId>),
 
+    /// Used to let the WM know of all tags of a window which is on more than one tag.
+    SetWindowTags(WindowHandle, Vec<TagId>),
+
+    /// Mark a window as demanding attention, or not.
+    SetWindowUrgency(WindowHandle, bool),
+
+    /// Draw the border of a window in this colour while it is not focused.
+    SetWindowBorderColor(WindowHandle, String),
+
     /// Tell the DM to return to normal mode if it is not (ie resize a
     /// window or moving a window).
     NormalMode,
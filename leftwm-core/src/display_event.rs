@@ -1,5 +1,5 @@
 use super::{models::Screen, models::Window, models::WindowHandle, Button, ModMask};
-use crate::models::WindowChange;
+use crate::models::{TagId, WindowChange};
 use crate::Command;
 
 #[allow(clippy::large_enum_variant)]
@@ -19,5 +19,7 @@ pub enum DisplayEvent {
     ScreenCreate(Screen),
     SendCommand(Command),
     ConfigureXlibWindow(WindowHandle),
+    ActivateWindow(WindowHandle, bool), // Activation request, true if it may take the focus.
+    ViewDesktop(TagId),                 // A pager asks to show a tag, counting through all tags.
     ChangeToNormalMode,
 }
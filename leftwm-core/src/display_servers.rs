@@ -3,6 +3,7 @@ mod mock_display_server;
 
 use crate::config::Config;
 use crate::display_action::DisplayAction;
+use crate::models::TabBar;
 use crate::models::Window;
 use crate::models::WindowHandle;
 use crate::models::Workspace;
@@ -31,6 +32,10 @@ pub trait DisplayServer {
 
     fn update_workspaces(&self, _focused: Option<&Workspace>) {}
 
+    /// Shows the tab bars of the `Tabbed` and `Stacked` layouts, bars which are not in the
+    /// list anymore are hidden.
+    fn update_tab_bars(&mut self, _tab_bars: Vec<TabBar>) {}
+
     fn execute_action(&mut self, _act: DisplayAction) -> Option<DisplayEvent> {
         None
     }
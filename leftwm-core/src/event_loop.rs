@@ -119,8 +119,14 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         self.update_windows();
 
         match self.state.mode {
-            // When (resizing / moving) only deal with the single window.
-            Mode::ResizingWindow(h) | Mode::MovingWindow(h) => {
+            // When (resizing / moving) a floating window only deal with the single window.
+            Mode::ResizingWindow(h) | Mode::MovingWindow(h)
+                if self
+                    .state
+                    .windows
+                    .iter()
+                    .any(|w| w.handle == h && w.floating()) =>
+            {
                 if let Some(window) = self.state.windows.iter().find(|w| w.handle == h) {
                     self.display_server.update_windows(vec![window]);
                 }
@@ -130,6 +136,17 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                 self.display_server.update_windows(windows);
             }
         }
+
+        let tab_bars = self
+            .state
+            .workspaces
+            .iter()
+            .filter_map(|ws| {
+                let tag = self.state.tags.get(ws.tag?)?;
+                tag.tab_bar(&self.state.windows, ws, &self.state.workspaces)
+            })
+            .collect();
+        self.display_server.update_tab_bars(tab_bars);
     }
 
     fn execute_command(&mut self, command: &Command) -> EventResponse {
@@ -2,8 +2,10 @@ pub mod command_handler;
 pub mod display_event_handler;
 mod focus_handler;
 mod goto_tag_handler;
+mod minimize_handler;
 mod mouse_combo_handler;
 mod screen_create_handler;
+mod tag_handler;
 mod window_handler;
 mod window_move_handler;
 mod window_resize_handler;
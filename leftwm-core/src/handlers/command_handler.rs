@@ -9,7 +9,7 @@ use super::*;
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
 use crate::layouts::Layout;
-use crate::models::{LayoutMode, TagId, WindowState};
+use crate::models::{LayoutMode, SnapPosition, SplitTree, TagId, WindowState, Xyhw, XyhwBuilder};
 use crate::state::State;
 use crate::utils::helpers;
 use crate::utils::helpers::relative_find;
@@ -35,16 +35,24 @@ macro_rules! move_focus_common_vars {
         let handle = $state.focus_manager.window(&$state.windows)?.handle;
         let tag_id = $state.focus_manager.tag(0)?;
         let tag = $state.tags.get(tag_id)?;
-        let layout = Some(tag.layout);
-
-        let for_active_workspace =
-            |x: &Window| -> bool { x.tag == Some(tag_id) && x.is_managed() };
+        let layout = Some(tag.layout.clone());
+        let workspaces = &$state.workspaces;
+        let for_active_workspace = |x: &Window| -> bool { arranged_with(workspaces, tag_id, x) };
 
         let to_reorder = helpers::vec_extract(&mut $state.windows, for_active_workspace);
-        $func($state, handle, layout, to_reorder, $($arg),*)
+        $func($state, handle, layout.as_ref(), to_reorder, $($arg),*)
     }};
 }
 
+/// Whether the window is arranged along with the windows of a tag, by the workspace showing the
+/// tag, or on the tag itself if it isn't shown.
+fn arranged_with(workspaces: &[Workspace], tag: TagId, window: &Window) -> bool {
+    match workspaces.iter().find(|ws| ws.has_tag(&tag)) {
+        Some(ws) => ws.manages(window, workspaces),
+        None => window.has_tag(&tag) && window.is_managed() && !window.is_minimized(),
+    }
+}
+
 fn process_internal<C: Config, SERVER: DisplayServer>(
     manager: &mut Manager<C, SERVER>,
     command: &Command,
@@ -55,9 +63,7 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::AttachScratchPad { window, scratchpad } => {
             scratchpad_handler::attach_scratchpad(*window, scratchpad, manager)
         }
-        Command::ReleaseScratchPad { window, tag } => {
-            scratchpad_handler::release_scratchpad(window.clone(), *tag, manager)
-        }
+        Command::ReleaseScratchPad { window, tag } => release_scratchpad(manager, window, *tag),
 
         Command::NextScratchPadWindow { scratchpad } => {
             scratchpad_handler::cycle_scratchpad_window(manager, scratchpad, Direction::Forward)
@@ -68,8 +74,17 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
 
         Command::ToggleFullScreen => toggle_state(state, WindowState::Fullscreen),
         Command::ToggleSticky => toggle_state(state, WindowState::Sticky),
-
+        Command::ToggleMaximize => toggle_maximize(state),
+        Command::MinimizeWindow => minimize_window(state),
+        Command::RestoreLastMinimized => restore_last_minimized(state),
+        Command::RestoreWindow(selector) => restore_window(state, selector),
+
+        // The focused window goes to a tag counted among the tags of the focused workspace,
+        // windows given by their handle are sent by pagers which count through all tags.
+        Command::SendWindowToTag { window: None, tag } => move_to_scoped_tag(manager, *tag),
         Command::SendWindowToTag { window, tag } => move_to_tag(*window, *tag, manager),
+        Command::ToggleWindowTag(tag) => toggle_window_tag(state, *tag),
+        Command::SetWindowTags(tags) => set_window_tags(state, tags),
         Command::MoveWindowToNextTag { follow } => move_to_tag_relative(manager, *follow, 1),
         Command::MoveWindowToPreviousTag { follow } => move_to_tag_relative(manager, *follow, -1),
         Command::MoveWindowToLastWorkspace => move_to_last_workspace(state),
@@ -79,20 +94,32 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::MoveWindowDown => move_focus_common_vars!(move_window_change(state, 1)),
         Command::MoveWindowTop { swap } => move_focus_common_vars!(move_window_top(state, *swap)),
         Command::SwapWindowTop { swap } => move_focus_common_vars!(swap_window_top(state, *swap)),
+        Command::MoveWindowDirection(direction) => move_window_direction(manager, *direction),
 
         Command::GoToTag { tag, swap } => goto_tag(state, *tag, *swap),
         Command::ReturnToLastTag => return_to_last_tag(state),
+        Command::ToggleTagView(tag) => toggle_tag_view(state, *tag),
+        Command::ViewOnlyTag(tag) => view_only_tag(state, *tag),
+        Command::AddTag(label) => Some(add_tag(state, label)),
+        Command::RemoveTag { tag, target } => remove_tag(state, *tag, *target),
+        Command::RenameTag { tag, label } => rename_tag(state, *tag, label),
+        Command::MoveTag { tag, position } => move_tag(state, *tag, *position),
 
         Command::CloseWindow => close_window(state),
         Command::SwapScreens => swap_tags(state),
         Command::NextLayout => next_layout(state),
         Command::PreviousLayout => previous_layout(state),
 
-        Command::SetLayout(layout) => set_layout(*layout, state),
+        Command::SetLayout(layout) => set_layout(layout.clone(), state),
+        Command::ResetLayout => reset_layout(state),
 
         Command::FloatingToTile => floating_to_tile(state),
         Command::TileToFloating => tile_to_floating(state),
         Command::ToggleFloating => toggle_floating(state),
+        Command::MoveFloatingBy(dx, dy) => move_floating_by(state, *dx, *dy),
+        Command::ResizeFloatingBy(dw, dh) => resize_floating_by(state, *dw, *dh),
+        Command::CenterFloating => center_floating(state),
+        Command::SnapFloating(position) => snap_floating(state, *position),
 
         Command::FocusNextTag => focus_tag_change(state, 1),
         Command::FocusPreviousTag => focus_tag_change(state, -1),
@@ -100,27 +127,11 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
         Command::FocusWindowUp => move_focus_common_vars!(focus_window_change(state, -1)),
         Command::FocusWindowDown => move_focus_common_vars!(focus_window_change(state, 1)),
         Command::FocusWindowTop { swap } => focus_window_top(state, *swap),
+        Command::FocusWindowDirection(direction) => focus_window_direction(state, *direction),
         Command::FocusWorkspaceNext => focus_workspace_change(state, 1),
         Command::FocusWorkspacePrevious => focus_workspace_change(state, -1),
 
-        Command::SoftReload => {
-            // Make sure the currently focused window is saved for the tag.
-            if let Some((handle, Some(tag))) = state
-                .focus_manager
-                .window(&state.windows)
-                .map(|w| (w.handle, w.tag))
-            {
-                let old_handle = state
-                    .focus_manager
-                    .tags_last_window
-                    .entry(tag)
-                    .or_insert(handle);
-                *old_handle = handle;
-            }
-            manager.config.save_state(&manager.state);
-            manager.hard_reload();
-            None
-        }
+        Command::SoftReload => soft_reload(manager),
         Command::HardReload => {
             manager.hard_reload();
             None
@@ -130,6 +141,21 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
 
         Command::IncreaseMainWidth(delta) => change_main_width(state, *delta, 1),
         Command::DecreaseMainWidth(delta) => change_main_width(state, *delta, -1),
+        Command::IncreaseMainCount => change_main_count(state, 1),
+        Command::DecreaseMainCount => change_main_count(state, -1),
+        Command::IncreaseWindowWeight(delta) => change_window_weight(state, *delta),
+        Command::DecreaseWindowWeight(delta) => change_window_weight(state, -*delta),
+        Command::PreselectSplit(direction) => preselect_split(state, *direction),
+        Command::SetSplitRatio(percent) => set_split_ratio(state, *percent),
+        Command::RotateSplitTree => update_split_tree(state, SplitTree::rotate),
+        Command::BalanceSplitTree => update_split_tree(state, SplitTree::balance),
+        Command::FlipSplitTree(axis) => update_split_tree(state, |tree| tree.flip(*axis)),
+        Command::ScrollLeft => scroll_viewport(state, false),
+        Command::ScrollRight => scroll_viewport(state, true),
+        Command::MoveColumnLeft => move_focus_common_vars!(move_window_change(state, -1)),
+        Command::MoveColumnRight => move_focus_common_vars!(move_window_change(state, 1)),
+        Command::IncreaseColumnWidth(delta) => change_column_width(state, *delta),
+        Command::DecreaseColumnWidth(delta) => change_column_width(state, delta.saturating_neg()),
         Command::SetMarginMultiplier(multiplier) => set_margin_multiplier(state, *multiplier),
         Command::SendWorkspaceToTag(ws_index, tag_index) => {
             Some(send_workspace_to_tag(state, *ws_index, *tag_index))
@@ -139,6 +165,26 @@ fn process_internal<C: Config, SERVER: DisplayServer>(
     }
 }
 
+fn soft_reload<C: Config, SERVER: DisplayServer>(manager: &mut Manager<C, SERVER>) -> Option<bool> {
+    let state = &mut manager.state;
+    // Make sure the currently focused window is saved for the tag.
+    if let Some((handle, Some(tag))) = state
+        .focus_manager
+        .window(&state.windows)
+        .map(|w| (w.handle, w.tag))
+    {
+        let old_handle = state
+            .focus_manager
+            .tags_last_window
+            .entry(tag)
+            .or_insert(handle);
+        *old_handle = handle;
+    }
+    manager.config.save_state(&manager.state);
+    manager.hard_reload();
+    None
+}
+
 fn toggle_state(state: &mut State, window_state: WindowState) -> Option<bool> {
     let window = state.focus_manager.window(&state.windows)?;
     let handle = window.handle;
@@ -152,6 +198,90 @@ fn toggle_state(state: &mut State, window_state: WindowState) -> Option<bool> {
     }
 }
 
+fn toggle_maximize(state: &mut State) -> Option<bool> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let window = state.windows.iter_mut().find(|w| w.handle == handle)?;
+    let maximize = !window.is_maximized();
+    let mut states = window.states();
+    states.retain(|s| s != &WindowState::MaximizedVert && s != &WindowState::MaximizedHorz);
+    if maximize {
+        states.extend([WindowState::MaximizedVert, WindowState::MaximizedHorz]);
+    }
+    window.set_states(states);
+    for window_state in [WindowState::MaximizedVert, WindowState::MaximizedHorz] {
+        let act = DisplayAction::SetState(handle, maximize, window_state);
+        state.actions.push_back(act);
+    }
+    state.sort_windows();
+    Some(true)
+}
+
+fn minimize_window(state: &mut State) -> Option<bool> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    Some(state.minimize_window(&handle))
+}
+
+fn toggle_window_tag(state: &mut State, tag: TagId) -> Option<bool> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let tag = state.scoped_tag(tag)?;
+    Some(state.toggle_window_tag(&handle, tag))
+}
+
+fn set_window_tags(state: &mut State, tags: &[TagId]) -> Option<bool> {
+    let handle = state.focus_manager.window(&state.windows)?.handle;
+    let tags: Vec<TagId> = tags
+        .iter()
+        .map(|tag| state.scoped_tag(*tag))
+        .collect::<Option<_>>()?;
+    Some(state.set_window_tags(&handle, &tags))
+}
+
+fn restore_last_minimized(state: &mut State) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    let handle = *state.tags.get(tag_id)?.minimized.last()?;
+    Some(state.restore_window(&handle))
+}
+
+/// Restores a minimized window of the focused tag, selected either by its 1-based position on
+/// the restore stack or by its `WM_CLASS`.
+fn restore_window(state: &mut State, selector: &str) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    let minimized = &state.tags.get(tag_id)?.minimized;
+    let handle = match selector.parse::<usize>() {
+        Ok(index) => *minimized.get(index.checked_sub(1)?)?,
+        Err(_) => *minimized.iter().rev().find(|&handle| {
+            state.windows.iter().any(|w| {
+                &w.handle == handle
+                    && (w.res_name.as_deref() == Some(selector)
+                        || w.res_class.as_deref() == Some(selector))
+            })
+        })?,
+    };
+    Some(state.restore_window(&handle))
+}
+
+/// Releases a scratchpad window to a tag counted among the tags of the focused workspace.
+fn release_scratchpad<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    window: &ReleaseScratchPadOption,
+    tag: Option<TagId>,
+) -> Option<bool> {
+    let tag = match tag {
+        Some(tag) => Some(manager.state.scoped_tag(tag)?),
+        None => None,
+    };
+    scratchpad_handler::release_scratchpad(window.clone(), tag, manager)
+}
+
+/// Sends the focused window to a tag counted among the tags of the focused workspace.
+fn move_to_scoped_tag<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    tag: TagId,
+) -> Option<bool> {
+    let tag = manager.state.scoped_tag(tag)?;
+    move_to_tag(None, tag, manager)
+}
+
 fn move_to_tag<C: Config, SERVER: DisplayServer>(
     window: Option<WindowHandle>,
     tag_id: TagId,
@@ -182,9 +312,8 @@ fn move_to_tag<C: Config, SERVER: DisplayServer>(
         .iter_mut()
         .find(|w| w.handle == handle)?;
 
-    window.untag();
     window.set_floating(false);
-    window.tag(&tag.id);
+    window.set_tag(Some(tag.id));
     window.apply_margin_multiplier(margin_multiplier);
     let act = DisplayAction::SetWindowTag(window.handle, Some(tag.id));
     manager.state.actions.push_back(act);
@@ -213,13 +342,9 @@ fn move_to_tag_relative<C: Config, SERVER: DisplayServer>(
     follow: bool,
     delta: i32,
 ) -> Option<bool> {
-    // Map indexing from 1..len to 0..(len - 1)
-    let current_tag = manager.state.focus_manager.tag(0).unwrap_or_default() - 1;
-    // apply euclidean division reminder to the result of offseting to wrap around tags vector
-    // and add 1 to remap back to 1..len indexing
-    let tags_len = manager.state.tags.normal().len() as isize;
-    let desired_tag = (current_tag as isize + delta as isize).rem_euclid(tags_len) + 1;
-    let desired_tag = desired_tag as usize;
+    let current_tag = manager.state.focus_manager.tag(0)?;
+    let tags = manager.state.scoped_tags();
+    let desired_tag = *relative_find(&tags, |tag| *tag == current_tag, delta, true)?;
 
     move_to_tag(None, desired_tag, manager);
     if follow {
@@ -246,19 +371,67 @@ fn move_window_to_workspace_change<C: Config, SERVER: DisplayServer>(
 }
 
 fn goto_tag(state: &mut State, input_tag: TagId, current_tag_swap: bool) -> Option<bool> {
+    let input_tag = state.scoped_tag(input_tag)?;
     let current_tag = state.focus_manager.tag(0).unwrap_or_default();
-    let previous_tag = state.focus_manager.tag(1).unwrap_or_default();
     let destination_tag = if current_tag_swap && current_tag == input_tag {
-        previous_tag
+        previous_tag(state)
     } else {
         input_tag
     };
     state.goto_tag_handler(destination_tag)
 }
 
+fn toggle_tag_view(state: &mut State, tag: TagId) -> Option<bool> {
+    let tag = state.scoped_tag(tag)?;
+    state.toggle_tag_view(tag)
+}
+
+fn view_only_tag(state: &mut State, tag: TagId) -> Option<bool> {
+    let tag = state.scoped_tag(tag)?;
+    state.goto_tag_handler(tag)
+}
+
+/// Appends a tag, which always changes what the bars show.
+fn add_tag(state: &mut State, label: &str) -> bool {
+    state.add_tag(label);
+    true
+}
+
+fn remove_tag(state: &mut State, tag: TagId, target: Option<TagId>) -> Option<bool> {
+    let tag = state.scoped_tag(tag)?;
+    let target = match target {
+        Some(target) => Some(state.scoped_tag(target)?),
+        None => None,
+    };
+    Some(state.remove_tag(tag, target))
+}
+
+fn rename_tag(state: &mut State, tag: TagId, label: &str) -> Option<bool> {
+    let tag = state.scoped_tag(tag)?;
+    Some(state.rename_tag(tag, label))
+}
+
+fn move_tag(state: &mut State, tag: TagId, position: usize) -> Option<bool> {
+    let tag = state.scoped_tag(tag)?;
+    let position = state.scoped_tag(position)?;
+    Some(state.move_tag(tag, position))
+}
+
 fn return_to_last_tag(state: &mut State) -> Option<bool> {
-    let previous_tag = state.focus_manager.tag(1).unwrap_or_default();
-    state.goto_tag_handler(previous_tag)
+    state.goto_tag_handler(previous_tag(state))
+}
+
+/// The tag focused before the current one among the tags of the focused workspace.
+fn previous_tag(state: &State) -> TagId {
+    let tags = state.scoped_tags();
+    state
+        .focus_manager
+        .tag_history
+        .iter()
+        .skip(1)
+        .find(|tag| tags.contains(tag))
+        .copied()
+        .unwrap_or_default()
 }
 
 fn focus_window(state: &mut State, param: &str) -> Option<bool> {
@@ -315,11 +488,11 @@ fn focus_window_by_class(state: &mut State, window_class: &str) -> Option<bool>
     match state
         .focus_manager
         .workspace(&state.workspaces)
-        .map(|ws| ws.layout)
+        .map(|ws| ws.layout.clone())
     {
         Some(layout) if layout == Layout::Monocle || layout == Layout::MainAndDeck => {
             let mut windows = helpers::vec_extract(&mut state.windows, |w| {
-                w.has_tag(&tag_id) && w.is_managed() && !w.floating()
+                w.has_tag(&tag_id) && w.is_managed() && !w.is_minimized() && !w.floating()
             });
 
             let cycle = |wins: &mut Vec<Window>, s: &mut State| {
@@ -353,9 +526,8 @@ fn focus_window_by_class(state: &mut State, window_class: &str) -> Option<bool>
 /// A delta of 1 means "next tag", a delta of -1 means "previous tag".
 fn focus_tag_change(state: &mut State, delta: i8) -> Option<bool> {
     let current_tag = state.focus_manager.tag(0)?;
-    let tags = state.tags.normal();
-    let relative_tag_id = relative_find(tags, |tag| tag.id == current_tag, i32::from(delta), true)
-        .map(|tag| tag.id)?;
+    let tags = state.scoped_tags();
+    let relative_tag_id = *relative_find(&tags, |tag| *tag == current_tag, i32::from(delta), true)?;
     state.goto_tag_handler(relative_tag_id)
 }
 
@@ -363,6 +535,15 @@ fn swap_tags(state: &mut State) -> Option<bool> {
     if state.workspaces.len() >= 2 && state.focus_manager.workspace_history.len() >= 2 {
         let hist_a = *state.focus_manager.workspace_history.get(0)?;
         let hist_b = *state.focus_manager.workspace_history.get(1)?;
+        // Tags stay on their output when every output has tags of its own.
+        let (ws_a, ws_b) = (state.workspaces.get(hist_a)?, state.workspaces.get(hist_b)?);
+        let fits = |tag: Option<TagId>, output: &str| {
+            tag.and_then(|tag| state.tags.get(tag))
+                .map_or(true, |tag| tag.belongs_to(output))
+        };
+        if !fits(ws_a.tag, &ws_b.output) || !fits(ws_b.tag, &ws_a.output) {
+            return None;
+        }
         //Update workspace tags
         let mut temp = None;
         std::mem::swap(&mut state.workspaces.get_mut(hist_a)?.tag, &mut temp);
@@ -397,21 +578,23 @@ fn move_to_last_workspace(state: &mut State) -> Option<bool> {
         let index = *state.focus_manager.workspace_history.get(1)?;
         let wp_tags = state.workspaces.get(index)?.tag;
         let window = state.focus_manager.window_mut(&mut state.windows)?;
-        window.tag = wp_tags;
+        window.set_tag(wp_tags);
         return Some(true);
     }
     None
 }
 
 fn next_layout(state: &mut State) -> Option<bool> {
-    let workspace = state.focus_manager.workspace_mut(&mut state.workspaces)?;
-    let layout = state.layout_manager.next_layout(workspace);
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let tag = workspace.tag.and_then(|id| state.tags.get(id));
+    let layout = state.layout_manager.next_layout(workspace, tag);
     set_layout(layout, state)
 }
 
 fn previous_layout(state: &mut State) -> Option<bool> {
-    let workspace = state.focus_manager.workspace_mut(&mut state.workspaces)?;
-    let layout = state.layout_manager.previous_layout(workspace);
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let tag = workspace.tag.and_then(|id| state.tags.get(id));
+    let layout = state.layout_manager.previous_layout(workspace, tag);
     set_layout(layout, state)
 }
 
@@ -437,13 +620,17 @@ fn set_layout(layout: Layout, state: &mut State) -> Option<bool> {
                 to_focus = state
                     .windows
                     .iter()
-                    .find(|w| w.has_tag(&tag_id) && w.is_managed() && !w.floating())
+                    .find(|w| {
+                        w.has_tag(&tag_id) && w.is_managed() && !w.is_minimized() && !w.floating()
+                    })
                     .cloned();
             } else if layout == Layout::MainAndDeck {
                 let tags_windows = state
                     .windows
                     .iter()
-                    .filter(|w| w.has_tag(&tag_id) && w.is_managed() && !w.floating())
+                    .filter(|w| {
+                        w.has_tag(&tag_id) && w.is_managed() && !w.is_minimized() && !w.floating()
+                    })
                     .collect::<Vec<&Window>>();
                 if let (Some(mw), Some(tdw)) = (tags_windows.get(0), tags_windows.get(1)) {
                     // If the focused window is the main or the top of the deck, we don't do
@@ -464,14 +651,24 @@ fn set_layout(layout: Layout, state: &mut State) -> Option<bool> {
         }
     }
     let workspace = state.focus_manager.workspace_mut(&mut state.workspaces)?;
-    workspace.layout = layout;
+    let main_width = layout.main_width();
+    workspace.layout = layout.clone();
 
     if state.layout_manager.mode == LayoutMode::Workspace {
-        workspace.main_width_percentage = layout.main_width();
+        workspace.main_width_percentage = main_width;
     }
 
     let tag = state.tags.get_mut(tag_id)?;
-    tag.set_layout(layout, layout.main_width());
+    tag.set_layout(layout, main_width);
+    tag.layout_pinned = true;
+    Some(true)
+}
+
+/// Hands the layout of the focused tag back to the layout rules.
+fn reset_layout(state: &mut State) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    let tag = state.tags.get_mut(tag_id)?;
+    tag.layout_pinned = false;
     Some(true)
 }
 
@@ -533,18 +730,84 @@ fn toggle_floating(state: &mut State) -> Option<bool> {
     }
 }
 
+/// Changes the geometry of the focused floating window through its floating offsets. `change`
+/// gets the geometry of the window and the area of its workspace which is not taken by docks and
+/// gutters. The window does not shrink below its minimum size.
+fn change_floating(state: &mut State, change: impl FnOnce(&mut Xyhw, Xyhw)) -> Option<bool> {
+    let window = state.focus_manager.window(&state.windows)?;
+    if !window.floating() || window.is_fullscreen() || window.is_maximized() {
+        return None;
+    }
+    let workspace = state
+        .workspaces
+        .iter()
+        .find(|ws| ws.owns(window, &state.workspaces))?;
+    let container = workspace.xyhw;
+    let area = XyhwBuilder {
+        x: workspace.x(),
+        y: workspace.y(),
+        h: workspace.height(),
+        w: workspace.width(),
+        ..XyhwBuilder::default()
+    }
+    .into();
+    let handle = window.handle;
+    let window = state.windows.iter_mut().find(|w| w.handle == handle)?;
+    let mut xyhw = window
+        .get_floating_offsets()
+        .map_or_else(|| window.exact_xyhw(), |offsets| container + offsets);
+    change(&mut xyhw, area);
+    window.set_floating_offsets(Some(xyhw - container));
+    // The sizes below the minimum are not shown, so they are not kept either.
+    let border = window.border() * 2;
+    xyhw.set_w(window.width() + border);
+    xyhw.set_h(window.height() + border);
+    window.set_floating_offsets(Some(xyhw - container));
+    Some(true)
+}
+
+fn move_floating_by(state: &mut State, dx: i32, dy: i32) -> Option<bool> {
+    change_floating(state, |xyhw, _| {
+        xyhw.set_x(xyhw.x() + dx);
+        xyhw.set_y(xyhw.y() + dy);
+    })
+}
+
+fn resize_floating_by(state: &mut State, dw: i32, dh: i32) -> Option<bool> {
+    change_floating(state, |xyhw, _| {
+        xyhw.set_w(xyhw.w() + dw);
+        xyhw.set_h(xyhw.h() + dh);
+    })
+}
+
+fn center_floating(state: &mut State) -> Option<bool> {
+    change_floating(state, |xyhw, area| {
+        xyhw.set_x(area.x() + (area.w() - xyhw.w()) / 2);
+        xyhw.set_y(area.y() + (area.h() - xyhw.h()) / 2);
+    })
+}
+
+fn snap_floating(state: &mut State, position: SnapPosition) -> Option<bool> {
+    change_floating(state, |xyhw, area| {
+        let (x, y) = position.place(xyhw.w(), xyhw.h(), area);
+        xyhw.set_x(x);
+        xyhw.set_y(y);
+    })
+}
+
 fn move_window_change(
     state: &mut State,
     mut handle: WindowHandle,
-    layout: Option<Layout>,
+    layout: Option<&Layout>,
     mut to_reorder: Vec<Window>,
     val: i32,
 ) -> Option<bool> {
     let is_handle = |x: &Window| -> bool { x.handle == handle };
-    if layout == Some(Layout::Monocle) {
-        handle = helpers::relative_find(&to_reorder, is_handle, -val, true)?.handle;
-        _ = helpers::cycle_vec(&mut to_reorder, val);
-    } else if layout == Some(Layout::MainAndDeck) {
+    if layout == Some(&Layout::Monocle) {
+        let steps = focus_cycle_steps(&to_reorder, handle, -val);
+        handle = helpers::relative_find(&to_reorder, is_handle, -val * steps, true)?.handle;
+        _ = helpers::cycle_vec(&mut to_reorder, val * steps);
+    } else if layout == Some(&Layout::MainAndDeck) {
         if let Some(index) = to_reorder.iter().position(|x: &Window| !x.floating()) {
             let mut window_group = to_reorder.split_off(index + 1);
             if !to_reorder.iter().any(|w| w.handle == handle) {
@@ -565,7 +828,7 @@ fn move_window_change(
 fn move_window_top(
     state: &mut State,
     handle: WindowHandle,
-    _layout: Option<Layout>,
+    _layout: Option<&Layout>,
     mut to_reorder: Vec<Window>,
     swap: bool,
 ) -> Option<bool> {
@@ -596,7 +859,7 @@ fn move_window_top(
 fn swap_window_top(
     state: &mut State,
     handle: WindowHandle,
-    _layout: Option<Layout>,
+    _layout: Option<&Layout>,
     mut to_reorder: Vec<Window>,
     swap: bool,
 ) -> Option<bool> {
@@ -628,18 +891,19 @@ fn swap_window_top(
 fn focus_window_change(
     state: &mut State,
     mut handle: WindowHandle,
-    layout: Option<Layout>,
+    layout: Option<&Layout>,
     mut to_reorder: Vec<Window>,
     val: i32,
 ) -> Option<bool> {
     let is_handle = |x: &Window| -> bool { x.handle == handle };
-    if layout == Some(Layout::Monocle) {
+    if layout == Some(&Layout::Monocle) {
         // For Monocle we want to also move windows up/down
         // Not the best solution but results
         // in desired behaviour
-        handle = helpers::relative_find(&to_reorder, is_handle, -val, true)?.handle;
-        _ = helpers::cycle_vec(&mut to_reorder, val);
-    } else if layout == Some(Layout::MainAndDeck) {
+        let steps = focus_cycle_steps(&to_reorder, handle, -val);
+        handle = helpers::relative_find(&to_reorder, is_handle, -val * steps, true)?.handle;
+        _ = helpers::cycle_vec(&mut to_reorder, val * steps);
+    } else if layout == Some(&Layout::MainAndDeck) {
         let len = to_reorder.len() as i32;
         if len > 0 {
             let index = match to_reorder.iter().position(|x: &Window| !x.floating()) {
@@ -653,14 +917,134 @@ fn focus_window_change(
                 None => len.saturating_sub(1) as usize,
             };
             let window_group = &to_reorder[..=index];
-            handle = helpers::relative_find(window_group, is_handle, -val, true)?.handle;
+            let steps = focus_cycle_steps(window_group, handle, -val);
+            handle = helpers::relative_find(window_group, is_handle, -val * steps, true)?.handle;
+        }
+    } else {
+        let steps = focus_cycle_steps(&to_reorder, handle, val);
+        if let Some(new_focused) = helpers::relative_find(&to_reorder, is_handle, val * steps, true)
+        {
+            handle = new_focused.handle;
         }
-    } else if let Some(new_focused) = helpers::relative_find(&to_reorder, is_handle, val, true) {
-        handle = new_focused.handle;
     }
     state.windows.append(&mut to_reorder);
     state.handle_window_focus(&handle);
-    Some(layout == Some(Layout::Monocle))
+    Some(layout == Some(&Layout::Monocle) || state.focused_tag_is_tabbed())
+}
+
+/// How many times to step by `shift` through `windows` from `handle` to reach a window which is
+/// not skipped by focus cycling. Steps once if all the other windows are skipped.
+fn focus_cycle_steps(windows: &[Window], handle: WindowHandle, shift: i32) -> i32 {
+    let is_handle = |w: &Window| w.handle == handle;
+    (1..windows.len() as i32)
+        .find(|steps| {
+            helpers::relative_find(windows, is_handle, shift * steps, true)
+                .map_or(false, |w| !w.skip_focus_cycle)
+        })
+        .unwrap_or(1)
+}
+
+/// The visible window closest to `from` in the direction on the workspace, leaving out `handle`.
+fn window_in_direction(
+    state: &State,
+    workspace: &Workspace,
+    handle: Option<WindowHandle>,
+    from: Xyhw,
+    direction: crate::models::Direction,
+    tiled_only: bool,
+) -> Option<WindowHandle> {
+    let candidates: Vec<&Window> = state
+        .windows
+        .iter()
+        .filter(|w| {
+            Some(w.handle) != handle
+                && w.visible()
+                && workspace.manages(w, &state.workspaces)
+                && !(tiled_only && w.floating())
+        })
+        .collect();
+    let areas: Vec<Xyhw> = candidates.iter().map(|w| w.calculated_xyhw()).collect();
+    direction
+        .nearest(from, &areas)
+        .map(|i| candidates[i].handle)
+}
+
+/// The workspace next to `workspace` in the direction, eg. the one on the adjacent monitor.
+fn workspace_in_direction<'a>(
+    state: &'a State,
+    workspace: &Workspace,
+    direction: crate::models::Direction,
+) -> Option<&'a Workspace> {
+    let others: Vec<&Workspace> = state
+        .workspaces
+        .iter()
+        .filter(|ws| *ws != workspace)
+        .collect();
+    let areas: Vec<Xyhw> = others.iter().map(|ws| ws.xyhw).collect();
+    direction.nearest(workspace.xyhw, &areas).map(|i| others[i])
+}
+
+/// The focused window with its area, or the area of the focused workspace if it is empty.
+fn focused_area(state: &State) -> Option<(Option<WindowHandle>, Xyhw, &Workspace)> {
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    match state.focus_manager.window(&state.windows) {
+        Some(window) if workspace.owns(window, &state.workspaces) => {
+            Some((Some(window.handle), window.calculated_xyhw(), workspace))
+        }
+        _ => Some((None, workspace.xyhw, workspace)),
+    }
+}
+
+fn focus_window_direction(state: &mut State, direction: crate::models::Direction) -> Option<bool> {
+    let (handle, from, workspace) = focused_area(state)?;
+    if let Some(target) = window_in_direction(state, workspace, handle, from, direction, false) {
+        state.handle_window_focus(&target);
+        return Some(state.focused_tag_is_tabbed());
+    }
+    // Cross over to the workspace next to this one.
+    let adjacent = workspace_in_direction(state, workspace, direction)?;
+    if let Some(target) = window_in_direction(state, adjacent, handle, from, direction, false) {
+        state.handle_window_focus(&target);
+    } else {
+        let adjacent = adjacent.clone();
+        state.focus_workspace(&adjacent);
+    }
+    Some(state.focused_tag_is_tabbed())
+}
+
+fn move_window_direction<C: Config, SERVER: DisplayServer>(
+    manager: &mut Manager<C, SERVER>,
+    direction: crate::models::Direction,
+) -> Option<bool> {
+    let state = &mut manager.state;
+    let (Some(handle), from, workspace) = focused_area(state)? else {
+        return None;
+    };
+    let layout_tag = workspace.tag;
+    let floating = state
+        .windows
+        .iter()
+        .find(|w| w.handle == handle)?
+        .floating();
+    let target = if floating {
+        None
+    } else {
+        window_in_direction(state, workspace, Some(handle), from, direction, true)
+    };
+    let Some(target) = target else {
+        // Send the window to the workspace next to this one.
+        let tag_id = workspace_in_direction(state, workspace, direction)?.tag?;
+        return move_to_tag(None, tag_id, manager);
+    };
+
+    let index = state.windows.iter().position(|w| w.handle == handle)?;
+    let target_index = state.windows.iter().position(|w| w.handle == target)?;
+    state.windows.swap(index, target_index);
+    if let Some(tag) = layout_tag.and_then(|id| state.tags.get_mut(id)) {
+        tag.split_tree.swap(handle, target);
+    }
+    state.handle_window_focus(&handle);
+    Some(true)
 }
 
 fn focus_window_top(state: &mut State, swap: bool) -> Option<bool> {
@@ -670,7 +1054,7 @@ fn focus_window_top(state: &mut State, swap: bool) -> Option<bool> {
     let next = state
         .windows
         .iter()
-        .find(|x| x.tag == Some(tag) && !x.floating() && x.is_managed())
+        .find(|x| arranged_with(&state.workspaces, tag, x) && !x.floating())
         .map(|w| w.handle);
 
     match (next, cur, prev) {
@@ -680,7 +1064,7 @@ fn focus_window_top(state: &mut State, swap: bool) -> Option<bool> {
         (Some(next), Some(cur), _) if next != cur => state.handle_window_focus(&next),
         _ => {}
     }
-    None
+    Some(state.focused_tag_is_tabbed())
 }
 
 fn close_all_other_windows(state: &mut State) -> Option<bool> {
@@ -735,6 +1119,70 @@ fn change_main_width(state: &mut State, delta: i8, factor: i8) -> Option<bool> {
     Some(true)
 }
 
+fn change_main_count(state: &mut State, delta: i8) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    let tag = state.tags.get_mut(tag_id)?;
+    tag.change_main_count(delta);
+    Some(true)
+}
+
+fn change_window_weight(state: &mut State, delta: f32) -> Option<bool> {
+    let window = state.focus_manager.window_mut(&mut state.windows)?;
+    window.change_size_weight(delta);
+    Some(true)
+}
+
+fn preselect_split(state: &mut State, direction: crate::models::Direction) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    let tree = &mut state.tags.get_mut(tag_id)?.split_tree;
+    // Preselecting the same direction again cancels the preselection.
+    if tree.preselection == Some(direction) {
+        tree.preselection = None;
+    } else {
+        tree.preselection = Some(direction);
+    }
+    Some(false)
+}
+
+fn set_split_ratio(state: &mut State, percent: u8) -> Option<bool> {
+    let window = state.focus_manager.window(&state.windows)?;
+    let handle = window.handle;
+    let tag_id = state
+        .workspaces
+        .iter()
+        .find(|ws| ws.owns(window, &state.workspaces))
+        .map_or(window.tag, |ws| ws.tag);
+    let tag = state.tags.get_mut(tag_id?)?;
+    Some(tag.split_tree.set_ratio(handle, percent))
+}
+
+fn update_split_tree(state: &mut State, update: impl FnOnce(&mut SplitTree)) -> Option<bool> {
+    let tag_id = state.focus_manager.tag(0)?;
+    let tag = state.tags.get_mut(tag_id)?;
+    update(&mut tag.split_tree);
+    Some(tag.layout == Layout::Bsp)
+}
+
+fn scroll_viewport(state: &mut State, forward: bool) -> Option<bool> {
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let tag = state.tags.get_mut(workspace.tag?)?;
+    let columns = tag.columns(&state.windows, workspace, &state.workspaces);
+    tag.viewport.scroll(&columns, forward);
+    Some(tag.layout == Layout::Scrolling)
+}
+
+fn change_column_width(state: &mut State, delta: i8) -> Option<bool> {
+    let window = state.focus_manager.window_mut(&mut state.windows)?;
+    window.change_column_width(delta);
+    let handle = window.handle;
+    // Keep the resized column in view.
+    let workspace = state.focus_manager.workspace(&state.workspaces)?;
+    let tag = state.tags.get_mut(workspace.tag?)?;
+    let columns = tag.columns(&state.windows, workspace, &state.workspaces);
+    tag.viewport.show(&columns, handle);
+    Some(true)
+}
+
 fn set_margin_multiplier(state: &mut State, margin_multiplier: f32) -> Option<bool> {
     let ws = state.focus_manager.workspace_mut(&mut state.workspaces)?;
     ws.set_margin_multiplier(margin_multiplier);
@@ -756,18 +1204,21 @@ fn set_margin_multiplier(state: &mut State, margin_multiplier: f32) -> Option<bo
 
 fn send_workspace_to_tag(state: &mut State, ws_index: usize, tag_index: usize) -> bool {
     // todo: address inconsistency of using the index instead of the id here
-    if ws_index < state.workspaces.len() && tag_index < state.tags.len_normal() {
-        let workspace = &state.workspaces[ws_index].clone();
-        state.focus_workspace(workspace);
-        state.goto_tag_handler(tag_index + 1);
-        return true;
-    }
-    false
+    let Some(workspace) = state.workspaces.get(ws_index).cloned() else {
+        return false;
+    };
+    let Some(&tag_id) = state.tags_of_output(Some(&workspace.output)).get(tag_index) else {
+        return false;
+    };
+    state.focus_workspace(&workspace);
+    state.goto_tag_handler(tag_id);
+    true
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::LayoutRule;
     use crate::models::Tags;
 
     #[test]
@@ -1147,4 +1598,379 @@ mod tests {
         assert_eq!(manager.state.windows[0].border(), 1);
         assert_eq!(manager.state.windows[1].border(), 1);
     }
+
+    #[test]
+    fn window_weight_changes_are_clamped() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+
+        assert!(manager.command_handler(&Command::IncreaseWindowWeight(0.5)));
+        assert!((manager.state.windows[0].size_weight - 1.5).abs() < f32::EPSILON);
+
+        assert!(manager.command_handler(&Command::DecreaseWindowWeight(10.0)));
+        assert!((manager.state.windows[0].size_weight - 0.25).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn split_tree_follows_the_focused_window() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        assert!(manager.command_handler(&Command::SetLayout(Layout::Bsp)));
+        for i in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+            manager.update_windows();
+        }
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        manager.update_windows();
+
+        assert!(!manager.command_handler(&Command::PreselectSplit(crate::models::Direction::Up)));
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(3), None, None),
+            -1,
+            -1,
+        );
+        manager.update_windows();
+
+        let tag = manager.state.tags.get(1).unwrap();
+        assert_eq!(tag.split_tree.preselection, None);
+        assert_eq!(
+            tag.split_tree.windows(),
+            vec![
+                WindowHandle::MockHandle(3),
+                WindowHandle::MockHandle(1),
+                WindowHandle::MockHandle(2),
+            ]
+        );
+
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+        assert!(manager.command_handler(&Command::SetSplitRatio(70)));
+        manager.update_windows();
+        let first = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == WindowHandle::MockHandle(1));
+        let second = manager
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == WindowHandle::MockHandle(2));
+        assert_eq!(
+            first.unwrap().normal.w() * 7,
+            second.unwrap().normal.w() * 3
+        );
+    }
+
+    #[test]
+    fn scrolling_hides_columns_outside_the_viewport() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        assert!(manager.command_handler(&Command::SetLayout(Layout::Scrolling)));
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        let visible = |manager: &Manager<_, _>| -> Vec<bool> {
+            manager.state.windows.iter().map(Window::visible).collect()
+        };
+
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        manager.update_windows();
+        assert_eq!(visible(&manager), vec![true, true, false]);
+
+        assert!(manager.command_handler(&Command::ScrollRight));
+        manager.update_windows();
+        assert_eq!(visible(&manager), vec![false, true, true]);
+
+        manager.state.focus_window(&WindowHandle::MockHandle(3));
+        assert!(manager.command_handler(&Command::IncreaseColumnWidth(50)));
+        manager.update_windows();
+        assert_eq!(visible(&manager), vec![false, false, true]);
+    }
+
+    #[test]
+    fn set_layout_pins_the_layout_until_it_is_reset() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.state.layout_manager.rules = vec![
+            LayoutRule {
+                max_windows: Some(1),
+                layout: Layout::Monocle,
+                ..LayoutRule::default()
+            },
+            LayoutRule {
+                layout: Layout::GridHorizontal,
+                ..LayoutRule::default()
+            },
+        ];
+        let layout = |manager: &Manager<_, _>| manager.state.tags.get(1).unwrap().layout.clone();
+
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.update_windows();
+        assert_eq!(layout(&manager), Layout::Monocle);
+
+        assert!(manager.command_handler(&Command::SetLayout(Layout::EvenVertical)));
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, None),
+            -1,
+            -1,
+        );
+        manager.update_windows();
+        assert_eq!(layout(&manager), Layout::EvenVertical);
+
+        assert!(manager.command_handler(&Command::ResetLayout));
+        manager.update_windows();
+        assert_eq!(layout(&manager), Layout::GridHorizontal);
+    }
+
+    #[test]
+    fn minimized_windows_are_restored_from_the_stack() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.update_windows();
+        let minimized =
+            |manager: &Manager<_, _>| manager.state.tags.get(1).unwrap().minimized.clone();
+        let focused = |manager: &Manager<_, _>| {
+            manager
+                .state
+                .focus_manager
+                .window(&manager.state.windows)
+                .map(|w| w.handle)
+        };
+
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+        assert!(manager.command_handler(&Command::MinimizeWindow));
+        manager.state.focus_window(&WindowHandle::MockHandle(1));
+        assert!(manager.command_handler(&Command::MinimizeWindow));
+        manager.update_windows();
+        assert_eq!(
+            minimized(&manager),
+            vec![WindowHandle::MockHandle(2), WindowHandle::MockHandle(1)]
+        );
+        assert_eq!(focused(&manager), Some(WindowHandle::MockHandle(3)));
+        let shown: Vec<bool> = manager.state.windows.iter().map(Window::visible).collect();
+        assert_eq!(shown.iter().filter(|&&v| v).count(), 1);
+        // The remaining window takes up the whole workspace.
+        let remaining = manager.state.windows.iter().find(|w| w.visible()).unwrap();
+        assert_eq!(remaining.normal.w(), manager.state.workspaces[0].xyhw.w());
+
+        assert!(manager.command_handler(&Command::RestoreLastMinimized));
+        assert_eq!(minimized(&manager), vec![WindowHandle::MockHandle(2)]);
+        assert_eq!(focused(&manager), Some(WindowHandle::MockHandle(1)));
+
+        assert!(manager.command_handler(&Command::RestoreWindow("1".to_string())));
+        manager.update_windows();
+        assert!(minimized(&manager).is_empty());
+        assert!(manager.state.windows.iter().all(Window::visible));
+
+        // A window without a tag is left alone.
+        manager.state.windows[0].set_tag(None);
+        let handle = manager.state.windows[0].handle;
+        assert!(!manager.state.minimize_window(&handle));
+        assert!(!manager.state.windows[0].is_minimized());
+    }
+
+    #[test]
+    fn maximized_windows_fill_the_workspace_until_restored() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.update_windows();
+        let handle = WindowHandle::MockHandle(2);
+        let normal = |manager: &Manager<_, _>| {
+            manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.handle == handle)
+                .unwrap()
+                .normal
+        };
+        let tiled = normal(&manager);
+
+        manager.state.focus_window(&handle);
+        assert!(manager.command_handler(&Command::ToggleMaximize));
+        manager.update_windows();
+        let workspace = &manager.state.workspaces[0];
+        let area = (
+            workspace.x(),
+            workspace.y(),
+            workspace.width(),
+            workspace.height(),
+        );
+        let maximized = normal(&manager);
+        assert_eq!(
+            (maximized.x(), maximized.y(), maximized.w(), maximized.h()),
+            area
+        );
+        let order = manager
+            .state
+            .actions
+            .iter()
+            .rev()
+            .find_map(|act| match act {
+                DisplayAction::SetWindowOrder(_, handles) => Some(handles.clone()),
+                _ => None,
+            });
+        assert_eq!(order.unwrap()[0], handle);
+
+        assert!(manager.command_handler(&Command::ToggleMaximize));
+        manager.update_windows();
+        assert_eq!(normal(&manager), tiled);
+    }
+
+    #[test]
+    fn directional_commands_follow_the_window_geometry() {
+        use crate::models::{BBox, Direction as ScreenDirection};
+
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen {
+            bbox: BBox {
+                height: 600,
+                width: 800,
+                x: 800,
+                y: 0,
+            },
+            ..Screen::default()
+        });
+        // The windows end up on the second workspace, the main window on its left half.
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.update_windows();
+        let at = |manager: &Manager<_, _>, x: i32, y: i32| {
+            manager
+                .state
+                .windows
+                .iter()
+                .find(|w| w.contains_point(x, y))
+                .map(|w| w.handle)
+        };
+        let focused = |manager: &Manager<_, _>| {
+            manager
+                .state
+                .focus_manager
+                .window(&manager.state.windows)
+                .map(|w| w.handle)
+        };
+        let main = at(&manager, 1000, 300).unwrap();
+        let top = at(&manager, 1400, 150).unwrap();
+        let bottom = at(&manager, 1400, 450).unwrap();
+
+        manager.state.focus_window(&main);
+        manager.command_handler(&Command::FocusWindowDirection(ScreenDirection::Right));
+        assert_eq!(focused(&manager), Some(top));
+        manager.command_handler(&Command::FocusWindowDirection(ScreenDirection::Down));
+        assert_eq!(focused(&manager), Some(bottom));
+        manager.command_handler(&Command::FocusWindowDirection(ScreenDirection::Left));
+        assert_eq!(focused(&manager), Some(main));
+        // There is nothing left of the main window, the empty workspace on the left is focused.
+        manager.command_handler(&Command::FocusWindowDirection(ScreenDirection::Left));
+        let workspace = manager
+            .state
+            .focus_manager
+            .workspace(&manager.state.workspaces);
+        assert_eq!(
+            workspace.map(|ws| ws.id),
+            Some(manager.state.workspaces[0].id)
+        );
+
+        manager.state.focus_window(&main);
+        assert!(manager.command_handler(&Command::MoveWindowDirection(ScreenDirection::Right)));
+        manager.update_windows();
+        assert_eq!(at(&manager, 1400, 150), Some(main));
+        assert_eq!(at(&manager, 1000, 300), Some(top));
+
+        manager.command_handler(&Command::MoveWindowDirection(ScreenDirection::Left));
+        manager.update_windows();
+        assert_eq!(at(&manager, 1000, 300), Some(main));
+        manager.command_handler(&Command::MoveWindowDirection(ScreenDirection::Left));
+        manager.update_windows();
+        let window = manager.state.windows.iter().find(|w| w.handle == main);
+        assert_eq!(window.unwrap().tag, Some(1));
+    }
+
+    #[test]
+    fn floating_windows_are_moved_and_resized_by_command() {
+        use crate::models::SnapPosition;
+
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.update_windows();
+        manager.command_handler(&Command::TileToFloating);
+        manager.update_windows();
+        let geometry = |manager: &Manager<_, _>| {
+            let xyhw = manager.state.windows[0].exact_xyhw();
+            (xyhw.x(), xyhw.y(), xyhw.w(), xyhw.h())
+        };
+        let (x, y, w, h) = geometry(&manager);
+
+        assert!(manager.command_handler(&Command::MoveFloatingBy(10, -5)));
+        manager.update_windows();
+        assert_eq!(geometry(&manager), (x + 10, y - 5, w, h));
+
+        manager.command_handler(&Command::ResizeFloatingBy(-50, 20));
+        manager.update_windows();
+        assert_eq!(geometry(&manager), (x + 10, y - 5, w - 50, h + 20));
+
+        // The window does not shrink below its minimum size.
+        manager.command_handler(&Command::ResizeFloatingBy(-1000, 0));
+        assert_eq!(manager.state.windows[0].width(), 100);
+        manager.command_handler(&Command::ResizeFloatingBy(10, 0));
+        assert_eq!(manager.state.windows[0].width(), 110);
+
+        manager.command_handler(&Command::ResizeFloatingBy(90, 0));
+        manager.command_handler(&Command::CenterFloating);
+        manager.update_windows();
+        let (_, _, w, h) = geometry(&manager);
+        assert_eq!(geometry(&manager), ((800 - w) / 2, (600 - h) / 2, w, h));
+
+        manager.command_handler(&Command::SnapFloating(SnapPosition::BottomRight));
+        manager.update_windows();
+        assert_eq!(geometry(&manager), (800 - w, 600 - h, w, h));
+
+        // Tiled windows are left alone.
+        manager.command_handler(&Command::FloatingToTile);
+        assert!(!manager.command_handler(&Command::MoveFloatingBy(10, 10)));
+    }
 }
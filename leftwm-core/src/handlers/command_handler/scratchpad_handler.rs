@@ -44,9 +44,8 @@ fn hide_scratchpad<C: Config, SERVER: DisplayServer>(
         .find(|w| w.handle == *scratchpad_window)
         .ok_or("Could not find window from scratchpad_window")?;
 
-    window.untag();
     // Hide the scratchpad.
-    window.tag(&nsp_tag.id);
+    window.set_tag(Some(nsp_tag.id));
     window.set_visible(false);
 
     // Send tag changement to X
@@ -85,7 +84,7 @@ fn hide_scratchpad<C: Config, SERVER: DisplayServer>(
             .state
             .windows
             .iter()
-            .find(|w| ws.is_managed(w))
+            .find(|w| ws.manages(w, &manager.state.workspaces))
             .map(|w| w.handle)
     } else {
         None
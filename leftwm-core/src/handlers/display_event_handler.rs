@@ -28,18 +28,22 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             DisplayEvent::MoveWindow(handle, x, y) => from_move_window(self, handle, x, y),
             DisplayEvent::ResizeWindow(handle, x, y) => from_resize_window(self, handle, x, y),
             DisplayEvent::ConfigureXlibWindow(handle) => from_configure_xlib_window(state, handle),
+            DisplayEvent::ActivateWindow(handle, may_focus) => {
+                state.activate_window(&handle, may_focus)
+            }
+            DisplayEvent::ViewDesktop(tag) => state.view_desktop(tag).unwrap_or(false),
         }
     }
 }
 
 fn from_window_take_focus(state: &mut State, handle: WindowHandle) -> bool {
     state.focus_window(&handle);
-    false
+    state.focused_tag_is_tabbed()
 }
 
 fn from_handle_window_focus(state: &mut State, handle: WindowHandle) -> bool {
     state.handle_window_focus(&handle);
-    false
+    state.focused_tag_is_tabbed()
 }
 
 fn from_move_focus_to(state: &mut State, x: i32, y: i32) -> bool {
@@ -62,7 +66,14 @@ fn from_change_to_normal_mode(state: &mut State) -> bool {
             // prevents the focus switching between the floating window and the
             // workspace behind. We will also apply the margin_multiplier here so that
             // it is only called once the window has stopped moving.
-            if let Some(window) = state.windows.iter_mut().find(|w| w.handle == h) {
+            // Tiled windows are resized through the layout and stay where they are, dragged
+            // ones are dropped where the drag ended.
+            state.drop_tiled_window(&h);
+            if let Some(window) = state
+                .windows
+                .iter_mut()
+                .find(|w| w.handle == h && w.floating())
+            {
                 let loc = window.calculated_xyhw();
                 let (x, y) = loc.center();
                 let (margin_multiplier, tag, normal) =
@@ -75,7 +86,7 @@ fn from_change_to_normal_mode(state: &mut State) -> bool {
                 let exact = window.normal + offset;
                 offset = exact - normal;
                 window.set_floating_offsets(Some(offset));
-                window.tag = tag;
+                window.set_tag(tag);
                 window.apply_margin_multiplier(margin_multiplier);
                 let act = DisplayAction::SetWindowTag(window.handle, tag);
                 state.actions.push_back(act);
@@ -104,7 +115,9 @@ fn from_move_window<C: Config, SERVER: DisplayServer>(
     // Setup for when window first moves.
     if let Mode::ReadyToMove(h) = manager.state.mode {
         manager.state.mode = Mode::MovingWindow(h);
-        prepare_window(&mut manager.state, h);
+        manager.state.tile_drop = None;
+        let float = !manager.state.reorder_tile_drag;
+        prepare_window(&mut manager.state, h, float);
     }
     manager.window_move_handler(&handle, x, y)
 }
@@ -117,7 +130,7 @@ fn from_resize_window<C: Config, SERVER: DisplayServer>(
     // Setup for when window first resizes.
     if let Mode::ReadyToResize(h) = manager.state.mode {
         manager.state.mode = Mode::ResizingWindow(h);
-        prepare_window(&mut manager.state, h);
+        prepare_window(&mut manager.state, h, false);
     }
     manager.window_resize_handler(&handle, x, y)
 }
@@ -130,11 +143,17 @@ fn from_configure_xlib_window(state: &mut State, handle: WindowHandle) -> bool {
     false
 }
 // Save off the info about position of the window when we start to move/resize.
-fn prepare_window(state: &mut State, handle: WindowHandle) {
+// Tiled windows only float when they are moved, resizing them changes the layout instead. With
+// `reorder_tile_drag` moving them changes the order of the windows.
+fn prepare_window(state: &mut State, handle: WindowHandle, float: bool) {
     if let Some(w) = state.windows.iter_mut().find(|w| w.handle == handle) {
         if w.floating() {
             let offset = w.get_floating_offsets().unwrap_or_default();
             w.start_loc = Some(offset);
+        } else if !float {
+            // Keep the place of the window in the layout.
+            w.start_loc = Some(w.normal);
+            return;
         } else {
             let container = w.container_size.unwrap_or_default();
             let normal = w.normal;
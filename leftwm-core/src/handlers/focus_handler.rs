@@ -1,6 +1,7 @@
 #![allow(clippy::wildcard_imports)]
 
 use super::*;
+use crate::config::ActivationPolicy;
 use crate::models::TagId;
 use crate::state::State;
 use crate::{display_action::DisplayAction, models::FocusBehaviour};
@@ -24,10 +25,14 @@ impl State {
         };
 
         // Make sure the focused window's workspace is focused.
-        if let Some(workspace) = self.workspaces.iter().find(|ws| ws.is_displaying(&window)) {
+        let mut tag = window.tag;
+        let workspaces = &self.workspaces;
+        if let Some(workspace) = workspaces.iter().find(|ws| ws.owns(&window, workspaces)) {
             // this is an uggly workaround to suffice some CI failure related to https://github.com/rust-lang/rust/issues/59159
             let workspace_output_borrow_checker_workaround = workspace.output.clone();
             let workspace_id_borrow_checker_workaround = workspace.id;
+            // A window shown along with another tag keeps the tag of the workspace focused.
+            tag = workspace.tag;
             _ = self.focus_workspace_work(
                 &workspace_output_borrow_checker_workaround,
                 workspace_id_borrow_checker_workaround,
@@ -35,11 +40,53 @@ impl State {
         }
 
         // Make sure the focused window's tag is focused.
-        if let Some(tag) = window.tag {
+        if let Some(tag) = tag {
             _ = self.focus_tag_work(tag);
         }
     }
 
+    /// Handles a request of a client to activate a window, following the `activation_policy` of
+    /// the window or else the one of the config. A request which may not take the focus, because
+    /// the user worked in another window since, only marks the window urgent.
+    /// Returns true if the focus changed.
+    pub fn activate_window(&mut self, handle: &WindowHandle, may_focus: bool) -> bool {
+        let Some(window) = self.windows.iter().find(|w| &w.handle == handle) else {
+            return false;
+        };
+        let policy = match window.activation_policy.unwrap_or(self.activation_policy) {
+            ActivationPolicy::Focus | ActivationPolicy::SwitchTag if !may_focus => {
+                ActivationPolicy::Urgent
+            }
+            policy => policy,
+        };
+        let is_shown = self.workspaces.iter().any(|ws| ws.is_displaying(window));
+        let shown = match (policy, window.tag) {
+            (ActivationPolicy::Ignore, _) => return false,
+            (ActivationPolicy::SwitchTag, Some(tag)) if !is_shown => {
+                self.view_desktop(tag) == Some(true)
+            }
+            (ActivationPolicy::Focus | ActivationPolicy::SwitchTag, _) => is_shown,
+            (ActivationPolicy::Urgent, _) => false,
+        };
+        if !shown {
+            let act = DisplayAction::SetWindowUrgency(*handle, true);
+            self.actions.push_back(act);
+            return false;
+        }
+        if !self.restore_window(handle) {
+            self.handle_window_focus(handle);
+        }
+        true
+    }
+
+    /// Whether the focused tag has a tab bar, which has to be redrawn when the focus changes.
+    pub(crate) fn focused_tag_is_tabbed(&self) -> bool {
+        self.focus_manager
+            .tag(0)
+            .and_then(|tag| self.tags.get(tag))
+            .map_or(false, |tag| tag.layout.is_tabbed())
+    }
+
     /// Focuses the given workspace.
     // NOTE: Should only be called externally from this file.
     pub fn focus_workspace(&mut self, workspace: &Workspace) {
@@ -84,7 +131,7 @@ impl State {
             let handle = self
                 .windows
                 .iter()
-                .find(|w| ws.is_managed(w))
+                .find(|w| ws.manages(w, &self.workspaces))
                 .map(|w| w.handle);
             if let Some(h) = handle {
                 self.focus_window_work(&h);
@@ -93,7 +140,7 @@ impl State {
 
         // Unfocus last window if the target tag is empty
         if let Some(window) = self.focus_manager.window(&self.windows) {
-            if window.tag != Some(*tag) {
+            if window.tag != Some(*tag) && !to_focus.iter().any(|ws| ws.is_displaying(window)) {
                 self.unfocus_current_window();
             }
         }
@@ -159,7 +206,7 @@ impl State {
         let mut dists: Vec<(i32, &Window)> = self
             .windows
             .iter()
-            .filter(|x| ws.is_managed(x) && x.can_focus())
+            .filter(|x| ws.manages(x, &self.workspaces) && x.can_focus())
             .map(|w| (distance(w, x, y), w))
             .collect();
         dists.sort_by(|a, b| (a.0).cmp(&b.0));
@@ -195,7 +242,7 @@ impl State {
         // Find the handle in our managed windows.
         let found: &Window = self.windows.iter().find(|w| &w.handle == handle)?;
         // Docks don't want to get focus. If they do weird things happen. They don't get events...
-        if !found.is_managed() {
+        if !found.is_managed() || found.is_minimized() {
             return None;
         }
         let previous = self.focus_manager.window(&self.windows);
@@ -481,4 +528,47 @@ mod tests {
         let focused = manager.state.focus_manager.window(&manager.state.windows);
         assert!(focused.is_none());
     }
+
+    #[test]
+    fn activating_a_window_follows_the_activation_policy() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for (id, tag) in [(1, 1), (2, 2)] {
+            let mut window = Window::new(WindowHandle::MockHandle(id), None, None);
+            window.tag(&tag);
+            manager.window_created_handler(window, -1, -1);
+        }
+        let handle = WindowHandle::MockHandle(2);
+        let urgent = |state: &mut State| {
+            let urgent = state
+                .actions
+                .iter()
+                .any(|a| matches!(a, DisplayAction::SetWindowUrgency(_, true)));
+            state.actions.clear();
+            urgent
+        };
+        manager.state.actions.clear();
+
+        assert!(!manager.state.activate_window(&handle, true));
+        assert!(urgent(&mut manager.state));
+
+        manager.state.activation_policy = ActivationPolicy::Focus;
+        assert!(!manager.state.activate_window(&handle, true));
+        assert!(urgent(&mut manager.state));
+
+        // Requests sent before the user worked in another window do not switch tags.
+        manager.state.activation_policy = ActivationPolicy::SwitchTag;
+        assert!(!manager.state.activate_window(&handle, false));
+        assert!(urgent(&mut manager.state));
+        assert!(manager.state.activate_window(&handle, true));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(2));
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert_eq!(focused.map(|w| w.handle), Some(handle));
+
+        manager.state.windows[0].activation_policy = Some(ActivationPolicy::Ignore);
+        assert!(!manager
+            .state
+            .activate_window(&WindowHandle::MockHandle(1), true));
+        assert!(!urgent(&mut manager.state));
+    }
 }
@@ -1,8 +1,11 @@
-use crate::{models::TagId, state::State};
+use crate::{display_action::DisplayAction, models::TagId, state::State};
 
 impl State {
+    /// Shows only the tag on the focused workspace. A workspace which showed the tag before
+    /// shows the previous tag of the focused workspace instead. Tags of other outputs can't be
+    /// shown when every output has tags of its own.
     pub fn goto_tag_handler(&mut self, tag_id: TagId) -> Option<bool> {
-        if tag_id > self.tags.len_normal() || tag_id < 1 {
+        if !self.scoped_tags().contains(&tag_id) {
             return Some(false);
         }
 
@@ -20,19 +23,79 @@ impl State {
         if let Some(ws) = self.workspaces.iter_mut().find(|ws| ws.tag == new_tag) {
             ws.tag = Some(old_tag);
         }
+        for ws in &mut self.workspaces {
+            ws.extra_tags.retain(|&tag| tag != tag_id);
+        }
 
-        self.focus_manager.workspace_mut(&mut self.workspaces)?.tag = new_tag;
+        self.focus_manager
+            .workspace_mut(&mut self.workspaces)?
+            .show_tag(&tag_id);
         self.focus_tag(&tag_id);
         self.update_static();
         self.layout_manager
             .update_layouts(&mut self.workspaces, self.tags.all_mut());
         Some(true)
     }
+
+    /// Shows the windows of the tag together with the ones already shown on the focused
+    /// workspace, or stops showing them if they are. Tags which are the main tag of another
+    /// workspace can't be added, and the last tag of a workspace can't be removed.
+    pub fn toggle_tag_view(&mut self, tag_id: TagId) -> Option<bool> {
+        if !self.scoped_tags().contains(&tag_id) {
+            return Some(false);
+        }
+        let workspace = self.focus_manager.workspace(&self.workspaces)?;
+        if workspace.tag == Some(tag_id) {
+            let ws = self.focus_manager.workspace_mut(&mut self.workspaces)?;
+            if ws.extra_tags.is_empty() {
+                return Some(false);
+            }
+            let next = ws.extra_tags.remove(0);
+            ws.tag = Some(next);
+            self.focus_tag(&next);
+        } else if workspace.extra_tags.contains(&tag_id) {
+            let ws = self.focus_manager.workspace_mut(&mut self.workspaces)?;
+            ws.extra_tags.retain(|&tag| tag != tag_id);
+        } else {
+            if self.workspaces.iter().any(|ws| ws.tag == Some(tag_id)) {
+                return Some(false);
+            }
+            for ws in &mut self.workspaces {
+                ws.extra_tags.retain(|&tag| tag != tag_id);
+            }
+            let ws = self.focus_manager.workspace_mut(&mut self.workspaces)?;
+            ws.extra_tags.push(tag_id);
+        }
+
+        // Move the focus away from a window which is no longer shown.
+        let workspace = self.focus_manager.workspace(&self.workspaces)?;
+        if let Some(window) = self.focus_manager.window(&self.windows) {
+            if !workspace.is_displaying(window) {
+                let handle = window.handle;
+                let next = self
+                    .windows
+                    .iter()
+                    .find(|w| workspace.manages(w, &self.workspaces) && w.can_focus())
+                    .map(|w| w.handle);
+                if let Some(next) = next {
+                    self.focus_window(&next);
+                } else {
+                    self.actions
+                        .push_back(DisplayAction::Unfocus(Some(handle), false));
+                    self.focus_manager.window_history.push_front(None);
+                }
+            }
+        }
+        self.update_static();
+        self.layout_manager
+            .update_layouts(&mut self.workspaces, self.tags.all_mut());
+        Some(true)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::models::Screen;
+    use crate::models::{Screen, Window, WindowHandle};
     use crate::Manager;
 
     #[test]
@@ -44,4 +107,42 @@ mod tests {
         assert_eq!(manager.state.workspaces[0].tag, Some(2));
         assert_eq!(manager.state.workspaces[1].tag, Some(1));
     }
+
+    #[test]
+    fn toggling_a_tag_view_shows_its_windows_along_with_the_workspace_tag() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.state.windows[1].tag = Some(2);
+        manager.update_windows();
+        assert!(!manager.state.windows[1].visible());
+
+        assert_eq!(manager.state.toggle_tag_view(2), Some(true));
+        manager.update_windows();
+        assert_eq!(manager.state.workspaces[0].tags(), vec![1, 2]);
+        assert!(manager.state.windows.iter().all(Window::visible));
+        // Both windows are arranged by the layout of the workspace tag.
+        assert_ne!(
+            manager.state.windows[0].normal.x(),
+            manager.state.windows[1].normal.x()
+        );
+        manager.state.focus_window(&WindowHandle::MockHandle(2));
+        assert_eq!(manager.state.focus_manager.tag(0), Some(1));
+
+        // Hiding the workspace tag leaves the other one.
+        assert_eq!(manager.state.toggle_tag_view(1), Some(true));
+        assert_eq!(manager.state.workspaces[0].tags(), vec![2]);
+        assert_eq!(manager.state.toggle_tag_view(2), Some(false));
+
+        assert_eq!(manager.state.toggle_tag_view(3), Some(true));
+        manager.state.goto_tag_handler(3);
+        assert_eq!(manager.state.workspaces[0].tags(), vec![3]);
+    }
 }
@@ -0,0 +1,91 @@
+use super::{Window, WindowHandle};
+use crate::display_action::DisplayAction;
+use crate::models::WindowState;
+use crate::state::State;
+use crate::utils::helpers;
+
+impl State {
+    /// Takes a window out of the layout of its tag without closing it and puts it on the restore
+    /// stack of the tag. If the window was focused, a neighbour on the tag is focused instead.
+    /// Returns true if the window was minimized.
+    pub fn minimize_window(&mut self, handle: &WindowHandle) -> bool {
+        let is_tag_window =
+            |w: &Window| w.is_managed() && (&w.handle == handle || !w.is_minimized());
+        let Some(window) = self.windows.iter_mut().find(|w| &w.handle == handle) else {
+            return false;
+        };
+        if !window.is_managed() {
+            return false;
+        }
+        let tag_id = window.tag;
+        let Some(tag) = tag_id.and_then(|id| self.tags.get_mut(id)) else {
+            return false;
+        };
+        if tag.minimized.contains(handle) {
+            return false;
+        }
+        if !window.is_minimized() {
+            let mut states = window.states();
+            states.push(WindowState::Hidden);
+            window.set_states(states);
+            let act = DisplayAction::SetState(*handle, true, WindowState::Hidden);
+            self.actions.push_back(act);
+        }
+        tag.minimized.push(*handle);
+        window.set_visible(false);
+
+        self.focus_manager
+            .tags_last_window
+            .retain(|_, h| h != handle);
+        if self.focus_manager.window_history.front() == Some(&Some(*handle)) {
+            let on_tag: Vec<&Window> = self
+                .windows
+                .iter()
+                .filter(|w| w.tag == tag_id && is_tag_window(w))
+                .collect();
+            let is_handle = |w: &&Window| &w.handle == handle;
+            let next = helpers::relative_find(&on_tag, is_handle, 1, false)
+                .or_else(|| helpers::relative_find(&on_tag, is_handle, -1, false))
+                .map(|w| w.handle);
+            if let Some(next) = next {
+                self.focus_window(&next);
+            } else {
+                let act = DisplayAction::Unfocus(Some(*handle), false);
+                self.actions.push_back(act);
+                self.focus_manager.window_history.push_front(None);
+            }
+        }
+        true
+    }
+
+    /// Puts a minimized window back into the layout of its tag and takes it off the restore
+    /// stack. The window is focused if its tag is shown.
+    /// Returns true if the window was restored.
+    pub fn restore_window(&mut self, handle: &WindowHandle) -> bool {
+        let Some(window) = self.windows.iter_mut().find(|w| &w.handle == handle) else {
+            return false;
+        };
+        let mut restored = window.is_minimized();
+        if restored {
+            let mut states = window.states();
+            states.retain(|s| s != &WindowState::Hidden);
+            window.set_states(states);
+            let act = DisplayAction::SetState(*handle, false, WindowState::Hidden);
+            self.actions.push_back(act);
+        }
+        for tag in self.tags.all_mut() {
+            let count = tag.minimized.len();
+            tag.minimized.retain(|h| h != handle);
+            restored = restored || count != tag.minimized.len();
+        }
+        if !restored {
+            return false;
+        }
+
+        if self.workspaces.iter().any(|ws| ws.is_displaying(window)) {
+            window.set_visible(true);
+            self.focus_window(handle);
+        }
+        true
+    }
+}
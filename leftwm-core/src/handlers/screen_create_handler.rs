@@ -1,12 +1,31 @@
 use super::{Manager, Screen, Workspace};
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
+use crate::models::Tag;
+use crate::DisplayAction;
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     /// Process a collection of events, and apply the changes to a manager.
     ///
     /// Returns `true` if changes need to be rendered.
     pub fn screen_create_handler(&mut self, screen: Screen) -> bool {
+        // When every output has tags of its own, an output missing from the workspaces of the
+        // config gets a copy of the tags as well.
+        let tags = self.state.tags.normal();
+        if tags.iter().any(|tag| tag.namespace.is_some())
+            && !tags
+                .iter()
+                .any(|tag| tag.namespace.as_ref() == Some(&screen.output))
+        {
+            let labels = self.config.create_list_of_tag_labels();
+            self.state
+                .tags
+                .add_new_for_output(&labels, Some(&screen.output));
+            self.state.load_tag_configs(&self.config.tag_configs());
+            let act = DisplayAction::SetDesktopNames(self.state.desktop_names());
+            self.state.actions.push_back(act);
+        }
+
         let tag_index = self.state.workspaces.len();
         let tag_len = self.state.tags.len_normal();
         let workspace_id = self
@@ -31,9 +50,30 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         }
         new_workspace.load_config(&self.config);
 
+        // Prefer a tag which wants to be on this output, then the next tag in line. Only the tags
+        // of the output are candidates when every output has tags of its own.
+        let is_free = |id: &usize| !self.state.workspaces.iter().any(|ws| ws.has_tag(id));
+        let candidates: Vec<&Tag> = self
+            .state
+            .tags
+            .normal()
+            .iter()
+            .filter(|tag| tag.belongs_to(&screen.output))
+            .collect();
+        let preferred = candidates
+            .iter()
+            .find(|tag| tag.config.output.as_ref() == Some(&screen.output) && is_free(&tag.id))
+            .map(|tag| tag.id);
+        let in_line = candidates
+            .iter()
+            .map(|tag| tag.id)
+            .filter(|id| *id > tag_index)
+            .chain(candidates.iter().map(|tag| tag.id))
+            .find(is_free);
+
         // Make sure there are enough tags for this new screen.
-        let next_id = if tag_len > tag_index {
-            tag_index + 1
+        let next_id = if let Some(id) = preferred.or(in_line) {
+            id
         } else {
             // Add a new tag for the workspace.
             self.state.tags.add_new_unlabeled(
@@ -43,8 +83,15 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             )
         };
 
+        // A tag with a layout of its own keeps it, the others start with the layout of the
+        // workspace.
         if let Some(tag) = self.state.tags.get_mut(next_id) {
-            tag.layout = new_workspace.layout;
+            if tag.config.initial_layout().is_some() {
+                new_workspace.layout = tag.layout.clone();
+                new_workspace.main_width_percentage = tag.main_width_percentage;
+            } else {
+                tag.layout = new_workspace.layout.clone();
+            }
         }
 
         self.state.focus_workspace(&new_workspace);
@@ -60,6 +107,10 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::tests::TestConfig;
+    use crate::config::TagConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::layouts::Layout;
     use crate::Manager;
 
     #[test]
@@ -108,4 +159,32 @@ mod tests {
         assert!(manager.state.workspaces[2].has_tag(&3));
         assert!(manager.state.workspaces[3].has_tag(&4));
     }
+    #[test]
+    fn tags_start_on_their_preferred_output_with_their_own_settings() {
+        let config = TestConfig {
+            tags: vec!["web".to_string(), "chat".to_string(), "code".to_string()],
+            tag_configs: vec![
+                TagConfig::default(),
+                TagConfig::default(),
+                TagConfig {
+                    layout: Some(Layout::Monocle),
+                    main_width: Some(70),
+                    output: Some("HDMI-1".to_string()),
+                    ..TagConfig::default()
+                },
+            ],
+            ..TestConfig::default()
+        };
+        let mut manager: Manager<_, MockDisplayServer> = Manager::new(config);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen {
+            output: "HDMI-1".to_string(),
+            ..Screen::default()
+        });
+
+        assert!(manager.state.workspaces[0].has_tag(&1));
+        assert!(manager.state.workspaces[1].has_tag(&3));
+        assert_eq!(manager.state.workspaces[1].layout, Layout::Monocle);
+        assert_eq!(manager.state.workspaces[1].main_width_percentage, 70);
+    }
 }
@@ -0,0 +1,596 @@
+use super::WindowHandle;
+use crate::display_action::DisplayAction;
+use crate::models::{Tag, TagId};
+use crate::state::State;
+
+impl State {
+    /// Appends a new tag with the label to the normal tags and returns its ID.
+    pub fn add_tag(&mut self, label: &str) -> TagId {
+        let layout = self
+            .focus_manager
+            .workspace(&self.workspaces)
+            .map(|ws| self.layout_manager.new_layout(&ws.output, ws.id))
+            .unwrap_or_default();
+        let namespace = self
+            .focus_manager
+            .tag(0)
+            .and_then(|id| self.tags.get(id))
+            .and_then(|tag| tag.namespace.clone());
+        let id = self.tags.add_new(label, layout);
+        if let Some(tag) = self.tags.get_mut(id) {
+            tag.namespace = namespace;
+        }
+        self.tags_changed(&[]);
+        id
+    }
+
+    /// Removes a tag and moves its windows to `target`, which defaults to the tag on its left,
+    /// or to the tag on its right for the first tag. Both tags have to belong to the same
+    /// outputs. Workspaces showing the removed tag show the target instead, or another tag if
+    /// the target is already shown. A tag is only removed if every workspace which can show it
+    /// can still show a tag of its own.
+    /// Returns true if the tag was removed.
+    pub fn remove_tag(&mut self, id: TagId, target: Option<TagId>) -> bool {
+        let len = self.tags.len_normal();
+        if !(1..=len).contains(&id) {
+            return false;
+        }
+        let namespace = self.tags.get(id).and_then(|tag| tag.namespace.clone());
+        let siblings: Vec<TagId> = self
+            .tags
+            .normal()
+            .iter()
+            .filter(|tag| tag.namespace == namespace)
+            .map(|tag| tag.id)
+            .collect();
+        let position = siblings
+            .iter()
+            .position(|&tag| tag == id)
+            .unwrap_or_default();
+        let default_target = if position == 0 { 1 } else { position - 1 };
+        let Some(target) = target.or_else(|| siblings.get(default_target).copied()) else {
+            return false;
+        };
+        let workspaces = self
+            .workspaces
+            .iter()
+            .filter(|ws| {
+                namespace
+                    .as_ref()
+                    .map_or(true, |output| output == &ws.output)
+            })
+            .count();
+        if !siblings.contains(&target) || id == target || siblings.len() <= workspaces {
+            return false;
+        }
+
+        let previous = self.window_tags();
+        for window in &mut self.windows {
+            window.extra_tags.retain(|&tag| tag != id);
+            if window.tag == Some(id) {
+                window.tag = Some(target);
+                window.extra_tags.retain(|&tag| tag != target);
+            }
+        }
+        for index in 0..self.workspaces.len() {
+            self.workspaces[index].extra_tags.retain(|&tag| tag != id);
+            if self.workspaces[index].tag != Some(id) {
+                continue;
+            }
+            let candidates = || {
+                std::iter::once(target)
+                    .chain(siblings.iter().copied())
+                    .filter(|tag| *tag != id)
+            };
+            // A tag only shown next to the first tag of another workspace is taken from it.
+            let replacement = candidates()
+                .find(|tag| !self.workspaces.iter().any(|ws| ws.has_tag(tag)))
+                .or_else(|| {
+                    candidates().find(|tag| self.workspaces.iter().all(|ws| ws.tag != Some(*tag)))
+                });
+            if let Some(replacement) = replacement {
+                for workspace in &mut self.workspaces {
+                    workspace.extra_tags.retain(|&tag| tag != replacement);
+                }
+                self.workspaces[index].tag = Some(replacement);
+            }
+        }
+        self.focus_manager.tags_last_window.remove(&id);
+        self.focus_manager.tag_history.retain(|&tag| tag != id);
+
+        let Some(removed) = self.tags.remove(id) else {
+            return false;
+        };
+        self.retag(|tag| if tag > id && tag <= len { tag - 1 } else { tag });
+        let target = if target > id { target - 1 } else { target };
+        if let Some(tag) = self.tags.get_mut(target) {
+            tag.minimized.extend(removed.minimized);
+        }
+        self.tags_changed(&previous);
+        true
+    }
+
+    /// Changes the label of a tag.
+    /// Returns true if the tag exists.
+    pub fn rename_tag(&mut self, id: TagId, label: &str) -> bool {
+        if id > self.tags.len_normal() {
+            return false;
+        }
+        let Some(tag) = self.tags.get_mut(id) else {
+            return false;
+        };
+        tag.label = label.to_string();
+        self.tags_changed(&[]);
+        true
+    }
+
+    /// Moves a tag to another position in the list of tags, shifting the tags in between.
+    /// Returns true if the tag was moved.
+    pub fn move_tag(&mut self, id: TagId, position: usize) -> bool {
+        let previous = self.window_tags();
+        if self.tags.move_to(id, position).is_none() {
+            return false;
+        }
+        self.retag(|tag| {
+            if tag == id {
+                position
+            } else if id < tag && tag <= position {
+                tag - 1
+            } else if position <= tag && tag < id {
+                tag + 1
+            } else {
+                tag
+            }
+        });
+        self.tags_changed(&previous);
+        true
+    }
+
+    /// Adds the tag to the tags of a window, or takes it away if the window has other tags.
+    /// Returns true if the tags of the window changed.
+    pub fn toggle_window_tag(&mut self, handle: &WindowHandle, tag: TagId) -> bool {
+        let Some(window) = self.windows.iter().find(|w| &w.handle == handle) else {
+            return false;
+        };
+        let mut tags = window.tags();
+        if tags.contains(&tag) {
+            tags.retain(|&t| t != tag);
+        } else {
+            tags.push(tag);
+        }
+        self.set_window_tags(handle, &tags)
+    }
+
+    /// Puts a window on the tags, the first of which arranges it. The focus moves on if the
+    /// window is not shown anymore.
+    /// Returns true if the tags of the window changed.
+    pub fn set_window_tags(&mut self, handle: &WindowHandle, tags: &[TagId]) -> bool {
+        let len = self.tags.len_normal();
+        if tags.is_empty() || tags.iter().any(|tag| !(1..=len).contains(tag)) {
+            return false;
+        }
+        let mut unique: Vec<TagId> = vec![];
+        for tag in tags {
+            if !unique.contains(tag) {
+                unique.push(*tag);
+            }
+        }
+        let Some(window) = self.windows.iter_mut().find(|w| &w.handle == handle) else {
+            return false;
+        };
+        if !window.is_managed() || window.tags() == unique {
+            return false;
+        }
+        let was_shown = self.workspaces.iter().any(|ws| ws.is_displaying(window));
+        window.tag = Some(unique[0]);
+        window.extra_tags = unique.split_off(1);
+        let act = DisplayAction::SetWindowTags(*handle, window.tags());
+        self.actions.push_back(act);
+
+        let is_shown = self.workspaces.iter().any(|ws| ws.is_displaying(window));
+        let is_focused =
+            self.focus_manager.window(&self.windows).map(|w| w.handle) == Some(*handle);
+        if was_shown && !is_shown && is_focused {
+            let workspace = self.focus_manager.workspace(&self.workspaces);
+            let next = self
+                .windows
+                .iter()
+                .find(|w| {
+                    workspace.map_or(false, |ws| ws.manages(w, &self.workspaces)) && w.can_focus()
+                })
+                .map(|w| w.handle);
+            if let Some(next) = next {
+                self.focus_window(&next);
+            } else {
+                let act = DisplayAction::Unfocus(Some(*handle), false);
+                self.actions.push_back(act);
+                self.focus_manager.window_history.push_front(None);
+            }
+        }
+        self.sort_windows();
+        true
+    }
+
+    /// The normal tags a workspace on the output can show, all of them unless every output has
+    /// tags of its own.
+    pub fn tags_of_output(&self, output: Option<&str>) -> Vec<TagId> {
+        self.tags
+            .normal()
+            .iter()
+            .filter(|tag| output.map_or(tag.namespace.is_none(), |o| tag.belongs_to(o)))
+            .map(|tag| tag.id)
+            .collect()
+    }
+
+    /// The normal tags the focused workspace can show. Tag numbers given by the user count
+    /// through these.
+    pub fn scoped_tags(&self) -> Vec<TagId> {
+        let workspace = self.focus_manager.workspace(&self.workspaces);
+        self.tags_of_output(workspace.map(|ws| ws.output.as_str()))
+    }
+
+    /// The ID of the tag with the number, counted from 1, among the tags of the focused
+    /// workspace.
+    pub fn scoped_tag(&self, number: usize) -> Option<TagId> {
+        self.scoped_tags().get(number.checked_sub(1)?).copied()
+    }
+
+    /// The names of the normal tags as EWMH desktops.
+    pub fn desktop_names(&self) -> Vec<String> {
+        self.tags.normal().iter().map(Tag::desktop_name).collect()
+    }
+
+    /// Shows a tag given by its ID, like a pager asks for a desktop. The tag is shown on the
+    /// focused workspace, or on a workspace of the output it belongs to.
+    pub fn view_desktop(&mut self, tag_id: TagId) -> Option<bool> {
+        let tag = self.tags.normal().get(tag_id.checked_sub(1)?)?;
+        let is_focused_output = self
+            .focus_manager
+            .workspace(&self.workspaces)
+            .map_or(false, |ws| tag.belongs_to(&ws.output));
+        if !is_focused_output {
+            let workspace = self
+                .workspaces
+                .iter()
+                .find(|ws| ws.has_tag(&tag_id))
+                .or_else(|| self.workspaces.iter().find(|ws| tag.belongs_to(&ws.output)))?
+                .clone();
+            self.focus_workspace(&workspace);
+        }
+        self.goto_tag_handler(tag_id)
+    }
+
+    /// The tags of all windows, to find the ones which changed.
+    fn window_tags(&self) -> Vec<(WindowHandle, Vec<TagId>)> {
+        self.windows.iter().map(|w| (w.handle, w.tags())).collect()
+    }
+
+    /// Gives everything referring to a tag the new ID of the tag.
+    fn retag(&mut self, new_id: impl Fn(TagId) -> TagId) {
+        for window in &mut self.windows {
+            window.tag = window.tag.map(&new_id);
+            for tag in &mut window.extra_tags {
+                *tag = new_id(*tag);
+            }
+        }
+        for workspace in &mut self.workspaces {
+            workspace.tag = workspace.tag.map(&new_id);
+            for tag in &mut workspace.extra_tags {
+                *tag = new_id(*tag);
+            }
+        }
+        for tag in &mut self.focus_manager.tag_history {
+            *tag = new_id(*tag);
+        }
+        self.focus_manager.tags_last_window = self
+            .focus_manager
+            .tags_last_window
+            .drain()
+            .map(|(tag, handle)| (new_id(tag), handle))
+            .collect();
+    }
+
+    /// Lets the display server know about the new list of tags and about the windows which are
+    /// now on another tag.
+    fn tags_changed(&mut self, previous: &[(WindowHandle, Vec<TagId>)]) {
+        for window in &self.windows {
+            let changed = previous
+                .iter()
+                .any(|(handle, tags)| handle == &window.handle && tags != &window.tags());
+            if changed {
+                let act = DisplayAction::SetWindowTags(window.handle, window.tags());
+                self.actions.push_back(act);
+            }
+        }
+        self.actions
+            .push_back(DisplayAction::SetDesktopNames(self.desktop_names()));
+        let act = DisplayAction::SetCurrentTags(self.focus_manager.tag(0));
+        self.actions.push_back(act);
+        self.update_static();
+        self.layout_manager
+            .update_layouts(&mut self.workspaces, self.tags.all_mut());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::tests::TestConfig;
+    use crate::display_action::DisplayAction;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::dto::{DisplayState, ManagerState};
+    use crate::models::{BBox, Screen, Window, WindowHandle};
+    use crate::{Command, Manager};
+
+    fn labels(manager: &Manager<impl crate::Config, impl crate::DisplayServer>) -> Vec<String> {
+        let tags = manager.state.tags.normal();
+        tags.iter().map(|t| t.label.clone()).collect()
+    }
+
+    #[test]
+    fn removing_and_moving_tags_renumbers_windows_and_workspaces() {
+        let tags = ["1", "2", "3", "4"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen::default());
+        for (i, tag) in [(1, 1), (2, 2), (3, 3), (4, 4)] {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+            manager.state.windows.last_mut().unwrap().tag = Some(tag);
+        }
+        manager.state.goto_tag_handler(3);
+        let window_tags = |manager: &Manager<_, _>| -> Vec<Option<usize>> {
+            manager.state.windows.iter().map(|w| w.tag).collect()
+        };
+
+        // Tag 3 is shown on the second workspace, its windows move to tag 2.
+        assert!(manager.state.remove_tag(3, None));
+        assert_eq!(labels(&manager), vec!["1", "2", "4"]);
+        assert_eq!(
+            window_tags(&manager),
+            vec![Some(1), Some(2), Some(2), Some(3)]
+        );
+        assert_eq!(manager.state.workspaces[0].tag, Some(1));
+        assert_eq!(manager.state.workspaces[1].tag, Some(2));
+        assert_eq!(manager.state.focus_manager.tag_history, &[2, 1]);
+
+        // Every workspace needs a tag of its own.
+        assert!(manager.state.remove_tag(3, Some(1)));
+        assert!(!manager.state.remove_tag(2, None));
+
+        let id = manager.state.add_tag("web");
+        assert_eq!(id, 3);
+        assert!(manager.state.move_tag(3, 1));
+        assert_eq!(labels(&manager), vec!["web", "1", "2"]);
+        assert_eq!(
+            window_tags(&manager),
+            vec![Some(2), Some(3), Some(3), Some(2)]
+        );
+        assert_eq!(manager.state.workspaces[0].tag, Some(2));
+        assert_eq!(manager.state.workspaces[1].tag, Some(3));
+
+        assert!(manager.state.rename_tag(1, "mail"));
+        assert_eq!(labels(&manager), vec!["mail", "1", "2"]);
+        assert!(!manager.state.rename_tag(4, "none"));
+    }
+
+    #[test]
+    fn windows_show_up_on_each_of_their_tags() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        let handle = WindowHandle::MockHandle(2);
+        let visible = |manager: &Manager<_, _>| -> Vec<bool> {
+            manager.state.windows.iter().map(Window::visible).collect()
+        };
+
+        assert!(manager.state.set_window_tags(&handle, &[2, 1, 2]));
+        assert_eq!(manager.state.windows[1].tags(), vec![2, 1]);
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::SetWindowTags(h, tags) if h == &handle && tags == &[2, 1]
+        )));
+        manager.update_windows();
+        assert_eq!(visible(&manager), vec![true, true]);
+        manager.state.goto_tag_handler(2);
+        manager.update_windows();
+        assert_eq!(visible(&manager), vec![false, true]);
+
+        assert!(manager.state.toggle_window_tag(&handle, 1));
+        assert_eq!(manager.state.windows[1].tags(), vec![2]);
+        // A window keeps at least one tag.
+        assert!(!manager.state.toggle_window_tag(&handle, 2));
+        assert!(!manager.state.set_window_tags(&handle, &[4]));
+    }
+
+    #[test]
+    fn a_window_shown_on_two_workspaces_is_arranged_by_one() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut manager = Manager::new_test(tags);
+        for x in [0, 800] {
+            manager.screen_create_handler(Screen {
+                bbox: BBox {
+                    height: 600,
+                    width: 800,
+                    x,
+                    y: 0,
+                },
+                ..Screen::default()
+            });
+        }
+        for (i, ws) in [(1, 0), (2, 0), (3, 1)] {
+            let workspace = manager.state.workspaces[ws].clone();
+            manager.state.focus_workspace(&workspace);
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        assert!(manager
+            .state
+            .set_window_tags(&WindowHandle::MockHandle(1), &[1, 2]));
+        manager.update_windows();
+
+        let windows = &manager.state.windows;
+        let workspaces = &manager.state.workspaces;
+        assert!(workspaces[0].manages(&windows[0], workspaces));
+        assert!(!workspaces[1].manages(&windows[0], workspaces));
+        // The window stays in the layout of its own tag, the other workspace has one window left.
+        assert!(windows[0].normal.x() < 800);
+        assert_eq!(windows[2].normal.x(), 800);
+        assert_eq!(windows[2].normal.w(), 800);
+
+        // It isn't focused from the other workspace either.
+        let workspace = manager.state.workspaces[1].clone();
+        manager.state.focus_workspace(&workspace);
+        assert_eq!(
+            manager.get_next_or_previous_handle(&WindowHandle::MockHandle(3)),
+            None
+        );
+    }
+    #[test]
+    fn every_output_gets_tags_of_its_own() {
+        let config = TestConfig {
+            tags: ["1", "2", "3"].map(String::from).to_vec(),
+            independent_tags: true,
+            workspaces: Some(
+                ["A", "B"]
+                    .map(|output| crate::config::Workspace {
+                        output: output.to_string(),
+                        ..Default::default()
+                    })
+                    .to_vec(),
+            ),
+            ..TestConfig::default()
+        };
+        let mut manager: Manager<_, MockDisplayServer> = Manager::new(config);
+        for output in ["A", "B"] {
+            manager.screen_create_handler(Screen {
+                output: output.to_string(),
+                ..Screen::default()
+            });
+        }
+        let shown = |manager: &Manager<_, _>| -> Vec<Option<usize>> {
+            manager.state.workspaces.iter().map(|ws| ws.tag).collect()
+        };
+
+        assert_eq!(
+            manager.state.desktop_names(),
+            vec!["A_1", "A_2", "A_3", "B_1", "B_2", "B_3"]
+        );
+        assert_eq!(shown(&manager), vec![Some(1), Some(4)]);
+
+        // Tag numbers count through the tags of the focused output.
+        assert!(manager.command_handler(&Command::GoToTag {
+            tag: 2,
+            swap: false
+        }));
+        assert_eq!(shown(&manager), vec![Some(1), Some(5)]);
+        manager.command_handler(&Command::FocusNextTag);
+        manager.command_handler(&Command::FocusNextTag);
+        assert_eq!(shown(&manager), vec![Some(1), Some(4)]);
+        assert_eq!(manager.state.goto_tag_handler(2), Some(false));
+
+        // Pagers count through all tags and move to the output of the tag.
+        assert_eq!(manager.state.view_desktop(2), Some(true));
+        assert_eq!(shown(&manager), vec![Some(2), Some(4)]);
+
+        let display = DisplayState::from(ManagerState::from(&manager.state));
+        let tags = &display.workspaces[1].tags;
+        let names: Vec<&str> = tags.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["1", "2", "3"]);
+        assert!(tags[0].mine && !tags[1].mine);
+    }
+    #[test]
+    fn unlisted_outputs_get_tags_of_their_own() {
+        let config = TestConfig {
+            tags: ["1", "2"].map(String::from).to_vec(),
+            independent_tags: true,
+            workspaces: Some(vec![crate::config::Workspace {
+                output: "A".to_string(),
+                ..Default::default()
+            }]),
+            ..TestConfig::default()
+        };
+        let mut manager: Manager<_, MockDisplayServer> = Manager::new(config);
+        for output in ["A", "B"] {
+            manager.screen_create_handler(Screen {
+                output: output.to_string(),
+                ..Screen::default()
+            });
+        }
+
+        assert_eq!(
+            manager.state.desktop_names(),
+            vec!["A_1", "A_2", "B_1", "B_2"]
+        );
+        assert_eq!(manager.state.workspaces[1].tag, Some(3));
+    }
+
+    #[test]
+    fn tags_are_removed_among_the_tags_of_the_focused_output() {
+        let config = TestConfig {
+            tags: ["1", "2"].map(String::from).to_vec(),
+            independent_tags: true,
+            workspaces: Some(
+                ["A", "B"]
+                    .map(|output| crate::config::Workspace {
+                        output: output.to_string(),
+                        ..Default::default()
+                    })
+                    .to_vec(),
+            ),
+            ..TestConfig::default()
+        };
+        let mut manager: Manager<_, MockDisplayServer> = Manager::new(config);
+        for output in ["A", "B"] {
+            manager.screen_create_handler(Screen {
+                output: output.to_string(),
+                ..Screen::default()
+            });
+        }
+        let remove = |tag, target| Command::RemoveTag { tag, target };
+        let shown = |manager: &Manager<_, _>| -> Vec<Option<usize>> {
+            manager.state.workspaces.iter().map(|ws| ws.tag).collect()
+        };
+
+        let workspace = manager.state.workspaces[0].clone();
+        manager.state.focus_workspace(&workspace);
+
+        // The target has to be a tag of the same output.
+        assert!(!manager.state.remove_tag(1, Some(3)));
+        assert!(manager.command_handler(&remove(1, None)));
+        assert_eq!(manager.state.desktop_names(), vec!["A_2", "B_1", "B_2"]);
+        assert_eq!(shown(&manager), vec![Some(1), Some(2)]);
+
+        // The last tag of an output stays.
+        assert!(!manager.command_handler(&remove(1, None)));
+        assert!(!manager.state.remove_tag(1, None));
+        assert_eq!(shown(&manager), vec![Some(1), Some(2)]);
+
+        let workspace = manager.state.workspaces[1].clone();
+        manager.state.focus_workspace(&workspace);
+        assert!(manager.command_handler(&Command::RenameTag {
+            tag: 2,
+            label: "web".to_string()
+        }));
+        assert!(manager.command_handler(&Command::MoveTag {
+            tag: 2,
+            position: 1
+        }));
+        assert_eq!(manager.state.desktop_names(), vec!["A_2", "B_web", "B_1"]);
+        assert!(manager.command_handler(&remove(2, None)));
+        assert_eq!(manager.state.desktop_names(), vec!["A_2", "B_web"]);
+        assert_eq!(shown(&manager), vec![Some(1), Some(2)]);
+    }
+}
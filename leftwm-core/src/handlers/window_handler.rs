@@ -1,10 +1,11 @@
 use super::{Manager, Window, WindowChange, WindowType, Workspace};
 use crate::child_process::exec_shell;
+use crate::command::Command;
 use crate::config::{Config, InsertBehavior};
 use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
 use crate::layouts::Layout;
-use crate::models::{WindowHandle, WindowState, Xyhw};
+use crate::models::{WindowHandle, WindowState, Xyhw, XyhwBuilder};
 use crate::state::State;
 use crate::utils::helpers;
 use std::env;
@@ -35,7 +36,10 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             &mut on_same_tag,
         );
         self.config.load_window(&mut window);
-        insert_window(&mut self.state, &mut window, layout);
+        match find_swallowable_terminal(&self.state, &window) {
+            Some(terminal) => swallow_terminal(&mut self.state, &mut window, terminal),
+            None => insert_window(&mut self.state, &mut window, &layout),
+        }
 
         let follow_mouse = self.state.focus_manager.focus_new_windows
             && self.state.focus_manager.behaviour.is_sloppy()
@@ -46,11 +50,24 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         self.state.actions.push_back(act);
 
         // Let the DS know the correct desktop to find this window.
-        if window.tag.is_some() {
+        if !window.extra_tags.is_empty() {
+            let act = DisplayAction::SetWindowTags(window.handle, window.tags());
+            self.state.actions.push_back(act);
+        } else if window.tag.is_some() {
             let act = DisplayAction::SetWindowTag(window.handle, window.tag);
             self.state.actions.push_back(act);
         }
 
+        if let Some(color) = &window.border_color {
+            let act = DisplayAction::SetWindowBorderColor(window.handle, color.clone());
+            self.state.actions.push_back(act);
+        }
+
+        // Windows which start iconified go straight onto the restore stack of their tag.
+        if window.is_minimized() {
+            self.state.minimize_window(&window.handle);
+        }
+
         // Tell the WM to reevaluate the stacking order, so the new window is put in the correct layer
         self.state.sort_windows();
         self.state.handle_single_border(self.config.border_width());
@@ -59,6 +76,11 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             self.state.focus_window(&window.handle);
         }
 
+        if let Some(scratchpad) = window.spawn_scratchpad {
+            let window = Some(window.handle);
+            self.command_handler(&Command::AttachScratchPad { window, scratchpad });
+        }
+
         if let Some(cmd) = &self.config.on_new_window_cmd() {
             exec_shell(cmd, &mut self.children);
         }
@@ -77,10 +99,15 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                 Some(window) => (window.transient, window.floating(), window.visible()),
                 None => return false,
             };
+        // Bring back the terminal this window took the place of.
+        let terminal = restore_swallowed_terminal(&mut self.state, handle);
         self.state
             .focus_manager
             .tags_last_window
             .retain(|_, h| h != handle);
+        for tag in self.state.tags.all_mut() {
+            tag.minimized.retain(|h| h != handle);
+        }
         self.state.windows.retain(|w| &w.handle != handle);
 
         self.state.handle_single_border(self.config.border_width());
@@ -96,6 +123,8 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             {
                 let act = DisplayAction::FocusWindowUnderCursor;
                 self.state.actions.push_back(act);
+            } else if let Some(terminal) = terminal {
+                self.state.focus_window(&terminal);
             } else if let Some(parent) =
                 find_transient_parent(&self.state.windows, transient).map(|p| p.handle)
             {
@@ -110,13 +139,18 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         }
 
         // Only update windows if this window is visible.
-        visible
+        visible || terminal.is_some()
     }
 
     pub fn window_changed_handler(&mut self, change: WindowChange) -> bool {
         let mut changed = false;
         let mut fullscreen_changed = false;
+        let mut maximized_changed = false;
+        let mut minimized = None;
+        let handle = change.handle;
         let strut_changed = change.strut.is_some();
+        let renamed =
+            change.name.is_some() || change.res_name.is_some() || change.res_class.is_some();
         let windows = self.state.windows.clone();
         if let Some(window) = self
             .state
@@ -127,6 +161,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             if let Some(ref states) = change.states {
                 let change_contains = states.contains(&WindowState::Fullscreen);
                 fullscreen_changed = change_contains || window.is_fullscreen();
+                let maximized = states.contains(&WindowState::MaximizedVert)
+                    && states.contains(&WindowState::MaximizedHorz);
+                maximized_changed = maximized != window.is_maximized();
             }
             let container = match find_transient_parent(&windows, window.transient) {
                 Some(parent) => Some(parent.exact_xyhw()),
@@ -134,12 +171,16 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
                     .state
                     .workspaces
                     .iter()
-                    .find(|ws| ws.tag == window.tag)
+                    .find(|ws| ws.owns(window, &self.state.workspaces))
                     .map(|ws| ws.xyhw),
                 _ => None,
             };
 
+            let was_minimized = window.is_minimized();
             changed = change.update(window, container);
+            if window.is_minimized() != was_minimized {
+                minimized = Some(window.is_minimized());
+            }
             if window.r#type == WindowType::Dock {
                 update_workspace_avoid_list(&mut self.state);
                 // Don't let changes from docks re-render the worker. This will result in an
@@ -161,17 +202,116 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             // Reorder windows.
             self.state.sort_windows();
         }
+        if maximized_changed && !fullscreen_changed {
+            // Restack the window.
+            self.state.sort_windows();
+        }
         if strut_changed {
             self.state.update_static();
         }
+        match minimized {
+            Some(true) => _ = self.state.minimize_window(&handle),
+            Some(false) => _ = self.state.restore_window(&handle),
+            None => {}
+        }
+        if renamed {
+            if let Some(previous) = windows.iter().find(|w| w.handle == handle) {
+                changed = self.reapply_window_rules(previous) || changed;
+            }
+        }
         changed
     }
 
+    /// Runs the window rules again which ask for it after the title or class of a window changed.
+    /// The window is moved to the tag and floated as the matching rule says, takes the margin
+    /// multiplier of its new tag and becomes a scratchpad if the rule spawns one.
+    /// Returns true if a rule was applied.
+    fn reapply_window_rules(&mut self, previous: &Window) -> bool {
+        let handle = previous.handle;
+        let Some(mut window) = self
+            .state
+            .windows
+            .iter()
+            .find(|w| w.handle == handle)
+            .cloned()
+        else {
+            return false;
+        };
+        if !self
+            .config
+            .reapply_predefined_window(&mut self.state, &mut window, previous)
+        {
+            return false;
+        }
+        self.config.load_window(&mut window);
+        if window.r#type == WindowType::Normal {
+            let workspaces = &self.state.workspaces;
+            let margin_multiplier = tag_margin_multiplier(&self.state, &window).or_else(|| {
+                let ws = workspaces.iter().find(|ws| ws.owns(&window, workspaces));
+                ws.map(|ws| ws.margin_multiplier)
+            });
+            if let Some(margin_multiplier) = margin_multiplier {
+                window.apply_margin_multiplier(margin_multiplier);
+            }
+        }
+
+        let is_focused = self.state.focus_manager.window_history.front() == Some(&Some(handle));
+        let next = if is_focused && window.tag != previous.tag {
+            self.get_next_or_previous_handle(&handle)
+        } else {
+            None
+        };
+        if !window.extra_tags.is_empty() && window.tags() != previous.tags() {
+            let act = DisplayAction::SetWindowTags(handle, window.tags());
+            self.state.actions.push_back(act);
+        } else if window.tag != previous.tag {
+            let act = DisplayAction::SetWindowTag(handle, window.tag);
+            self.state.actions.push_back(act);
+        }
+        if window.floating() && !previous.floating() {
+            let ws = self
+                .state
+                .workspaces
+                .iter()
+                .find(|ws| ws.owns(&window, &self.state.workspaces));
+            if let Some(ws) = ws {
+                let size = window.requested.unwrap_or_else(|| ws.center_halfed());
+                let center = previous.calculated_xyhw().center();
+                place_floating(&self.state, &mut window, ws, size, center);
+            }
+        }
+        if let Some(color) = &window.border_color {
+            let act = DisplayAction::SetWindowBorderColor(handle, color.clone());
+            self.state.actions.push_back(act);
+        }
+        let scratchpad = window
+            .spawn_scratchpad
+            .clone()
+            .filter(|scratchpad| previous.spawn_scratchpad.as_ref() != Some(scratchpad));
+        if let Some(w) = self.state.windows.iter_mut().find(|w| w.handle == handle) {
+            *w = window;
+        }
+
+        self.state.sort_windows();
+        self.state.handle_single_border(self.config.border_width());
+        if let Some(next) = next {
+            self.state.focus_window(&next);
+        }
+
+        if let Some(scratchpad) = scratchpad {
+            let window = Some(handle);
+            self.command_handler(&Command::AttachScratchPad { window, scratchpad });
+        }
+        true
+    }
+
     /// Find the next or previous window on the currently focused workspace.
     /// May return `None` if no other window is present.
     pub fn get_next_or_previous_handle(&mut self, handle: &WindowHandle) -> Option<WindowHandle> {
         let focused_workspace = self.state.focus_manager.workspace(&self.state.workspaces)?;
-        let on_focused_workspace = |x: &Window| -> bool { focused_workspace.is_managed(x) };
+        let workspaces = &self.state.workspaces;
+        let on_focused_workspace =
+            |x: &Window| -> bool { focused_workspace.manages(x, workspaces) };
         let mut windows_on_workspace =
             helpers::vec_extract(&mut self.state.windows, on_focused_workspace);
         let is_handle = |x: &Window| -> bool { &x.handle == handle };
@@ -216,6 +356,83 @@ fn find_terminal(state: &State, pid: Option<u32>) -> Option<&Window> {
     None
 }
 
+/// The terminal a new window takes the place of, if it was launched from one which swallows its
+/// windows.
+fn find_swallowable_terminal(state: &State, window: &Window) -> Option<WindowHandle> {
+    if window.no_swallow
+        || window.r#type != WindowType::Normal
+        || window.transient.is_some()
+        || window.floating()
+        || is_scratchpad(state, window)
+    {
+        return None;
+    }
+    find_terminal(state, window.pid)
+        .filter(|terminal| {
+            terminal.is_terminal
+                && terminal.swallowed.is_none()
+                && terminal.tag == window.tag
+                && terminal.is_managed()
+                && !terminal.floating()
+        })
+        .map(|terminal| terminal.handle)
+}
+
+/// Hides the terminal and puts the window in its place.
+fn swallow_terminal(state: &mut State, window: &mut Window, terminal: WindowHandle) {
+    let Some(index) = state.windows.iter().position(|w| w.handle == terminal) else {
+        return;
+    };
+    let Some(hidden_tag) = state.tags.get_hidden_by_label("SWALLOWED").map(|t| t.id) else {
+        return;
+    };
+    let swallowed = &mut state.windows[index];
+    window.set_tag(swallowed.tag);
+    window.size_weight = swallowed.size_weight;
+    window.column_width = swallowed.column_width;
+    window.swallowed = Some(terminal);
+    if let Some(tag) = window.tag.and_then(|id| state.tags.get_mut(id)) {
+        tag.split_tree.replace(terminal, window.handle);
+    }
+
+    swallowed.set_tag(Some(hidden_tag));
+    swallowed.set_visible(false);
+    let act = DisplayAction::SetWindowTag(terminal, Some(hidden_tag));
+    state.actions.push_back(act);
+    state
+        .focus_manager
+        .tags_last_window
+        .values_mut()
+        .filter(|h| **h == terminal)
+        .for_each(|h| *h = window.handle);
+    state.windows.insert(index, window.clone());
+}
+
+/// Puts the terminal swallowed by a window back into the place of the window.
+/// Returns the handle of the terminal.
+fn restore_swallowed_terminal(state: &mut State, handle: &WindowHandle) -> Option<WindowHandle> {
+    let window = state.windows.iter().find(|w| &w.handle == handle)?;
+    let (tag, size_weight, column_width) = (window.tag, window.size_weight, window.column_width);
+    let terminal = state
+        .windows
+        .iter()
+        .position(|w| Some(w.handle) == window.swallowed)?;
+    let mut terminal = state.windows.remove(terminal);
+    terminal.set_tag(tag);
+    terminal.size_weight = size_weight;
+    terminal.column_width = column_width;
+    if let Some(tag) = tag.and_then(|id| state.tags.get_mut(id)) {
+        tag.split_tree.replace(*handle, terminal.handle);
+    }
+    let act = DisplayAction::SetWindowTag(terminal.handle, tag);
+    state.actions.push_back(act);
+
+    let index = state.windows.iter().position(|w| &w.handle == handle)?;
+    let restored = terminal.handle;
+    state.windows.insert(index, terminal);
+    Some(restored)
+}
+
 fn find_transient_parent(windows: &[Window], transient: Option<WindowHandle>) -> Option<&Window> {
     let mut transient = transient?;
     loop {
@@ -231,10 +448,14 @@ fn find_transient_parent(windows: &[Window], transient: Option<WindowHandle>) ->
     }
 }
 
-fn insert_window(state: &mut State, window: &mut Window, layout: Layout) {
+fn insert_window(state: &mut State, window: &mut Window, layout: &Layout) {
     let mut was_fullscreen = false;
     if window.r#type == WindowType::Normal {
-        let for_active_workspace = |x: &Window| -> bool { window.tag == x.tag && x.is_managed() };
+        let workspaces = &state.workspaces;
+        let ws = workspaces.iter().find(|ws| ws.owns(window, workspaces));
+        let for_active_workspace = |x: &Window| -> bool {
+            ws.map_or(window.tag == x.tag, |ws| ws.owns(x, workspaces)) && x.is_managed()
+        };
         // Only minimize when the new window is type normal.
         if let Some(fsw) = state
             .windows
@@ -249,7 +470,7 @@ fn insert_window(state: &mut State, window: &mut Window, layout: Layout) {
         if matches!(layout, Layout::Monocle | Layout::MainAndDeck) {
             // Extract the current windows on the same workspace.
             let mut to_reorder = helpers::vec_extract(&mut state.windows, for_active_workspace);
-            if layout == Layout::Monocle || to_reorder.is_empty() {
+            if *layout == Layout::Monocle || to_reorder.is_empty() {
                 // When in monocle we want the new window to be fullscreen if the previous window was
                 // fullscreen.
                 if was_fullscreen {
@@ -293,7 +514,15 @@ fn insert_window(state: &mut State, window: &mut Window, layout: Layout) {
         .unwrap_or(0);
 
     // Past special cases we just insert the window based on the configured insert behavior
-    match state.insert_behavior {
+    let tag_insert_behavior = window
+        .tag
+        .and_then(|id| state.tags.get(id))
+        .and_then(|tag| tag.config.insert_behavior);
+    match window
+        .insert_behavior
+        .or(tag_insert_behavior)
+        .unwrap_or(state.insert_behavior)
+    {
         InsertBehavior::Top => state.windows.insert(0, window.clone()),
         InsertBehavior::Bottom => state.windows.push(window.clone()),
         InsertBehavior::AfterCurrent if current_index < state.windows.len() => {
@@ -305,6 +534,14 @@ fn insert_window(state: &mut State, window: &mut Window, layout: Layout) {
     }
 }
 
+/// The margin multiplier set for the tag of the window, if any.
+fn tag_margin_multiplier(state: &State, window: &Window) -> Option<f32> {
+    window
+        .tag
+        .and_then(|id| state.tags.get(id))
+        .and_then(|tag| tag.config.margin_multiplier)
+}
+
 fn is_scratchpad(state: &State, window: &Window) -> bool {
     state
         .active_scratchpads
@@ -315,21 +552,42 @@ fn is_scratchpad(state: &State, window: &Window) -> bool {
 fn set_relative_floating(window: &mut Window, ws: &Workspace, outer: Xyhw) {
     window.set_floating(true);
     window.normal = ws.xyhw;
-    let xyhw = window.requested.map_or_else(
-        || ws.center_halfed(),
-        |mut requested| {
-            requested.center_relative(outer, window.border);
-            if ws.xyhw.contains_xyhw(&requested) {
-                requested
-            } else {
-                requested.center_relative(ws.xyhw, window.border);
-                requested
-            }
-        },
-    );
+    let mut xyhw = window.requested.unwrap_or_else(|| ws.center_halfed());
+    xyhw.center_relative(outer, window.border);
+    if !ws.xyhw.contains_xyhw(&xyhw) {
+        xyhw.center_relative(ws.xyhw, window.border);
+    }
     window.set_floating_exact(xyhw);
 }
 
+/// Puts a new floating window of the given size on its workspace, following the
+/// `floating_placement` of the window or else the one of the config. A `floating_geometry` of the
+/// window takes precedence over both.
+fn place_floating(state: &State, window: &mut Window, ws: &Workspace, size: Xyhw, xy: (i32, i32)) {
+    window.set_floating(true);
+    window.normal = ws.xyhw;
+    let placement = window
+        .floating_placement
+        .unwrap_or(state.floating_placement);
+    let area = XyhwBuilder {
+        x: ws.x(),
+        y: ws.y(),
+        h: ws.height(),
+        w: ws.width(),
+        ..XyhwBuilder::default()
+    }
+    .into();
+    let others: Vec<Xyhw> = state
+        .windows
+        .iter()
+        .filter(|w| ws.manages(w, &state.workspaces) && w.floating())
+        .map(Window::exact_xyhw)
+        .collect();
+    let geometry = window.floating_geometry.unwrap_or_default();
+    let placed = placement.place(geometry.resize(size, area), area, xy, &others);
+    window.set_floating_exact(geometry.position(placed, area));
+}
+
 fn setup_window(
     state: &mut State,
     window: &mut Window,
@@ -349,7 +607,8 @@ fn setup_window(
 
     if let Some(ws) = ws {
         // Setup basic variables.
-        let for_active_workspace = |x: &Window| -> bool { ws.tag == x.tag && x.is_managed() };
+        let for_active_workspace =
+            |x: &Window| -> bool { ws.owns(x, &state.workspaces) && x.is_managed() };
         *is_first = !state.windows.iter().any(|w| for_active_workspace(w));
         // May have been set by a predefined tag.
         if window.tag.is_none() {
@@ -357,7 +616,7 @@ fn setup_window(
                 find_terminal(state, window.pid).map_or_else(|| ws.tag, |terminal| terminal.tag);
         }
         *on_same_tag = ws.tag == window.tag;
-        *layout = ws.layout;
+        *layout = ws.layout.clone();
 
         // Setup a scratchpad window.
         if let Some((scratchpad_name, _)) = state
@@ -391,20 +650,19 @@ fn setup_window(
         // Setup window based on type.
         match window.r#type {
             WindowType::Normal => {
-                window.apply_margin_multiplier(ws.margin_multiplier);
+                let margin_multiplier = tag_margin_multiplier(state, window);
+                window.apply_margin_multiplier(margin_multiplier.unwrap_or(ws.margin_multiplier));
                 if window.floating() {
-                    set_relative_floating(window, ws, ws.xyhw);
+                    let size = window.requested.unwrap_or_else(|| ws.center_halfed());
+                    place_floating(state, window, ws, size, xy);
                 }
             }
             WindowType::Dialog => {
-                if window.can_resize() {
-                    window.set_floating(true);
-                    let new_float_exact = ws.center_halfed();
-                    window.normal = ws.xyhw;
-                    window.set_floating_exact(new_float_exact);
-                } else {
-                    set_relative_floating(window, ws, ws.xyhw);
-                }
+                let size = match window.requested {
+                    Some(requested) if !window.can_resize() => requested,
+                    _ => ws.center_halfed(),
+                };
+                place_floating(state, window, ws, size, xy);
             }
             WindowType::Splash => set_relative_floating(window, ws, ws.xyhw),
             _ => {}
@@ -413,10 +671,10 @@ fn setup_window(
     }
 
     // Setup a window is workspace is `None`. This shouldn't really happen.
-    window.tag = Some(1);
+    window.set_tag(Some(1));
     if is_scratchpad(state, window) {
         if let Some(scratchpad_tag) = state.tags.get_hidden_by_label("NSP") {
-            window.tag(&scratchpad_tag.id);
+            window.set_tag(Some(scratchpad_tag.id));
             window.set_floating(true);
         }
     }
@@ -500,6 +758,40 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn insert_behavior_of_the_tag_overrides_the_global_one() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.state.insert_behavior = InsertBehavior::Bottom;
+
+        manager.screen_create_handler(Screen::default());
+        manager
+            .state
+            .tags
+            .get_mut(1)
+            .unwrap()
+            .config
+            .insert_behavior = Some(InsertBehavior::Top);
+        manager
+            .state
+            .tags
+            .get_mut(1)
+            .unwrap()
+            .config
+            .margin_multiplier = Some(2.0);
+        for i in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+
+        let expected = vec![WindowHandle::MockHandle(2), WindowHandle::MockHandle(1)];
+        let actual: Vec<WindowHandle> = manager.state.windows.iter().map(|w| w.handle).collect();
+        assert_eq!(actual, expected);
+        assert!((manager.state.windows[0].margin_multiplier() - 2.0).abs() < f32::EPSILON);
+    }
+
     #[test]
     fn insert_behavior_after_current_add_window_after_the_current_window() {
         let mut manager = Manager::new_test(vec![]);
@@ -708,4 +1000,205 @@ mod tests {
         assert_eq!((manager.state.windows[0]).border(), 0);
         assert_eq!((manager.state.windows[1]).border(), 0);
     }
+
+    #[test]
+    fn swallowed_terminal_is_restored_in_place() {
+        let mut manager = Manager::new_test(vec![]);
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        let mut app = Window::new(WindowHandle::MockHandle(4), None, None);
+        app.tag = Some(1);
+        swallow_terminal(&mut manager.state, &mut app, WindowHandle::MockHandle(2));
+        manager.update_windows();
+
+        let hidden_tag = manager.state.tags.get_hidden_by_label("SWALLOWED").unwrap();
+        let handles: Vec<WindowHandle> = manager.state.windows.iter().map(|w| w.handle).collect();
+        let index = handles.iter().position(|h| h == &app.handle).unwrap();
+        assert_eq!(handles[index + 1], WindowHandle::MockHandle(2));
+        let terminal = &manager.state.windows[index + 1];
+        assert!(terminal.has_tag(&hidden_tag.id));
+        assert!(!terminal.visible());
+
+        manager.window_destroyed_handler(&app.handle);
+        manager.update_windows();
+        let restored: Vec<WindowHandle> = manager.state.windows.iter().map(|w| w.handle).collect();
+        assert_eq!(restored[index], WindowHandle::MockHandle(2));
+        assert!(manager.state.windows[index].has_tag(&1));
+        assert!(manager.state.windows[index].visible());
+    }
+
+    #[test]
+    fn floating_windows_are_placed_by_the_placement_policy() {
+        use crate::config::FloatingPlacement;
+
+        let mut manager = Manager::new_test(vec![]);
+        manager.state.floating_placement = FloatingPlacement::UnderCursor;
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.update_windows();
+        let floating = |id: i32| {
+            let mut window = Window::new(WindowHandle::MockHandle(id), None, None);
+            window.set_floating(true);
+            window.requested = Some(
+                XyhwBuilder {
+                    h: 100,
+                    w: 200,
+                    ..XyhwBuilder::default()
+                }
+                .into(),
+            );
+            window
+        };
+        let position = |manager: &Manager<_, _>, id: i32| {
+            let handle = WindowHandle::MockHandle(id);
+            let window = manager.state.windows.iter().find(|w| w.handle == handle);
+            let xyhw = window.unwrap().exact_xyhw();
+            (xyhw.x(), xyhw.y())
+        };
+
+        manager.window_created_handler(floating(3), 500, 300);
+        assert_eq!(position(&manager, 3), (400, 250));
+
+        // Window rules can pick another placement.
+        let mut window = floating(4);
+        window.floating_placement = Some(FloatingPlacement::Cascade);
+        manager.window_created_handler(window, 500, 300);
+        assert_eq!(position(&manager, 4), (32, 32));
+
+        // Dialogs are centered on their parent, the main window on the left half.
+        let mut dialog = floating(5);
+        dialog.r#type = WindowType::Dialog;
+        dialog.can_resize = false;
+        dialog.transient = Some(WindowHandle::MockHandle(1));
+        manager.window_created_handler(dialog, 500, 300);
+        assert_eq!(position(&manager, 5), (99, 249));
+    }
+
+    #[test]
+    fn window_rule_overrides_are_applied() {
+        use crate::models::ScratchPad;
+
+        let mut manager = Manager::new_test_with_border(vec![], 1);
+        manager.state.scratchpads.push(ScratchPad {
+            name: "term".into(),
+            value: "term".to_string(),
+            x: None,
+            y: None,
+            height: None,
+            width: None,
+        });
+        manager.screen_create_handler(Screen::default());
+        let mut skipped = Window::new(WindowHandle::MockHandle(1), None, None);
+        skipped.skip_focus_cycle = true;
+        manager.window_created_handler(skipped, -1, -1);
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(2), None, None),
+            -1,
+            -1,
+        );
+        let mut main = Window::new(WindowHandle::MockHandle(3), None, None);
+        main.insert_behavior = Some(InsertBehavior::Top);
+        main.border_width = Some(4);
+        main.border_color = Some("#ff0000".to_string());
+        manager.window_created_handler(main, -1, -1);
+
+        let handles: Vec<WindowHandle> = manager.state.windows.iter().map(|w| w.handle).collect();
+        let expected: Vec<WindowHandle> = [3, 1, 2].map(WindowHandle::MockHandle).to_vec();
+        assert_eq!(handles, expected);
+        let borders: Vec<i32> = manager.state.windows.iter().map(Window::border).collect();
+        assert_eq!(borders, vec![4, 1, 1]);
+        assert!(manager.state.actions.iter().any(|a| matches!(
+            a,
+            DisplayAction::SetWindowBorderColor(WindowHandle::MockHandle(3), color) if color == "#ff0000"
+        )));
+
+        // The focus cycles past the skipped window.
+        manager.state.focus_window(&WindowHandle::MockHandle(3));
+        manager.command_handler(&Command::FocusWindowDown);
+        let focused = manager.state.focus_manager.window(&manager.state.windows);
+        assert_eq!(focused.unwrap().handle, WindowHandle::MockHandle(2));
+
+        let mut scratchpad = Window::new(WindowHandle::MockHandle(4), None, Some(4));
+        scratchpad.spawn_scratchpad = Some("term".into());
+        manager.window_created_handler(scratchpad, -1, -1);
+        let attached = manager.state.active_scratchpads.get(&"term".into());
+        assert_eq!(attached.and_then(|pids| pids.front()), Some(&4));
+    }
+
+    #[test]
+    fn window_rules_are_reapplied_when_the_class_changes() {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        let handle = WindowHandle::MockHandle(1);
+        manager.window_created_handler(Window::new(handle, None, None), -1, -1);
+        assert_eq!(manager.state.windows[0].tag, Some(1));
+
+        let mut change = WindowChange::new(handle);
+        change.res_class = Some(Some("ShouldGoToTag2".to_string()));
+        assert!(manager.window_changed_handler(change));
+        assert_eq!(manager.state.windows[0].tag, Some(2));
+        assert!(manager
+            .state
+            .actions
+            .iter()
+            .any(|a| matches!(a, DisplayAction::SetWindowTag(h, Some(2)) if h == &handle)));
+    }
+
+    #[test]
+    fn reapplied_window_rules_keep_the_margin_multiplier_and_spawn_scratchpads() {
+        use crate::models::ScratchPad;
+
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.state.scratchpads.push(ScratchPad {
+            name: "term".into(),
+            value: "term".to_string(),
+            x: None,
+            y: None,
+            height: None,
+            width: None,
+        });
+        manager.screen_create_handler(Screen::default());
+        manager.state.workspaces[0].margin_multiplier = 3.0;
+        manager
+            .state
+            .tags
+            .get_mut(2)
+            .unwrap()
+            .config
+            .margin_multiplier = Some(2.0);
+        for i in 1..=2 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, Some(i as u32)),
+                -1,
+                -1,
+            );
+        }
+        let margin_multiplier = |manager: &Manager<_, _>, i: usize| -> f32 {
+            manager.state.windows[i].margin_multiplier()
+        };
+        assert!((margin_multiplier(&manager, 0) - 3.0).abs() < f32::EPSILON);
+
+        let mut change = WindowChange::new(WindowHandle::MockHandle(1));
+        change.res_class = Some(Some("ShouldGoToTag2".to_string()));
+        assert!(manager.window_changed_handler(change));
+        assert!((margin_multiplier(&manager, 0) - 2.0).abs() < f32::EPSILON);
+
+        let mut change = WindowChange::new(WindowHandle::MockHandle(2));
+        change.res_class = Some(Some("ShouldBeScratchPad".to_string()));
+        assert!(manager.window_changed_handler(change));
+        let attached = manager.state.active_scratchpads.get(&"term".into());
+        assert_eq!(attached.and_then(|pids| pids.front()), Some(&2));
+    }
 }
@@ -1,7 +1,9 @@
 use super::{Manager, Window, WindowHandle, Workspace};
 use crate::config::Config;
+use crate::display_action::DisplayAction;
 use crate::display_servers::DisplayServer;
 use crate::models::Xyhw;
+use crate::state::State;
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     pub fn window_move_handler(
@@ -12,6 +14,10 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     ) -> bool {
         let disable_snap = &self.config.disable_window_snap();
         match self.state.windows.iter_mut().find(|w| w.handle == *handle) {
+            // Only windows dragged with `reorder_tile_drag` stay tiled while moving.
+            Some(w) if !w.floating() => {
+                drag_tiled(&mut self.state, handle, offset_x, offset_y).unwrap_or_default()
+            }
             Some(w) => {
                 process_window(w, offset_x, offset_y);
                 if !disable_snap && snap_to_workspace(w, &self.state.workspaces) {
@@ -24,6 +30,60 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     }
 }
 
+/// Keeps track of where a tiled window is dragged to. The window is dropped there once the drag
+/// ends, see [`State::drop_tiled_window`].
+fn drag_tiled(
+    state: &mut State,
+    handle: &WindowHandle,
+    offset_x: i32,
+    offset_y: i32,
+) -> Option<bool> {
+    let window = state.windows.iter().find(|w| w.handle == *handle)?;
+    let (x, y) = window.start_loc?.center();
+    state.tile_drop = Some((x + offset_x, y + offset_y));
+    Some(false)
+}
+
+impl State {
+    /// Swaps a dragged tiled window with the tiled window it was dropped on, and moves it to the
+    /// tag of the workspace it was dropped on.
+    /// Returns true if the window moved.
+    pub(crate) fn drop_tiled_window(&mut self, handle: &WindowHandle) -> bool {
+        let Some((x, y)) = self.tile_drop.take() else {
+            return false;
+        };
+        let Some(index) = self.windows.iter().position(|w| w.handle == *handle) else {
+            return false;
+        };
+        let Some(workspace) = self.workspaces.iter().find(|ws| ws.contains_point(x, y)) else {
+            return false;
+        };
+
+        let mut changed = false;
+        let target = self.windows.iter().position(|w| {
+            w.handle != *handle
+                && workspace.manages(w, &self.workspaces)
+                && !w.floating()
+                && w.calculated_xyhw().contains_point(x, y)
+        });
+        if let Some(target) = target {
+            self.windows.swap(index, target);
+            changed = true;
+        }
+
+        let Some(window) = self.windows.iter_mut().find(|w| w.handle == *handle) else {
+            return changed;
+        };
+        if !workspace.is_displaying(window) {
+            window.snap_to_workspace(workspace);
+            let act = DisplayAction::SetWindowTag(window.handle, window.tag);
+            self.actions.push_back(act);
+            changed = true;
+        }
+        changed
+    }
+}
+
 fn process_window(window: &mut Window, offset_x: i32, offset_y: i32) {
     let mut offset = window.get_floating_offsets().unwrap_or_default();
     let start = window.start_loc.unwrap_or_default();
@@ -74,3 +134,101 @@ fn should_snap(window: &mut Window, workspace: &Workspace, loc: Xyhw) -> bool {
     }
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::tests::TestConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::models::{BBox, Mode, Screen};
+    use crate::DisplayEvent;
+
+    /// Three windows on the second of two workspaces, dragged with `reorder_tile_drag`.
+    fn manager_with_dragged_tiles() -> Manager<TestConfig, MockDisplayServer> {
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.state.reorder_tile_drag = true;
+        manager.screen_create_handler(Screen::default());
+        manager.screen_create_handler(Screen {
+            bbox: BBox {
+                height: 600,
+                width: 800,
+                x: 800,
+                y: 0,
+            },
+            ..Screen::default()
+        });
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.update_windows();
+        manager
+    }
+
+    #[test]
+    fn dragging_a_tiled_window_reorders_and_retags_it() {
+        let mut manager = manager_with_dragged_tiles();
+        let mut drag = |id: i32, x: i32, y: i32| {
+            let handle = WindowHandle::MockHandle(id);
+            manager.state.mode = Mode::ReadyToMove(handle);
+            manager.display_event_handler(DisplayEvent::MoveWindow(handle, x, y));
+            manager.display_event_handler(DisplayEvent::ChangeToNormalMode);
+            manager.update_windows();
+            manager
+                .state
+                .windows
+                .iter()
+                .map(|w| (w.handle, w.tag, w.floating()))
+                .collect::<Vec<_>>()
+        };
+
+        // From the main area onto the last window of the stack.
+        assert_eq!(
+            drag(1, 400, 150),
+            vec![
+                (WindowHandle::MockHandle(3), Some(2), false),
+                (WindowHandle::MockHandle(2), Some(2), false),
+                (WindowHandle::MockHandle(1), Some(2), false),
+            ]
+        );
+        // Onto the empty workspace.
+        assert_eq!(
+            drag(2, -800, 0)[1],
+            (WindowHandle::MockHandle(2), Some(1), false)
+        );
+    }
+
+    #[test]
+    fn dragged_tiled_windows_only_move_once_dropped() {
+        let mut manager = manager_with_dragged_tiles();
+        let handle = WindowHandle::MockHandle(1);
+        manager.state.mode = Mode::ReadyToMove(handle);
+        manager.state.actions.clear();
+        // Over the first window of the stack and the other workspace onto the last window.
+        for (x, y) in [(400, -150), (-800, 0), (400, 150)] {
+            manager.display_event_handler(DisplayEvent::MoveWindow(handle, x, y));
+        }
+        let order = |manager: &Manager<_, _>| -> Vec<WindowHandle> {
+            manager.state.windows.iter().map(|w| w.handle).collect()
+        };
+        assert_eq!(
+            order(&manager),
+            (1..=3).map(WindowHandle::MockHandle).collect::<Vec<_>>()
+        );
+
+        manager.display_event_handler(DisplayEvent::ChangeToNormalMode);
+        assert_eq!(
+            order(&manager),
+            [3, 2, 1].map(WindowHandle::MockHandle).to_vec()
+        );
+        assert!(manager.state.windows.iter().all(|w| w.tag == Some(2)));
+        assert!(!manager
+            .state
+            .actions
+            .iter()
+            .any(|a| matches!(a, DisplayAction::SetWindowTag(..))));
+    }
+}
@@ -1,6 +1,7 @@
 use super::{Manager, Window, WindowHandle};
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
+use crate::state::State;
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
     pub fn window_resize_handler(
@@ -10,6 +11,9 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
         offset_h: i32,
     ) -> bool {
         if let Some(w) = self.state.windows.iter_mut().find(|w| &w.handle == handle) {
+            if !w.floating() {
+                return resize_tiled(&mut self.state, handle, offset_w, offset_h).is_some();
+            }
             process_window(w, offset_w, offset_h);
             return true;
         }
@@ -26,3 +30,216 @@ fn process_window(window: &mut Window, offset_w: i32, offset_h: i32) {
     offset.set_h(start.h() + offset_h);
     window.set_floating_offsets(Some(offset));
 }
+
+/// Moves the bottom right corner of a tiled window by changing the layout instead of the
+/// window. Between windows of the same area the size weights are shifted, the border between
+/// the main area and the stack changes the main width of the tag. Only the borders the corner
+/// touches are moved, and only in layouts which size their windows that way.
+fn resize_tiled(
+    state: &mut State,
+    handle: &WindowHandle,
+    offset_w: i32,
+    offset_h: i32,
+) -> Option<bool> {
+    let window = state.windows.iter().find(|w| &w.handle == handle)?;
+    let start = window.start_loc?;
+    let workspaces = &state.workspaces;
+    let ws_index = workspaces
+        .iter()
+        .position(|ws| ws.owns(window, workspaces))?;
+    let workspace = &workspaces[ws_index];
+    let tag = state.tags.get_mut(workspace.tag?)?;
+
+    let mut tiled: Vec<&mut Window> = state
+        .windows
+        .iter_mut()
+        .filter(|w| workspace.manages(w, workspaces) && !w.floating())
+        .collect();
+    let index = tiled.iter().position(|w| &w.handle == handle)?;
+    let main_count = tag.main_count.clamp(1, tiled.len());
+    let is_main = index < main_count;
+    let current = tiled[index].normal;
+    let (right, bottom) = (current.x() + current.w(), current.y() + current.h());
+
+    // A neighbour of the same area sharing the right or bottom border, which is the next
+    // window unless the tag is flipped.
+    let area = if is_main {
+        0..main_count
+    } else {
+        main_count..tiled.len()
+    };
+    let neighbour = [index + 1, index.wrapping_sub(1)]
+        .into_iter()
+        .filter(|i| area.contains(i))
+        .find_map(|i| {
+            let other = tiled[i].normal;
+            if other.x() == current.x() && other.y() == bottom {
+                Some((i, false))
+            } else if other.y() == current.y() && other.x() == right {
+                Some((i, true))
+            } else {
+                None
+            }
+        });
+    if let Some((other, horizontal)) = neighbour.filter(|_| tag.layout.uses_size_weights()) {
+        let (target, pair_size) = if horizontal {
+            (start.w() + offset_w, current.w() + tiled[other].normal.w())
+        } else {
+            (start.h() + offset_h, current.h() + tiled[other].normal.h())
+        };
+        if pair_size > 0 {
+            let pair_weight = tiled[index].size_weight + tiled[other].size_weight;
+            let share = (target as f32 / pair_size as f32).clamp(0.1, 0.9);
+            tiled[index].size_weight = (pair_weight * share).clamp(0.25, 4.0);
+            tiled[other].size_weight = (pair_weight * (1.0 - share)).clamp(0.25, 4.0);
+        }
+    }
+
+    if main_count == tiled.len() || !tag.layout.has_main_and_stack() {
+        return Some(true);
+    }
+    // The border between the main area and the stack, if the corner touches it. The main area
+    // is on either side of the stack, or above or below it.
+    let (main, stack) = tiled.split_at(main_count);
+    let (main, stack) = (bounds(main), bounds(stack));
+    let (before, after) = if is_main {
+        (main, stack)
+    } else {
+        (stack, main)
+    };
+    let (target, size) = if before.2 == right && after.0 == right {
+        (start.w() + offset_w, current.w())
+    } else if before.3 == bottom && after.1 == bottom {
+        (start.h() + offset_h, current.h())
+    } else {
+        return Some(true);
+    };
+    if size > 0 {
+        let main_width = f32::from(tag.main_width_percentage);
+        let percentage = if is_main {
+            main_width * target as f32 / size as f32
+        } else {
+            100.0 - (100.0 - main_width) * target as f32 / size as f32
+        };
+        let percentage = percentage.round().clamp(5.0, 95.0) as u8;
+        tag.set_main_width(percentage);
+        state.workspaces[ws_index].main_width_percentage = percentage;
+    }
+    Some(true)
+}
+
+/// The left, top, right and bottom edges of the area taken by the windows.
+fn bounds(windows: &[&mut Window]) -> (i32, i32, i32, i32) {
+    let left = windows.iter().map(|w| w.normal.x()).min();
+    let top = windows.iter().map(|w| w.normal.y()).min();
+    let right = windows.iter().map(|w| w.normal.x() + w.normal.w()).max();
+    let bottom = windows.iter().map(|w| w.normal.y() + w.normal.h()).max();
+    (
+        left.unwrap_or_default(),
+        top.unwrap_or_default(),
+        right.unwrap_or_default(),
+        bottom.unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::Layout;
+    use crate::models::{Mode, Screen};
+    use crate::DisplayEvent;
+
+    fn drag(manager: &mut Manager<impl Config, impl DisplayServer>, id: u32, x: i32, y: i32) {
+        let handle = WindowHandle::MockHandle(id as i32);
+        manager.state.mode = Mode::ReadyToResize(handle);
+        manager.display_event_handler(DisplayEvent::ResizeWindow(handle, x, y));
+        manager.display_event_handler(DisplayEvent::ChangeToNormalMode);
+        manager.update_windows();
+    }
+
+    #[test]
+    fn resizing_tiled_windows_changes_the_layout() {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.update_windows();
+
+        drag(&mut manager, 1, 80, 0);
+        assert_eq!(manager.state.tags.get(1).unwrap().main_width_percentage, 60);
+        assert!(!manager.state.windows[0].floating());
+
+        drag(&mut manager, 2, 0, 60);
+        let heights: Vec<i32> = manager.state.windows[1..]
+            .iter()
+            .map(|w| w.normal.h())
+            .collect();
+        assert_eq!(heights, vec![360, 240]);
+    }
+
+    fn manager_with_windows(
+        layout: Layout,
+        main_width: u8,
+    ) -> Manager<impl Config, impl DisplayServer> {
+        let mut manager = Manager::new_test(vec!["1".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        for i in 1..=3 {
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(i), None, None),
+                -1,
+                -1,
+            );
+        }
+        manager.state.workspaces[0].layout = layout.clone();
+        manager.state.workspaces[0].main_width_percentage = main_width;
+        manager
+            .state
+            .tags
+            .get_mut(1)
+            .unwrap()
+            .set_layout(layout, main_width);
+        manager.update_windows();
+        manager
+    }
+
+    fn main_width(manager: &Manager<impl Config, impl DisplayServer>) -> u8 {
+        manager.state.tags.get(1).unwrap().main_width_percentage
+    }
+
+    #[test]
+    fn resizing_tiled_windows_of_a_flipped_tag_moves_the_touched_border() {
+        let mut manager = manager_with_windows(Layout::MainAndVertStack, 50);
+        manager.state.tags.get_mut(1).unwrap().flipped_horizontal = true;
+        manager.update_windows();
+
+        // The main window is on the right, its right border is the edge of the workspace.
+        drag(&mut manager, 1, 80, 0);
+        assert_eq!(main_width(&manager), 50);
+        drag(&mut manager, 2, 80, 0);
+        assert_eq!(main_width(&manager), 40);
+    }
+
+    #[test]
+    fn resizing_tiled_windows_with_the_main_area_on_the_right() {
+        let mut manager = manager_with_windows(Layout::RightWiderLeftStack, 60);
+        let stack_width = manager.state.windows[1].normal.w();
+
+        drag(&mut manager, 2, stack_width / 4, 0);
+        assert_eq!(main_width(&manager), 50);
+        drag(&mut manager, 1, 80, 0);
+        assert_eq!(main_width(&manager), 50);
+    }
+
+    #[test]
+    fn resizing_tiled_windows_leaves_layouts_without_main_width_alone() {
+        let mut manager = manager_with_windows(Layout::Bsp, 50);
+
+        drag(&mut manager, 1, 80, 0);
+        assert_eq!(main_width(&manager), 50);
+    }
+}
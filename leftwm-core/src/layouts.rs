@@ -1,13 +1,18 @@
 use super::models::Window;
 use super::models::Workspace;
+use crate::models::TabBar;
 use crate::models::Tag;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
+use std::sync::{Arc, PoisonError, RwLock};
 use thiserror::Error;
 
+mod bsp;
 mod center_main;
 mod center_main_balanced;
 mod center_main_fluid;
+mod custom_layout;
+mod dd;
 mod even_horizontal;
 mod even_vertical;
 mod fibonacci;
@@ -18,9 +23,160 @@ mod main_and_horizontal_stack;
 mod main_and_vert_stack;
 mod monocle;
 mod right_main_and_vert_stack;
-mod dd;
+mod scrolling;
+mod tabbed;
+
+pub(crate) use bsp::area as bsp_area;
+pub use custom_layout::{CustomLayout, MainPosition, Overflow};
+
+/// An arrangement of the tiled windows of a tag.
+///
+/// Every built-in layout implements this trait. Custom arrangements can be added by implementing
+/// it and handing the definition to [`register`], after which they can be referred to by name
+/// through `Layout::Custom`.
+pub trait LayoutDefinition: Send + Sync {
+    /// The name used to refer to the layout in the config and from commands.
+    fn name(&self) -> &str;
+
+    /// Position the given windows inside the workspace.
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag);
+
+    /// The main width percentage a tag starts with when switching to this layout.
+    fn main_width(&self) -> u8 {
+        50
+    }
+
+    /// The possible permutations that a layout can be flipped => (horizontally, vertically)
+    fn rotations(&self) -> Vec<(bool, bool)> {
+        [(false, false), (true, false)].to_vec()
+    }
+}
+
+/// A registered custom layout, and whether it comes from the `custom_layouts` of the config.
+struct Registered {
+    definition: Arc<dyn LayoutDefinition>,
+    configured: bool,
+}
+
+static REGISTRY: RwLock<Vec<Registered>> = RwLock::new(Vec::new());
+
+/// Register a custom layout, replacing any previously registered layout with the same name.
+///
+/// Built-in layouts take precedence over custom layouts with the same name.
+pub fn register(definition: impl LayoutDefinition + 'static) {
+    let mut registry = REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+    push(&mut registry, Arc::new(definition), false);
+}
+
+/// Register the custom layouts of the config, dropping the ones registered from a previous
+/// config. Layouts registered by [`register`] are kept.
+pub(crate) fn register_configured(layouts: Vec<CustomLayout>) {
+    let mut registry = REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+    registry.retain(|registered| !registered.configured);
+    for layout in layouts {
+        push(&mut registry, Arc::new(layout), true);
+    }
+}
+
+fn push(registry: &mut Vec<Registered>, definition: Arc<dyn LayoutDefinition>, configured: bool) {
+    registry.retain(|registered| registered.definition.name() != definition.name());
+    registry.push(Registered {
+        definition,
+        configured,
+    });
+}
+
+/// Whether a custom layout with this name has been registered.
+pub fn is_registered(name: &str) -> bool {
+    registered(name).is_some()
+}
+
+fn registered(name: &str) -> Option<Arc<dyn LayoutDefinition>> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .find(|registered| registered.definition.name() == name)
+        .map(|registered| registered.definition.clone())
+}
+
+/// How the main area is divided when it holds more than one window.
+#[derive(Clone, Copy)]
+enum MainSplit {
+    Rows,
+    Columns,
+}
+
+/// Arranges the windows with `update`, treating the first `tag.main_count` windows as if they
+/// were a single main window. The area given to that window is then divided among all of them.
+fn with_main_count(
+    windows: &mut [&mut Window],
+    tag: &Tag,
+    direction: MainSplit,
+    update: impl FnOnce(&mut [&mut Window]),
+) {
+    let main_count = tag.main_count.clamp(1, windows.len().max(1));
+    update(&mut windows[main_count - 1..]);
+
+    let Some(last) = windows.get(main_count - 1) else {
+        return;
+    };
+    let area = last.normal;
+    let visible = last.visible();
+    let mains = &mut windows[..main_count];
+    let sizes = match direction {
+        MainSplit::Rows => weighted_sizes(area.h(), mains),
+        MainSplit::Columns => weighted_sizes(area.w(), mains),
+    };
+    let mut offset = 0;
+    for (window, size) in mains.iter_mut().zip(sizes) {
+        let (x, y, width, height) = match direction {
+            MainSplit::Rows => (area.x(), area.y() + offset, area.w(), size),
+            MainSplit::Columns => (area.x() + offset, area.y(), size, area.h()),
+        };
+        window.set_x(x);
+        window.set_y(y);
+        window.set_width(width);
+        window.set_height(height);
+        window.set_visible(visible);
+        offset += size;
+    }
+}
+
+/// Splits `total` among the windows in proportion to their size weights. The last window takes
+/// up what is left over after rounding.
+fn weighted_sizes(total: i32, windows: &[&mut Window]) -> Vec<i32> {
+    let weights: Vec<f32> = windows.iter().map(|w| w.size_weight).collect();
+    weighted_shares(total, &weights)
+}
 
-#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+/// Splits `total` in proportion to the weights. The last share takes up what is left over after
+/// rounding.
+fn weighted_shares(total: i32, weights: &[f32]) -> Vec<i32> {
+    let sum: f32 = weights.iter().sum();
+    let mut remaining = total;
+    weights
+        .iter()
+        .enumerate()
+        .map(|(i, weight)| {
+            if i + 1 == weights.len() {
+                return remaining;
+            }
+            let size = (total as f32 * weight / sum).floor() as i32;
+            remaining -= size;
+            size
+        })
+        .collect()
+}
+
+/// The part of `total` a window gets when it shares it with the next window, in proportion to
+/// their size weights. Halves `total` for windows of the same weight.
+fn weighted_split(total: i32, window: &Window, next: &Window) -> i32 {
+    let share = window.size_weight / (window.size_weight + next.size_weight);
+    (total as f32 * share).floor() as i32
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub enum Layout {
     MainAndVertStack,
     MainAndHorizontalStack,
@@ -37,6 +193,12 @@ pub enum Layout {
     RightWiderLeftStack,
     LeftWiderRightStack,
     DD,
+    Bsp,
+    Scrolling,
+    Tabbed,
+    Stacked,
+    /// A layout added through [`register`], referred to by its name.
+    Custom(String),
 }
 
 pub const LAYOUTS: &[Layout] = &[
@@ -55,6 +217,10 @@ pub const LAYOUTS: &[Layout] = &[
     Layout::RightWiderLeftStack,
     Layout::LeftWiderRightStack,
     Layout::DD,
+    Layout::Bsp,
+    Layout::Scrolling,
+    Layout::Tabbed,
+    Layout::Stacked,
 ];
 
 impl Default for Layout {
@@ -63,51 +229,98 @@ impl Default for Layout {
     }
 }
 
-// This is tedious, but simple and effective.
 impl Layout {
-    pub fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+    /// The definition backing this layout. A custom layout which is not (or no longer)
+    /// registered falls back to the default layout.
+    pub fn definition(&self) -> Arc<dyn LayoutDefinition> {
         match self {
-            Self::MainAndVertStack | Self::LeftWiderRightStack => {
-                main_and_vert_stack::update(workspace, tag, windows);
-            }
+            Self::MainAndVertStack => Arc::new(main_and_vert_stack::MainAndVertStack),
             Self::MainAndHorizontalStack => {
-                main_and_horizontal_stack::update(workspace, tag, windows);
-            }
-            Self::MainAndDeck => main_and_deck::update(workspace, tag, windows),
-            Self::GridHorizontal => grid_horizontal::update(workspace, tag, windows),
-            Self::EvenHorizontal => even_horizontal::update(workspace, windows),
-            Self::EvenVertical => even_vertical::update(workspace, windows),
-            Self::Fibonacci => fibonacci::update(workspace, tag, windows),
-            Self::LeftMain => left_main::update(workspace, tag, windows),
-            Self::CenterMain => center_main::update(workspace, tag, windows),
-            Self::CenterMainBalanced => center_main_balanced::update(workspace, tag, windows),
-            Self::CenterMainFluid => center_main_fluid::update(workspace, tag, windows),
-            Self::Monocle => monocle::update(workspace, windows),
-            Self::RightWiderLeftStack => {
-                right_main_and_vert_stack::update(workspace, tag, windows);
+                Arc::new(main_and_horizontal_stack::MainAndHorizontalStack)
             }
-            Self::DD => dd::update(workspace, tag, windows),
+            Self::MainAndDeck => Arc::new(main_and_deck::MainAndDeck),
+            Self::GridHorizontal => Arc::new(grid_horizontal::GridHorizontal),
+            Self::EvenHorizontal => Arc::new(even_horizontal::EvenHorizontal),
+            Self::EvenVertical => Arc::new(even_vertical::EvenVertical),
+            Self::Fibonacci => Arc::new(fibonacci::Fibonacci),
+            Self::LeftMain => Arc::new(left_main::LeftMain),
+            Self::CenterMain => Arc::new(center_main::CenterMain),
+            Self::CenterMainBalanced => Arc::new(center_main_balanced::CenterMainBalanced),
+            Self::CenterMainFluid => Arc::new(center_main_fluid::CenterMainFluid),
+            Self::Monocle => Arc::new(monocle::Monocle),
+            Self::RightWiderLeftStack => Arc::new(right_main_and_vert_stack::RightWiderLeftStack),
+            Self::LeftWiderRightStack => Arc::new(main_and_vert_stack::LeftWiderRightStack),
+            Self::DD => Arc::new(dd::DD),
+            Self::Bsp => Arc::new(bsp::Bsp),
+            Self::Scrolling => Arc::new(scrolling::Scrolling),
+            Self::Tabbed => Arc::new(tabbed::Tabbed),
+            Self::Stacked => Arc::new(tabbed::Stacked),
+            Self::Custom(name) => registered(name).unwrap_or_else(|| Self::default().definition()),
         }
     }
 
-    pub const fn main_width(&self) -> u8 {
-        match self {
-            Self::RightWiderLeftStack | Self::LeftWiderRightStack => 75,
-            _ => 50,
-        }
+    pub fn name(&self) -> String {
+        self.definition().name().to_owned()
+    }
+
+    pub fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        self.definition().update_windows(workspace, windows, tag);
+    }
+
+    pub fn main_width(&self) -> u8 {
+        self.definition().main_width()
     }
 
-    //The possible permutations that a layout can be flipped => (flipable_horz, flipable_vert)
     pub fn rotations(&self) -> Vec<(bool, bool)> {
+        self.definition().rotations()
+    }
+
+    /// Whether the layout divides the space of an area among its windows by their size weights.
+    /// Custom layouts defined in the config do, the other layouts size windows on their own.
+    pub fn uses_size_weights(&self) -> bool {
+        matches!(
+            self,
+            Self::MainAndVertStack
+                | Self::MainAndHorizontalStack
+                | Self::GridHorizontal
+                | Self::EvenHorizontal
+                | Self::EvenVertical
+                | Self::Fibonacci
+                | Self::LeftMain
+                | Self::CenterMain
+                | Self::CenterMainBalanced
+                | Self::CenterMainFluid
+                | Self::RightWiderLeftStack
+                | Self::LeftWiderRightStack
+                | Self::DD
+                | Self::Custom(_)
+        )
+    }
+
+    /// Whether the layout puts the main area next to a single stack, splitting the workspace
+    /// between them by the main width.
+    pub fn has_main_and_stack(&self) -> bool {
+        matches!(
+            self,
+            Self::MainAndVertStack
+                | Self::MainAndHorizontalStack
+                | Self::MainAndDeck
+                | Self::RightWiderLeftStack
+                | Self::LeftWiderRightStack
+        )
+    }
+
+    /// Whether the layout only shows the active window, along with a tab bar.
+    pub fn is_tabbed(&self) -> bool {
+        matches!(self, Self::Tabbed | Self::Stacked)
+    }
+
+    /// The tab bar of a tag using this layout, or `None` if the layout doesn't have one.
+    pub fn tab_bar(&self, workspace: &Workspace, tag: &Tag, windows: &[&Window]) -> Option<TabBar> {
         match self {
-            //Layouts that can be flipped both ways
-            Self::Fibonacci | Self::GridHorizontal => {
-                [(false, false), (true, false), (true, true), (false, true)].to_vec()
-            }
-            //Layouts that can be flipped vertically
-            Self::MainAndHorizontalStack => [(false, false), (false, true)].to_vec(),
-            //Layouts that can be flipped horizontally
-            _ => [(false, false), (true, false)].to_vec(),
+            Self::Tabbed => Some(tabbed::tab_bar(workspace, tag, windows, false)),
+            Self::Stacked => Some(tabbed::tab_bar(workspace, tag, windows, true)),
+            _ => None,
         }
     }
 }
@@ -136,6 +349,11 @@ impl FromStr for Layout {
             "RightWiderLeftStack" => Ok(Self::RightWiderLeftStack),
             "LeftWiderRightStack" => Ok(Self::LeftWiderRightStack),
             "DD" => Ok(Self::DD),
+            "Bsp" => Ok(Self::Bsp),
+            "Scrolling" => Ok(Self::Scrolling),
+            "Tabbed" => Ok(Self::Tabbed),
+            "Stacked" => Ok(Self::Stacked),
+            _ if is_registered(s) => Ok(Self::Custom(s.to_string())),
             _ => Err(ParseLayoutError(s.to_string())),
         }
     }
@@ -179,7 +397,7 @@ mod tests {
 
     #[test]
     fn test_from_str() {
-        let layout_strs: [&str; 14] = [
+        let layout_strs: [&str; 19] = [
             "MainAndVertStack",
             "MainAndHorizontalStack",
             "MainAndDeck",
@@ -195,6 +413,10 @@ mod tests {
             "RightWiderLeftStack",
             "LeftWiderRightStack",
             "DD",
+            "Bsp",
+            "Scrolling",
+            "Tabbed",
+            "Stacked",
         ];
 
         assert_eq!(layout_strs.len(), LAYOUTS.len());
@@ -206,4 +428,170 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn main_count_splits_the_main_area() {
+        let mut ws = Workspace::new(
+            BBox {
+                width: 1000,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            Layout::default(),
+            None,
+            String::from("TEST"),
+            0,
+        );
+        ws.margin = Margins::new(0);
+        ws.update_avoided_areas();
+        let mut tag = Tag::new(1, "test", Layout::MainAndVertStack);
+        tag.main_count = 2;
+        let mut windows: Vec<Window> = (1..=3)
+            .map(|i| {
+                let mut w = Window::new(WindowHandle::MockHandle(i), None, None);
+                w.border = 0;
+                w.margin = Margins::new(0);
+                w
+            })
+            .collect();
+        let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+        tag.layout.update_windows(&ws, &mut refs, &tag);
+
+        let geometry = |w: &Window| (w.x(), w.y(), w.width(), w.height());
+        assert_eq!(geometry(&windows[0]), (0, 0, 500, 300));
+        assert_eq!(geometry(&windows[1]), (0, 300, 500, 300));
+        assert_eq!(geometry(&windows[2]), (500, 0, 500, 600));
+    }
+
+    #[test]
+    fn stack_respects_size_weights() {
+        let mut ws = Workspace::new(
+            BBox {
+                width: 1000,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            Layout::default(),
+            None,
+            String::from("TEST"),
+            0,
+        );
+        ws.margin = Margins::new(0);
+        ws.update_avoided_areas();
+        let mut windows: Vec<Window> = (1..=3)
+            .map(|i| {
+                let mut w = Window::new(WindowHandle::MockHandle(i), None, None);
+                w.border = 0;
+                w.margin = Margins::new(0);
+                w
+            })
+            .collect();
+        windows[1].size_weight = 2.0;
+        let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+        even_vertical::update(&ws, &mut refs);
+
+        let geometry = |w: &Window| (w.y(), w.height());
+        assert_eq!(geometry(&windows[0]), (0, 150));
+        assert_eq!(geometry(&windows[1]), (150, 300));
+        assert_eq!(geometry(&windows[2]), (450, 150));
+    }
+
+    #[test]
+    fn grids_and_spirals_respect_size_weights() {
+        let mut ws = Workspace::new(
+            BBox {
+                width: 1000,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            Layout::default(),
+            None,
+            String::from("TEST"),
+            0,
+        );
+        ws.margin = Margins::new(0);
+        ws.update_avoided_areas();
+        let mut windows: Vec<Window> = (1..=4)
+            .map(|i| {
+                let mut w = Window::new(WindowHandle::MockHandle(i), None, None);
+                w.border = 0;
+                w.margin = Margins::new(0);
+                w
+            })
+            .collect();
+        windows[1].size_weight = 3.0;
+        let geometry = |w: &Window| (w.x(), w.y(), w.width(), w.height());
+
+        let tag = Tag::new(1, "test", Layout::GridHorizontal);
+        let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+        tag.layout.update_windows(&ws, &mut refs, &tag);
+        assert_eq!(geometry(&windows[0]), (0, 0, 500, 150));
+        assert_eq!(geometry(&windows[1]), (0, 150, 500, 450));
+        assert_eq!(geometry(&windows[2]), (500, 0, 500, 300));
+
+        let tag = Tag::new(1, "test", Layout::Fibonacci);
+        let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+        tag.layout.update_windows(&ws, &mut refs, &tag);
+        assert_eq!(geometry(&windows[0]), (0, 0, 250, 600));
+        assert_eq!(geometry(&windows[1]), (250, 0, 750, 450));
+        assert_eq!(geometry(&windows[2]), (250, 450, 375, 150));
+        assert_eq!(geometry(&windows[3]), (625, 450, 375, 150));
+    }
+
+    struct Stripes;
+
+    impl LayoutDefinition for Stripes {
+        fn name(&self) -> &'static str {
+            "Stripes"
+        }
+
+        fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], _: &Tag) {
+            for (i, w) in windows.iter_mut().enumerate() {
+                w.set_x(workspace.x() + i as i32);
+            }
+        }
+
+        fn main_width(&self) -> u8 {
+            60
+        }
+    }
+
+    #[test]
+    fn registered_layouts_can_be_named() {
+        assert!(Layout::from_str("Stripes").is_err());
+        register(Stripes);
+
+        let layout = Layout::from_str("Stripes").expect("Layout String");
+        assert_eq!(layout, Layout::Custom("Stripes".to_string()));
+        assert_eq!(layout.name(), "Stripes");
+        assert_eq!(layout.main_width(), 60);
+        assert_eq!(layout.rotations(), Layout::default().rotations());
+    }
+
+    #[test]
+    fn unregistered_custom_layouts_fall_back_to_the_default() {
+        let layout = Layout::Custom("NotRegistered".to_string());
+        assert_eq!(layout.name(), Layout::default().name());
+    }
+
+    #[test]
+    fn reloading_the_config_drops_the_custom_layouts_it_no_longer_defines() {
+        let layout = |name: &str| CustomLayout {
+            name: name.to_string(),
+            main: MainPosition::Left,
+            main_width: 50,
+            max_rows: None,
+            overflow: Overflow::Deck,
+        };
+        register(layout("Kept"));
+        register_configured(vec![layout("Removed")]);
+        register_configured(vec![]);
+
+        assert!(!is_registered("Removed"));
+        assert!(is_registered("Kept"));
+        assert!(Layout::from_str("Removed").is_err());
+    }
 }
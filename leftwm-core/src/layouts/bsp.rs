@@ -0,0 +1,80 @@
+use crate::layouts::LayoutDefinition;
+use crate::models::Tag;
+use crate::models::Window;
+use crate::models::Workspace;
+use crate::models::{Xyhw, XyhwBuilder};
+
+/// Layout which places the windows according to the split tree of the tag. Each new window
+/// splits the focused one, see `SplitTree`.
+///
+/// 1 window
+/// ```text
+/// +-----------------------+
+/// |                       |
+/// |           1           |
+/// |                       |
+/// +-----------------------+
+/// ```
+/// 3 windows, focus on 2 when opening 3
+/// ```text
+/// +-----------+-----------+
+/// |           |     2     |
+/// |     1     +-----------+
+/// |           |     3     |
+/// +-----------+-----------+
+/// ```
+pub fn update(workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+    let area = area(workspace);
+    // The tree is brought up to date before every redraw, this only covers windows that
+    // haven't been seen yet.
+    let mut tree = tag.split_tree.clone();
+    let handles: Vec<_> = windows.iter().map(|w| w.handle).collect();
+    tree.sync(&handles, None, area);
+
+    for (handle, xyhw) in tree.geometry(area) {
+        let Some(window) = windows.iter_mut().find(|w| w.handle == handle) else {
+            continue;
+        };
+        let mut x = xyhw.x();
+        let mut y = xyhw.y();
+        if tag.flipped_horizontal {
+            x = area.x() + area.w() - (x - area.x()) - xyhw.w();
+        }
+        if tag.flipped_vertical {
+            y = area.y() + area.h() - (y - area.y()) - xyhw.h();
+        }
+        window.set_x(x);
+        window.set_y(y);
+        window.set_width(xyhw.w());
+        window.set_height(xyhw.h());
+        window.set_visible(true);
+    }
+}
+
+/// The area of the workspace the split tree is laid out in.
+pub fn area(workspace: &Workspace) -> Xyhw {
+    XyhwBuilder {
+        x: workspace.x_limited(1),
+        y: workspace.y(),
+        h: workspace.height(),
+        w: workspace.width_limited(1),
+        ..XyhwBuilder::default()
+    }
+    .into()
+}
+
+pub struct Bsp;
+
+impl LayoutDefinition for Bsp {
+    fn name(&self) -> &'static str {
+        "Bsp"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        update(workspace, windows, tag);
+    }
+
+    fn rotations(&self) -> Vec<(bool, bool)> {
+        [(false, false), (true, false), (true, true), (false, true)].to_vec()
+    }
+}
@@ -1,3 +1,4 @@
+use crate::layouts::{weighted_sizes, with_main_count, LayoutDefinition, MainSplit};
 use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
@@ -133,11 +134,11 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
 
     // stack all the others
     if window_count > 2 {
-        let height_f = workspace.height() as f32 / (window_count - 2) as f32;
-        let height = height_f.floor() as i32;
+        let stack = iter.into_slice();
+        let heights = weighted_sizes(workspace.height(), stack);
         let mut y = 0;
 
-        for w in iter {
+        for (w, height) in stack.iter_mut().zip(heights) {
             w.set_height(height);
             w.set_width(secondary_width);
             w.set_x(stack_x);
@@ -146,3 +147,17 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
         }
     }
 }
+
+pub struct CenterMain;
+
+impl LayoutDefinition for CenterMain {
+    fn name(&self) -> &'static str {
+        "CenterMain"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        with_main_count(windows, tag, MainSplit::Rows, |windows| {
+            update(workspace, tag, windows);
+        });
+    }
+}
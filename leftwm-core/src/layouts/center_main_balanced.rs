@@ -1,10 +1,12 @@
+use crate::layouts::{weighted_split, with_main_count, LayoutDefinition, MainSplit};
 use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
 
 /// Layout which splits the workspace into three columns.
 /// Gives first window all of the center column.
-/// Divides the left and right columns among all other windows in a fibonacci layout.
+/// Divides the left and right columns among all other windows in a fibonacci layout, each split
+/// shared between two windows by their size weights.
 ///
 /// Meant for ultra-wide monitors.
 ///
@@ -254,46 +256,53 @@ fn update_fibonacci(
     let mut y = workspace_y;
     let mut height = workspace_height;
     let mut width = workspace_width;
-    let window_count = windows.len() as i32;
+    let window_count = windows.len();
 
     for i in 0..window_count {
         if i % 2 != 0 {
             continue;
         }
 
-        let half_width = (width as f32 / 2.0).floor() as i32;
-        let half_height = (height as f32 / 2.0).floor() as i32;
+        // Each window shares its part of the column with the next one by their weights.
+        let top_height = match windows.get(i + 1) {
+            Some(next) => weighted_split(height, windows[i], next),
+            None => height,
+        };
+        let left_width = match windows.get(i + 2) {
+            Some(next) => weighted_split(width, windows[i + 1], next),
+            None => width,
+        };
 
         match window_count - 1 - i {
             0 => {
-                setter(windows[i as usize], height, width, x, y);
+                setter(windows[i], height, width, x, y);
             }
             1 => {
-                setter(windows[i as usize], half_height, width, x, y);
+                setter(windows[i], top_height, width, x, y);
 
                 setter(
-                    windows[(i + 1) as usize],
-                    half_height,
+                    windows[i + 1],
+                    height - top_height,
                     width,
                     x,
-                    y + half_height,
+                    y + top_height,
                 );
             }
             _ => {
-                setter(windows[i as usize], half_height, width, x, y);
+                setter(windows[i], top_height, width, x, y);
 
                 setter(
-                    windows[(i + 1) as usize],
-                    half_height,
-                    half_width,
+                    windows[i + 1],
+                    height - top_height,
+                    left_width,
                     x,
-                    y + half_height,
+                    y + top_height,
                 );
 
-                x += half_width;
-                y += half_height;
-                width = half_width;
-                height = half_height;
+                x += left_width;
+                y += top_height;
+                width -= left_width;
+                height -= top_height;
             }
         }
     }
@@ -305,3 +314,17 @@ fn setter(window: &mut Window, height: i32, width: i32, x: i32, y: i32) {
     window.set_x(x);
     window.set_y(y);
 }
+
+pub struct CenterMainBalanced;
+
+impl LayoutDefinition for CenterMainBalanced {
+    fn name(&self) -> &'static str {
+        "CenterMainBalanced"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        with_main_count(windows, tag, MainSplit::Rows, |windows| {
+            update(workspace, tag, windows);
+        });
+    }
+}
@@ -0,0 +1,295 @@
+use crate::layouts::{weighted_sizes, with_main_count, LayoutDefinition, MainSplit, LAYOUTS};
+use crate::models::Tag;
+use crate::models::Window;
+use crate::models::Workspace;
+use serde::{Deserialize, Serialize};
+
+/// Side of the workspace the main column is placed on.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MainPosition {
+    #[default]
+    Left,
+    Right,
+    /// No main column, all windows go into the stack.
+    None,
+}
+
+/// What to do with stack windows that don't fit into a column of `max_rows`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Open another stack column.
+    #[default]
+    Columns,
+    /// Stack the remaining windows on top of each other in the last row, only the first of them
+    /// being visible.
+    Deck,
+}
+
+/// A layout described in the config instead of code.
+///
+/// The main windows go into the main column, the rest is split into rows which are spread
+/// over one or more stack columns.
+///
+/// `main: Left, main_width: 60, max_rows: Some(3), overflow: Deck` with 6 windows
+/// ```text
+/// +--------------+-------+
+/// |              |   2   |
+/// |              +-------+
+/// |      1       |   3   |
+/// |              +-------+
+/// |              | 4,5,6 |
+/// +--------------+-------+
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CustomLayout {
+    pub name: String,
+    #[serde(default)]
+    pub main: MainPosition,
+    #[serde(default = "default_main_width")]
+    pub main_width: u8,
+    #[serde(default)]
+    pub max_rows: Option<usize>,
+    #[serde(default)]
+    pub overflow: Overflow,
+}
+
+const fn default_main_width() -> u8 {
+    50
+}
+
+impl CustomLayout {
+    /// Returns a description of everything that is wrong with this definition.
+    pub fn validate(&self) -> Vec<String> {
+        let mut errors = vec![];
+        if self.name.trim().is_empty() {
+            errors.push("Custom layout name cannot be empty".to_string());
+        }
+        if LAYOUTS.iter().any(|layout| layout.name() == self.name) {
+            errors.push(format!(
+                "Custom layout `{}` has the same name as a built-in layout",
+                self.name
+            ));
+        }
+        if self.main_width == 0 || self.main_width > 100 {
+            errors.push(format!(
+                "Custom layout `{}` has a main_width of {}, it must be between 1 and 100",
+                self.name, self.main_width
+            ));
+        }
+        if self.max_rows == Some(0) {
+            errors.push(format!(
+                "Custom layout `{}` has a max_rows of 0, it must be at least 1",
+                self.name
+            ));
+        }
+        errors
+    }
+
+    fn arrange(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        if windows.is_empty() {
+            return;
+        }
+
+        let (main, stack) = if self.main == MainPosition::None {
+            (None, windows)
+        } else {
+            let (main, stack) = windows.split_at_mut(1);
+            (main.first_mut(), stack)
+        };
+
+        let max_rows = self.max_rows.unwrap_or(stack.len()).max(1);
+        let stack_columns = match self.overflow {
+            _ if stack.is_empty() => 0,
+            Overflow::Columns => (stack.len() + max_rows - 1) / max_rows,
+            Overflow::Deck => 1,
+        };
+        let column_count = stack_columns + usize::from(main.is_some());
+        let workspace_width = workspace.width_limited(column_count);
+        let workspace_x = workspace.x_limited(column_count);
+
+        let main_width = match main {
+            Some(_) if stack.is_empty() => workspace_width,
+            Some(_) => {
+                (workspace_width as f32 / 100.0 * tag.main_width_percentage()).floor() as i32
+            }
+            None => 0,
+        };
+        let main_on_right = (self.main == MainPosition::Right) != tag.flipped_horizontal;
+        let (main_x, stack_x) = if main_on_right {
+            (workspace_x + workspace_width - main_width, workspace_x)
+        } else {
+            (workspace_x, workspace_x + main_width)
+        };
+
+        if let Some(main) = main {
+            main.set_height(workspace.height());
+            main.set_width(main_width);
+            main.set_x(main_x);
+            main.set_y(workspace.y());
+            main.set_visible(true);
+        }
+
+        let stack_width = workspace_width - main_width;
+        for (column, windows) in stack
+            .chunks_mut(if self.overflow == Overflow::Deck {
+                stack.len().max(1)
+            } else {
+                max_rows
+            })
+            .enumerate()
+        {
+            let (x, width) = split(stack_width, stack_columns, column);
+            let rows = windows.len().min(max_rows);
+            let heights = weighted_sizes(workspace.height(), &windows[..rows]);
+            for (index, window) in windows.iter_mut().enumerate() {
+                let row = index.min(rows - 1);
+                let height = heights[row];
+                let mut y: i32 = heights[..row].iter().sum();
+                if tag.flipped_vertical {
+                    y = workspace.height() - y - height;
+                }
+                window.set_height(height);
+                window.set_width(width);
+                window.set_x(stack_x + x);
+                window.set_y(workspace.y() + y);
+                window.set_visible(index <= row);
+            }
+        }
+    }
+}
+
+impl LayoutDefinition for CustomLayout {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        if self.main == MainPosition::None {
+            self.arrange(workspace, windows, tag);
+        } else {
+            with_main_count(windows, tag, MainSplit::Rows, |windows| {
+                self.arrange(workspace, windows, tag);
+            });
+        }
+    }
+
+    fn main_width(&self) -> u8 {
+        self.main_width
+    }
+
+    fn rotations(&self) -> Vec<(bool, bool)> {
+        [(false, false), (true, false), (true, true), (false, true)].to_vec()
+    }
+}
+
+/// Offset and size of the `index`th of `count` equal parts of `total`. The last part takes the
+/// remainder.
+fn split(total: i32, count: usize, index: usize) -> (i32, i32) {
+    let size = total / count.max(1) as i32;
+    let offset = size * index as i32;
+    if index + 1 == count {
+        (offset, total - offset)
+    } else {
+        (offset, size)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{BBox, Margins, WindowHandle};
+
+    fn workspace() -> Workspace {
+        let mut ws = Workspace::new(
+            BBox {
+                width: 1000,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            crate::layouts::Layout::default(),
+            None,
+            String::from("TEST"),
+            0,
+        );
+        ws.margin = Margins::new(0);
+        ws.update_avoided_areas();
+        ws
+    }
+
+    fn windows(count: i32) -> Vec<Window> {
+        (1..=count)
+            .map(|i| {
+                let mut w = Window::new(WindowHandle::MockHandle(i), None, None);
+                w.border = 0;
+                w.margin = Margins::new(0);
+                w
+            })
+            .collect()
+    }
+
+    fn layout(overflow: Overflow) -> CustomLayout {
+        CustomLayout {
+            name: "Test".to_string(),
+            main: MainPosition::Left,
+            main_width: 60,
+            max_rows: Some(3),
+            overflow,
+        }
+    }
+
+    fn geometry(window: &Window) -> (i32, i32, i32, i32, bool) {
+        (
+            window.x(),
+            window.y(),
+            window.width(),
+            window.height(),
+            window.visible(),
+        )
+    }
+
+    #[test]
+    fn overflow_into_a_deck() {
+        let layout = layout(Overflow::Deck);
+        let ws = workspace();
+        let mut tag = Tag::new(1, "test", crate::layouts::Layout::default());
+        tag.set_main_width(60);
+        let mut windows = windows(6);
+        let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+        layout.update_windows(&ws, &mut refs, &tag);
+
+        assert_eq!(geometry(&windows[0]), (0, 0, 600, 600, true));
+        assert_eq!(geometry(&windows[1]), (600, 0, 400, 200, true));
+        assert_eq!(geometry(&windows[2]), (600, 200, 400, 200, true));
+        assert_eq!(geometry(&windows[3]), (600, 400, 400, 200, true));
+        assert_eq!(geometry(&windows[4]), (600, 400, 400, 200, false));
+        assert_eq!(geometry(&windows[5]), (600, 400, 400, 200, false));
+    }
+
+    #[test]
+    fn overflow_into_columns() {
+        let layout = layout(Overflow::Columns);
+        let ws = workspace();
+        let mut tag = Tag::new(1, "test", crate::layouts::Layout::default());
+        tag.set_main_width(60);
+        let mut windows = windows(5);
+        let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+        layout.update_windows(&ws, &mut refs, &tag);
+
+        assert_eq!(geometry(&windows[0]), (0, 0, 600, 600, true));
+        assert_eq!(geometry(&windows[1]), (600, 0, 200, 200, true));
+        assert_eq!(geometry(&windows[3]), (600, 400, 200, 200, true));
+        assert_eq!(geometry(&windows[4]), (800, 0, 200, 600, true));
+    }
+
+    #[test]
+    fn validate_rejects_bad_definitions() {
+        let mut layout = layout(Overflow::Deck);
+        assert!(layout.validate().is_empty());
+
+        layout.name = "Monocle".to_string();
+        layout.main_width = 0;
+        layout.max_rows = Some(0);
+        assert_eq!(layout.validate().len(), 3);
+    }
+}
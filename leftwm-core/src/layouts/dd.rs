@@ -1,3 +1,4 @@
+use crate::layouts::{weighted_shares, LayoutDefinition};
 use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
@@ -53,20 +54,19 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
     // choose the number of columns so that we get close to an even NxN grid.
     let num_cols = (virtual_window_count as f32).sqrt().ceil() as i32;
 
-    let mut iter = windows.iter_mut().enumerate().peekable();
+    let mut start = 0;
     let mut remaining_virtual_windows = virtual_window_count;
     let mut remaining_chat_windows = chat_window_count;
-    let mut remaining_video_windows = video_window_count;
     for col in 0..num_cols {
-        let iter_peek = iter.peek().map(|x| x.0).unwrap_or_default() as i32;
         let remaining_columns = num_cols - col;
         let num_virtual_rows_in_this_col = remaining_virtual_windows / remaining_columns;
-        let num_chat_rows_in_this_col = std::cmp::min(remaining_chat_windows, num_virtual_rows_in_this_col / 2);
-        let num_video_rows_in_this_col = num_virtual_rows_in_this_col - num_chat_rows_in_this_col * 2;
+        let num_chat_rows_in_this_col =
+            std::cmp::min(remaining_chat_windows, num_virtual_rows_in_this_col / 2);
+        let num_video_rows_in_this_col =
+            num_virtual_rows_in_this_col - num_chat_rows_in_this_col * 2;
+        let num_rows_in_this_col =
+            (num_chat_rows_in_this_col + num_video_rows_in_this_col) as usize;
 
-        let virtual_win_height = workspace.height() / num_virtual_rows_in_this_col;
-        let chat_win_height = virtual_win_height * 2;
-        let video_win_height = virtual_win_height;
         let win_width = workspace.width_limited(num_cols as usize) / num_cols;
 
         let pos_x = if tag.flipped_horizontal {
@@ -75,44 +75,52 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
             col
         };
 
-        // set chat windows
-        for row in 0..num_chat_rows_in_this_col {
-            let Some((_idx, win)) = iter.next() else {
-                return
-            };
-            win.set_height(chat_win_height);
+        // The windows of a column share its height by their weights, chat windows counting as
+        // two video windows.
+        let end = (start + num_rows_in_this_col).min(windows.len());
+        let column = &mut windows[start..end];
+        let weights: Vec<f32> = column
+            .iter()
+            .enumerate()
+            .map(|(row, win)| {
+                let virtual_rows = if (row as i32) < num_chat_rows_in_this_col {
+                    2.0
+                } else {
+                    1.0
+                };
+                virtual_rows * win.size_weight
+            })
+            .collect();
+        let heights = weighted_shares(workspace.height(), &weights);
+        let mut offset = 0;
+        for (win, height) in column.iter_mut().zip(heights) {
+            win.set_height(height);
             win.set_width(win_width);
 
             let pos_y = if tag.flipped_vertical {
-                num_virtual_rows_in_this_col - row - 1
+                workspace.height() - offset - height
             } else {
-                row
+                offset
             };
 
             win.set_x(workspace.x_limited(num_cols as usize) + win_width * pos_x);
-            win.set_y(workspace.y() + chat_win_height * pos_y);
-            remaining_virtual_windows = remaining_virtual_windows - 2;
-            remaining_chat_windows = remaining_chat_windows - 1;
+            win.set_y(workspace.y() + pos_y);
+            offset += height;
         }
+        start = end;
+        remaining_virtual_windows -= num_virtual_rows_in_this_col;
+        remaining_chat_windows -= num_chat_rows_in_this_col;
+    }
+}
 
-        // set video windows
-        for row in 0..num_video_rows_in_this_col {
-            let Some((_idx, win)) = iter.next() else {
-                return
-            };
-            win.set_height(video_win_height);
-            win.set_width(win_width);
+pub struct DD;
 
-            let pos_y = if tag.flipped_vertical {
-                num_virtual_rows_in_this_col - num_chat_rows_in_this_col - row - 1
-            } else {
-                row + num_chat_rows_in_this_col * 2
-            };
+impl LayoutDefinition for DD {
+    fn name(&self) -> &'static str {
+        "DD"
+    }
 
-            win.set_x(workspace.x_limited(num_cols as usize) + win_width * pos_x);
-            win.set_y(workspace.y() + video_win_height * pos_y);
-            remaining_virtual_windows = remaining_virtual_windows - 1;
-            remaining_video_windows = remaining_video_windows - 1;
-        }
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        update(workspace, tag, windows);
     }
 }
@@ -1,13 +1,14 @@
+use crate::layouts::{weighted_sizes, LayoutDefinition};
+use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
 
 /// Layout which gives each window full height, but splits the workspace width among them all.
 pub fn update(workspace: &Workspace, windows: &mut [&mut Window]) {
     let window_count = windows.len();
-    let width_f = workspace.width_limited(window_count) as f32 / windows.len() as f32;
-    let width = width_f.floor() as i32;
+    let widths = weighted_sizes(workspace.width_limited(window_count), windows);
     let mut x = 0;
-    for w in windows.iter_mut() {
+    for (w, width) in windows.iter_mut().zip(widths) {
         w.set_height(workspace.height());
         w.set_width(width);
         w.set_x(workspace.x_limited(window_count) + x);
@@ -15,3 +16,15 @@ pub fn update(workspace: &Workspace, windows: &mut [&mut Window]) {
         x += width;
     }
 }
+
+pub struct EvenHorizontal;
+
+impl LayoutDefinition for EvenHorizontal {
+    fn name(&self) -> &'static str {
+        "EvenHorizontal"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], _tag: &Tag) {
+        update(workspace, windows);
+    }
+}
@@ -1,12 +1,13 @@
+use crate::layouts::{weighted_sizes, LayoutDefinition};
+use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
 
 /// Layout which gives each window full width, but splits the workspace height among them all.
 pub fn update(workspace: &Workspace, windows: &mut [&mut Window]) {
-    let height_f = workspace.height() as f32 / windows.len() as f32;
-    let height = height_f.floor() as i32;
+    let heights = weighted_sizes(workspace.height(), windows);
     let mut y = 0;
-    for w in windows.iter_mut() {
+    for (w, height) in windows.iter_mut().zip(heights) {
         w.set_height(height);
         w.set_width(workspace.width_limited(1));
         w.set_x(workspace.x_limited(1));
@@ -14,3 +15,15 @@ pub fn update(workspace: &Workspace, windows: &mut [&mut Window]) {
         y += height;
     }
 }
+
+pub struct EvenVertical;
+
+impl LayoutDefinition for EvenVertical {
+    fn name(&self) -> &'static str {
+        "EvenVertical"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], _tag: &Tag) {
+        update(workspace, windows);
+    }
+}
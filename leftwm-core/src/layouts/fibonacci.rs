@@ -1,8 +1,10 @@
+use crate::layouts::{weighted_split, LayoutDefinition};
 use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
 
 /// Fibonacci layout, which divides the workspace in subsequent halves and assignes them to the windows
+/// Each split is shared between two windows by their size weights.
 /// ```text
 /// +-----------+-----------+
 /// |           |           |
@@ -30,38 +32,46 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
             continue;
         }
 
-        let half_width = (width as f32 / 2.0).floor() as i32;
-        let half_height = (height as f32 / 2.0).floor() as i32;
+        // Each window shares its part of the workspace with the next one by their weights.
+        let main_width = match windows.get(i + 1) {
+            Some(next) => weighted_split(width, windows[i], next),
+            None => width,
+        };
+        let alt_width = width - main_width;
+        let alt_height = match windows.get(i + 2) {
+            Some(next) => weighted_split(height, windows[i + 1], next),
+            None => height,
+        };
         let (main_x, alt_x);
         if tag.flipped_horizontal {
-            main_x = x + half_width;
+            main_x = x + alt_width;
             alt_x = x;
         } else {
             main_x = x;
-            alt_x = x + half_width;
+            alt_x = x + main_width;
         }
         let (new_y, alt_y);
         if tag.flipped_vertical {
             new_y = y;
-            alt_y = y + half_height;
+            alt_y = y + height - alt_height;
         } else {
-            new_y = y + half_height;
+            new_y = y + alt_height;
             alt_y = y;
         }
         match window_count - i {
             1 => setter(windows[i], height, width, x, y),
             2 => {
-                setter(windows[i], height, half_width, main_x, y);
-                setter(windows[i + 1], height, half_width, alt_x, y);
+                setter(windows[i], height, main_width, main_x, y);
+                setter(windows[i + 1], height, alt_width, alt_x, y);
             }
             _ => {
-                setter(windows[i], height, half_width, main_x, y);
-                setter(windows[i + 1], half_height, half_width, alt_x, alt_y);
+                setter(windows[i], height, main_width, main_x, y);
+                setter(windows[i + 1], alt_height, alt_width, alt_x, alt_y);
 
                 x = alt_x;
                 y = new_y;
-                width = half_width;
-                height = half_height;
+                width = alt_width;
+                height -= alt_height;
             }
         }
     }
@@ -73,3 +83,19 @@ fn setter(window: &mut Window, height: i32, width: i32, x: i32, y: i32) {
     window.set_x(x);
     window.set_y(y);
 }
+
+pub struct Fibonacci;
+
+impl LayoutDefinition for Fibonacci {
+    fn name(&self) -> &'static str {
+        "Fibonacci"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        update(workspace, tag, windows);
+    }
+
+    fn rotations(&self) -> Vec<(bool, bool)> {
+        [(false, false), (true, false), (true, true), (false, true)].to_vec()
+    }
+}
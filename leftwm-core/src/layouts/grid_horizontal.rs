@@ -1,3 +1,4 @@
+use crate::layouts::{weighted_sizes, LayoutDefinition};
 use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
@@ -27,14 +28,12 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
     // choose the number of columns so that we get close to an even NxN grid.
     let num_cols = (window_count as f32).sqrt().ceil() as i32;
 
-    let mut iter = windows.iter_mut().enumerate().peekable();
+    let mut start = 0;
     for col in 0..num_cols {
-        let iter_peek = iter.peek().map(|x| x.0).unwrap_or_default() as i32;
-        let remaining_windows = window_count - iter_peek;
+        let remaining_windows = window_count - start as i32;
         let remaining_columns = num_cols - col;
         let num_rows_in_this_col = remaining_windows / remaining_columns;
 
-        let win_height = workspace.height() / num_rows_in_this_col;
         let win_width = workspace.width_limited(num_cols as usize) / num_cols;
 
         let pos_x = if tag.flipped_horizontal {
@@ -43,21 +42,40 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
             col
         };
 
-        for row in 0..num_rows_in_this_col {
-            let Some((_idx, win)) = iter.next() else {
-                return
-            };
-            win.set_height(win_height);
+        // The windows of a column share its height by their weights.
+        let column = &mut windows[start..start + num_rows_in_this_col as usize];
+        let heights = weighted_sizes(workspace.height(), column);
+        let mut offset = 0;
+        for (win, height) in column.iter_mut().zip(heights) {
+            win.set_height(height);
             win.set_width(win_width);
 
             let pos_y = if tag.flipped_vertical {
-                num_rows_in_this_col - row - 1
+                workspace.height() - offset - height
             } else {
-                row
+                offset
             };
 
             win.set_x(workspace.x_limited(num_cols as usize) + win_width * pos_x);
-            win.set_y(workspace.y() + win_height * pos_y);
+            win.set_y(workspace.y() + pos_y);
+            offset += height;
         }
+        start += num_rows_in_this_col as usize;
+    }
+}
+
+pub struct GridHorizontal;
+
+impl LayoutDefinition for GridHorizontal {
+    fn name(&self) -> &'static str {
+        "GridHorizontal"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        update(workspace, tag, windows);
+    }
+
+    fn rotations(&self) -> Vec<(bool, bool)> {
+        [(false, false), (true, false), (true, true), (false, true)].to_vec()
     }
 }
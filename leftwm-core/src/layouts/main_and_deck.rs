@@ -1,3 +1,4 @@
+use crate::layouts::{with_main_count, LayoutDefinition, MainSplit};
 use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
@@ -71,3 +72,17 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
         }
     }
 }
+
+pub struct MainAndDeck;
+
+impl LayoutDefinition for MainAndDeck {
+    fn name(&self) -> &'static str {
+        "MainAndDeck"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        with_main_count(windows, tag, MainSplit::Rows, |windows| {
+            update(workspace, tag, windows);
+        });
+    }
+}
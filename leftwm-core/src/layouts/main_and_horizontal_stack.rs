@@ -1,3 +1,4 @@
+use crate::layouts::{weighted_sizes, with_main_count, LayoutDefinition, MainSplit};
 use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
@@ -47,10 +48,10 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
     }
 
     //stack all the others
-    let width_f = workspace_width as f32 / (window_count - 1) as f32;
-    let width = width_f.floor() as i32;
+    let stack = iter.into_slice();
+    let widths = weighted_sizes(workspace_width, stack);
     let mut x = 0;
-    for w in iter {
+    for (w, width) in stack.iter_mut().zip(widths) {
         w.set_height(workspace.height() - height);
         w.set_width(width);
         w.set_x(workspace_x + x);
@@ -58,3 +59,21 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
         x += width;
     }
 }
+
+pub struct MainAndHorizontalStack;
+
+impl LayoutDefinition for MainAndHorizontalStack {
+    fn name(&self) -> &'static str {
+        "MainAndHorizontalStack"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        with_main_count(windows, tag, MainSplit::Columns, |windows| {
+            update(workspace, tag, windows);
+        });
+    }
+
+    fn rotations(&self) -> Vec<(bool, bool)> {
+        [(false, false), (false, true)].to_vec()
+    }
+}
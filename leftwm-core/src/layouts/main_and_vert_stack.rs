@@ -1,3 +1,4 @@
+use crate::layouts::{weighted_sizes, with_main_count, LayoutDefinition, MainSplit};
 use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
@@ -49,10 +50,10 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
     }
 
     //stack all the others
-    let height_f = workspace.height() as f32 / (window_count - 1) as f32;
-    let height = height_f.floor() as i32;
+    let stack = iter.into_slice();
+    let heights = weighted_sizes(workspace.height(), stack);
     let mut y = 0;
-    for w in iter {
+    for (w, height) in stack.iter_mut().zip(heights) {
         w.set_height(height);
         w.set_width(workspace_width - primary_width);
         w.set_x(stack_x);
@@ -60,3 +61,35 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
         y += height;
     }
 }
+
+pub struct MainAndVertStack;
+
+impl LayoutDefinition for MainAndVertStack {
+    fn name(&self) -> &'static str {
+        "MainAndVertStack"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        with_main_count(windows, tag, MainSplit::Rows, |windows| {
+            update(workspace, tag, windows);
+        });
+    }
+}
+
+pub struct LeftWiderRightStack;
+
+impl LayoutDefinition for LeftWiderRightStack {
+    fn name(&self) -> &'static str {
+        "LeftWiderRightStack"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        with_main_count(windows, tag, MainSplit::Rows, |windows| {
+            update(workspace, tag, windows);
+        });
+    }
+
+    fn main_width(&self) -> u8 {
+        75
+    }
+}
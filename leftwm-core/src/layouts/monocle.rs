@@ -1,3 +1,5 @@
+use crate::layouts::LayoutDefinition;
+use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
 // use crate::models::WindowState;
@@ -40,3 +42,15 @@ pub fn update(workspace: &Workspace, windows: &mut [&mut Window]) {
         }
     }
 }
+
+pub struct Monocle;
+
+impl LayoutDefinition for Monocle {
+    fn name(&self) -> &'static str {
+        "Monocle"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], _tag: &Tag) {
+        update(workspace, windows);
+    }
+}
@@ -1,3 +1,4 @@
+use crate::layouts::{weighted_sizes, with_main_count, LayoutDefinition, MainSplit};
 use crate::models::Tag;
 use crate::models::Window;
 use crate::models::Workspace;
@@ -96,11 +97,12 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
     }
 
     // build other windows
-    let height = (workspace.height() as f32 / (window_count - 1) as f32).floor() as i32;
+    let stack = iter.into_slice();
+    let heights = weighted_sizes(workspace.height(), stack);
 
     let mut y = 0;
 
-    for w in iter {
+    for (w, height) in stack.iter_mut().zip(heights) {
         w.set_height(height);
         w.set_width(third_part);
         w.set_x(stack_x);
@@ -108,3 +110,21 @@ pub fn update(workspace: &Workspace, tag: &Tag, windows: &mut [&mut Window]) {
         y += height;
     }
 }
+
+pub struct RightWiderLeftStack;
+
+impl LayoutDefinition for RightWiderLeftStack {
+    fn name(&self) -> &'static str {
+        "RightWiderLeftStack"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        with_main_count(windows, tag, MainSplit::Rows, |windows| {
+            update(workspace, tag, windows);
+        });
+    }
+
+    fn main_width(&self) -> u8 {
+        75
+    }
+}
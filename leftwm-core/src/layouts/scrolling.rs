@@ -0,0 +1,50 @@
+use crate::layouts::LayoutDefinition;
+use crate::models::Tag;
+use crate::models::Window;
+use crate::models::Workspace;
+
+/// Layout which puts every window in its own column on a strip that can be wider than the
+/// workspace. The workspace shows part of the strip, see `Viewport`, and columns which don't
+/// fit into it completely are hidden.
+///
+/// 4 windows of half the workspace width, scrolled to the second one
+/// ```text
+///         +-----------+-----------+
+///    1    |     2     |     3     |    4
+///         +-----------+-----------+
+/// ```
+pub fn update(workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+    let columns: Vec<_> = windows.iter().map(|w| (w.handle, w.column_width)).collect();
+    let offset = tag.viewport.clamped_offset(&columns) as i32;
+    let workspace_width = workspace.width_limited(1);
+    let workspace_x = workspace.x_limited(1);
+
+    let mut start = 0;
+    for window in windows.iter_mut() {
+        let width = i32::from(window.column_width);
+        let end = start + width;
+        let x = (start - offset) * workspace_width / 100;
+        window.set_height(workspace.height());
+        window.set_width((end - offset) * workspace_width / 100 - x);
+        window.set_x(workspace_x + x);
+        window.set_y(workspace.y());
+        window.set_visible(start >= offset && end <= offset + 100);
+        start = end;
+    }
+}
+
+pub struct Scrolling;
+
+impl LayoutDefinition for Scrolling {
+    fn name(&self) -> &'static str {
+        "Scrolling"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        update(workspace, windows, tag);
+    }
+
+    fn rotations(&self) -> Vec<(bool, bool)> {
+        [(false, false)].to_vec()
+    }
+}
@@ -0,0 +1,236 @@
+use crate::layouts::LayoutDefinition;
+use crate::models::Tag;
+use crate::models::Window;
+use crate::models::Workspace;
+use crate::models::{Tab, TabBar, WindowHandle, Xyhw, XyhwBuilder};
+
+/// Layout which shows one window at a time below a row of tabs, one for each window.
+///
+/// 3 windows, 2 being active
+/// ```text
+/// +-------+-------+-------+
+/// |   1   |  [2]  |   3   |
+/// +-------+-------+-------+
+/// |                       |
+/// |           2           |
+/// |                       |
+/// +-----------------------+
+/// ```
+pub struct Tabbed;
+
+/// Layout which shows one window at a time below a list of the titles of all windows.
+///
+/// 3 windows, 2 being active
+/// ```text
+/// +-----------------------+
+/// |           1           |
+/// +-----------------------+
+/// |          [2]          |
+/// +-----------------------+
+/// |           3           |
+/// +-----------------------+
+/// |           2           |
+/// +-----------------------+
+/// ```
+pub struct Stacked;
+
+/// Shows the active window of the tag below the tab bar and hides the others.
+fn update(workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag, stacked: bool) {
+    let bar_height = bar_height(workspace, windows.len(), stacked);
+    let active = active_index(windows.iter().map(|w| w.handle), tag);
+    for (index, window) in windows.iter_mut().enumerate() {
+        window.set_height(workspace.height() - bar_height);
+        window.set_width(workspace.width_limited(1));
+        window.set_x(workspace.x_limited(1));
+        window.set_y(workspace.y() + bar_height);
+        window.set_visible(index == active);
+    }
+}
+
+/// The tab bar for the tiled windows of a tag using one of the tab layouts.
+pub fn tab_bar(workspace: &Workspace, tag: &Tag, windows: &[&Window], stacked: bool) -> TabBar {
+    let width = workspace.width_limited(1);
+    let height = bar_height(workspace, windows.len(), stacked);
+    let tab_height = tab_height(workspace, windows.len(), stacked);
+    let count = windows.len().max(1) as i32;
+    let active = active_index(windows.iter().map(|w| w.handle), tag);
+    let tabs = windows
+        .iter()
+        .enumerate()
+        .map(|(index, window)| {
+            let i = index as i32;
+            let xyhw = if stacked {
+                xyhw(0, i * tab_height, width, tab_height)
+            } else {
+                // The last tab takes up what is left over after rounding.
+                let tab_width = width / count;
+                let w = if i + 1 == count {
+                    width - tab_width * i
+                } else {
+                    tab_width
+                };
+                xyhw(tab_width * i, 0, w, tab_height)
+            };
+            Tab {
+                window: window.handle,
+                title: window.name.clone().unwrap_or_default(),
+                active: index == active,
+                xyhw,
+            }
+        })
+        .collect();
+    TabBar {
+        xyhw: xyhw(workspace.x_limited(1), workspace.y(), width, height),
+        tabs,
+    }
+}
+
+fn bar_height(workspace: &Workspace, window_count: usize, stacked: bool) -> i32 {
+    if stacked {
+        tab_height(workspace, window_count, stacked) * window_count as i32
+    } else {
+        workspace.tab_bar_height
+    }
+}
+
+/// The height of a tab. Stacked tabs shrink so that the list never takes up more than half of
+/// the workspace.
+fn tab_height(workspace: &Workspace, window_count: usize, stacked: bool) -> i32 {
+    let count = window_count.max(1) as i32;
+    if stacked {
+        workspace.tab_bar_height.min(workspace.height() / 2 / count)
+    } else {
+        workspace.tab_bar_height
+    }
+}
+
+/// The index of the window shown by the layout, falling back to the first window.
+fn active_index(mut handles: impl Iterator<Item = WindowHandle>, tag: &Tag) -> usize {
+    handles
+        .position(|handle| Some(handle) == tag.active_tab)
+        .unwrap_or(0)
+}
+
+fn xyhw(x: i32, y: i32, w: i32, h: i32) -> Xyhw {
+    XyhwBuilder {
+        x,
+        y,
+        h,
+        w,
+        ..XyhwBuilder::default()
+    }
+    .into()
+}
+
+impl LayoutDefinition for Tabbed {
+    fn name(&self) -> &'static str {
+        "Tabbed"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        update(workspace, windows, tag, false);
+    }
+
+    fn rotations(&self) -> Vec<(bool, bool)> {
+        [(false, false)].to_vec()
+    }
+}
+
+impl LayoutDefinition for Stacked {
+    fn name(&self) -> &'static str {
+        "Stacked"
+    }
+
+    fn update_windows(&self, workspace: &Workspace, windows: &mut [&mut Window], tag: &Tag) {
+        update(workspace, windows, tag, true);
+    }
+
+    fn rotations(&self) -> Vec<(bool, bool)> {
+        [(false, false)].to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layouts::Layout;
+    use crate::models::BBox;
+
+    fn workspace() -> Workspace {
+        let mut ws = Workspace::new(
+            BBox {
+                width: 900,
+                height: 600,
+                x: 0,
+                y: 0,
+            },
+            Layout::Tabbed,
+            None,
+            String::from("TEST"),
+            0,
+        );
+        ws.margin = crate::models::Margins::new(0);
+        ws.tab_bar_height = 20;
+        ws.update_avoided_areas();
+        ws
+    }
+
+    fn windows() -> Vec<Window> {
+        (1..=3)
+            .map(|i| Window::new(WindowHandle::MockHandle(i), Some(format!("w{i}")), None))
+            .collect()
+    }
+
+    #[test]
+    fn only_the_active_window_is_shown() {
+        let ws = workspace();
+        let mut tag = Tag::new(1, "test", Layout::Tabbed);
+        tag.active_tab = Some(WindowHandle::MockHandle(2));
+        let mut windows = windows();
+        let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+        Tabbed.update_windows(&ws, &mut refs, &tag);
+
+        let visible: Vec<bool> = windows.iter().map(Window::visible).collect();
+        assert_eq!(visible, vec![false, true, false]);
+        assert_eq!(windows[1].normal.y(), 20);
+        assert_eq!(windows[1].normal.h(), 580);
+    }
+
+    #[test]
+    fn tabs_split_the_bar() {
+        let ws = workspace();
+        let tag = Tag::new(1, "test", Layout::Tabbed);
+        let windows = windows();
+        let refs: Vec<&Window> = windows.iter().collect();
+
+        let bar = tab_bar(&ws, &tag, &refs, false);
+        assert_eq!((bar.xyhw.w(), bar.xyhw.h()), (900, 20));
+        assert!(bar.tabs[0].active);
+        assert_eq!(bar.tabs[2].title, "w3");
+        assert_eq!(bar.tabs[2].xyhw.x(), 600);
+        assert_eq!(bar.window_at(450, 10), Some(WindowHandle::MockHandle(2)));
+
+        let bar = tab_bar(&ws, &tag, &refs, true);
+        assert_eq!(bar.xyhw.h(), 60);
+        assert_eq!(bar.window_at(450, 50), Some(WindowHandle::MockHandle(3)));
+    }
+
+    #[test]
+    fn stacked_tabs_shrink_to_leave_room_for_the_window() {
+        let ws = workspace();
+        let tag = Tag::new(1, "test", Layout::Stacked);
+        let mut windows: Vec<Window> = (1..=40)
+            .map(|i| Window::new(WindowHandle::MockHandle(i), None, None))
+            .collect();
+        let refs: Vec<&Window> = windows.iter().collect();
+
+        let bar = tab_bar(&ws, &tag, &refs, true);
+        assert_eq!(bar.xyhw.h(), 280);
+        assert_eq!(bar.tabs[39].xyhw.y(), 273);
+
+        let mut refs: Vec<&mut Window> = windows.iter_mut().collect();
+        Stacked.update_windows(&ws, &mut refs, &tag);
+        assert_eq!(windows[0].normal.y(), 280);
+        assert_eq!(windows[0].normal.h(), 320);
+    }
+}
@@ -1,4 +1,5 @@
 //! Objects (such as windows) used to develop `LeftWM`.
+mod direction;
 mod dock_area;
 mod focus_manager;
 mod gutter;
@@ -9,7 +10,11 @@ mod mode;
 mod scratchpad;
 mod screen;
 mod size;
+mod snap_position;
+mod split_tree;
+mod tab_bar;
 mod tag;
+mod viewport;
 mod window;
 mod window_change;
 mod window_state;
@@ -21,6 +26,7 @@ mod xyhw_change;
 pub mod dto;
 use crate::layouts;
 
+pub use direction::Direction;
 pub use dock_area::DockArea;
 pub use focus_manager::FocusBehaviour;
 pub use focus_manager::FocusManager;
@@ -34,6 +40,8 @@ pub use mode::Mode;
 pub use scratchpad::{ScratchPad, ScratchPadName};
 pub use screen::{BBox, Screen};
 pub use size::Size;
+pub use snap_position::SnapPosition;
+pub use split_tree::{SplitAxis, SplitTree};
 pub use window::Window;
 pub use window::WindowHandle;
 pub use window_change::WindowChange;
@@ -44,8 +52,10 @@ pub use xyhw::Xyhw;
 pub use xyhw::XyhwBuilder;
 pub use xyhw_change::XyhwChange;
 
+pub use tab_bar::{Tab, TabBar};
 pub use tag::Tag;
 pub use tag::Tags;
+pub use viewport::Viewport;
 
 pub type TagId = usize;
 type MaybeWindowHandle = Option<WindowHandle>;
@@ -0,0 +1,113 @@
+use super::Xyhw;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// A direction on the screen, relative to a window.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+#[derive(Debug, Error)]
+#[error("Could not parse direction: {0}")]
+pub struct ParseDirectionError(String);
+
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Left" => Ok(Self::Left),
+            "Right" => Ok(Self::Right),
+            "Up" => Ok(Self::Up),
+            "Down" => Ok(Self::Down),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+impl Direction {
+    /// The index of the area closest to `from` in this direction. Only areas whose center lies
+    /// beyond the edge of `from` count. Areas lined up with `from` are preferred over nearer ones
+    /// which are offset to the side.
+    #[must_use]
+    pub fn nearest(self, from: Xyhw, areas: &[Xyhw]) -> Option<usize> {
+        let (from_x, from_y) = from.center();
+        areas
+            .iter()
+            .enumerate()
+            .filter_map(|(index, area)| {
+                let (x, y) = area.center();
+                let (beyond, gap, side_gap, side_offset) = match self {
+                    Self::Left => (
+                        x <= from.x(),
+                        from.x() - (area.x() + area.w()),
+                        range_gap(from.y(), from.h(), area.y(), area.h()),
+                        (y - from_y).abs(),
+                    ),
+                    Self::Right => (
+                        x >= from.x() + from.w(),
+                        area.x() - (from.x() + from.w()),
+                        range_gap(from.y(), from.h(), area.y(), area.h()),
+                        (y - from_y).abs(),
+                    ),
+                    Self::Up => (
+                        y <= from.y(),
+                        from.y() - (area.y() + area.h()),
+                        range_gap(from.x(), from.w(), area.x(), area.w()),
+                        (x - from_x).abs(),
+                    ),
+                    Self::Down => (
+                        y >= from.y() + from.h(),
+                        area.y() - (from.y() + from.h()),
+                        range_gap(from.x(), from.w(), area.x(), area.w()),
+                        (x - from_x).abs(),
+                    ),
+                };
+                beyond.then_some((index, (side_gap, gap.max(0), side_offset)))
+            })
+            .min_by_key(|(_, distance)| *distance)
+            .map(|(index, _)| index)
+    }
+}
+
+/// The space between two ranges, 0 if they overlap.
+fn range_gap(start: i32, len: i32, other_start: i32, other_len: i32) -> i32 {
+    (start.max(other_start) - (start + len).min(other_start + other_len)).max(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::XyhwBuilder;
+
+    fn area(x: i32, y: i32, w: i32, h: i32) -> Xyhw {
+        XyhwBuilder {
+            x,
+            y,
+            h,
+            w,
+            ..XyhwBuilder::default()
+        }
+        .into()
+    }
+
+    #[test]
+    fn nearest_prefers_areas_lined_up_with_the_window() {
+        // A main window on the left of a stack of two.
+        let main = area(0, 0, 400, 600);
+        let top = area(400, 0, 400, 300);
+        let bottom = area(400, 300, 400, 300);
+        let areas = [main, top, bottom];
+
+        assert_eq!(Direction::Right.nearest(main, &areas), Some(1));
+        assert_eq!(Direction::Left.nearest(bottom, &areas), Some(0));
+        assert_eq!(Direction::Up.nearest(bottom, &areas), Some(1));
+        assert_eq!(Direction::Down.nearest(top, &areas), Some(2));
+        assert_eq!(Direction::Left.nearest(main, &areas), None);
+    }
+}
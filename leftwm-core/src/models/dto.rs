@@ -1,10 +1,14 @@
 use crate::layouts::Layout;
+use crate::models::Tag;
 use crate::state::State;
 use serde::{Deserialize, Serialize};
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Viewport {
     pub tag: String,
+    /// All tags shown on the workspace, starting with `tag`.
+    #[serde(default)]
+    pub tags: Vec<String>,
     pub h: u32,
     pub w: u32,
     pub x: i32,
@@ -17,10 +21,22 @@ pub struct Viewport {
 pub struct ManagerState {
     pub window_title: Option<String>,
     pub desktop_names: Vec<String>,
+    /// The output each desktop belongs to when every output has tags of its own.
+    #[serde(default)]
+    pub desktop_outputs: Vec<Option<String>>,
     pub viewports: Vec<Viewport>,
     pub active_desktop: Vec<String>,
     pub working_tags: Vec<String>,
     pub urgent_tags: Vec<String>,
+    #[serde(default)]
+    pub minimized_windows: Vec<MinimizedWindow>,
+}
+
+/// A minimized window, listed in the order of the restore stack of its tag.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MinimizedWindow {
+    pub tag: String,
+    pub title: String,
 }
 
 #[allow(clippy::struct_excessive_bools)]
@@ -33,6 +49,8 @@ pub struct TagsForWorkspace {
     pub focused: bool,
     pub urgent: bool,
     pub busy: bool,
+    /// Titles of the minimized windows of the tag, the most recently minimized one last.
+    pub minimized: Vec<String>,
 }
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct DisplayWorkspace {
@@ -53,22 +71,17 @@ pub struct DisplayState {
 
 impl From<ManagerState> for DisplayState {
     fn from(m: ManagerState) -> Self {
-        let visible: Vec<String> = m.viewports.iter().map(|vp| vp.tag.clone()).collect();
+        let visible: Vec<String> = m
+            .viewports
+            .iter()
+            .flat_map(|vp| std::iter::once(&vp.tag).chain(&vp.tags))
+            .cloned()
+            .collect();
         let workspaces = m
             .viewports
             .iter()
             .enumerate()
-            .map(|(i, vp)| {
-                viewport_into_display_workspace(
-                    &m.desktop_names,
-                    &m.active_desktop,
-                    &visible,
-                    &m.working_tags,
-                    &m.urgent_tags,
-                    vp,
-                    i,
-                )
-            })
+            .map(|(i, vp)| viewport_into_display_workspace(&m, &visible, vp, i))
             .collect();
         Self {
             workspaces,
@@ -78,25 +91,36 @@ impl From<ManagerState> for DisplayState {
 }
 
 fn viewport_into_display_workspace(
-    all_tags: &[String],
-    focused: &[String],
+    m: &ManagerState,
     visible: &[String],
-    working_tags: &[String],
-    urgent_tags: &[String],
     viewport: &Viewport,
     ws_index: usize,
 ) -> DisplayWorkspace {
-    let tags: Vec<TagsForWorkspace> = all_tags
+    // Workspaces only list the tags of their output, by their label.
+    let tags: Vec<TagsForWorkspace> = m
+        .desktop_names
         .iter()
         .enumerate()
-        .map(|(index, t)| TagsForWorkspace {
-            name: t.clone(),
+        .filter_map(|(index, t)| match m.desktop_outputs.get(index) {
+            Some(Some(output)) if output != &viewport.output => None,
+            Some(Some(output)) => Some((t, t.strip_prefix(&format!("{output}_")))),
+            _ => Some((t, None)),
+        })
+        .enumerate()
+        .map(|(index, (t, label))| TagsForWorkspace {
+            name: label.unwrap_or(t).to_string(),
             index,
-            mine: viewport.tag == *t,
+            mine: viewport.tag == *t || viewport.tags.contains(t),
             visible: visible.contains(t),
-            focused: focused.contains(t),
-            urgent: urgent_tags.contains(t),
-            busy: working_tags.contains(t),
+            focused: m.active_desktop.contains(t),
+            urgent: m.urgent_tags.contains(t),
+            busy: m.working_tags.contains(t),
+            minimized: m
+                .minimized_windows
+                .iter()
+                .filter(|w| &w.tag == t)
+                .map(|w| w.title.clone())
+                .collect(),
         })
         .collect();
     DisplayWorkspace {
@@ -105,7 +129,7 @@ fn viewport_into_display_workspace(
         w: viewport.w,
         x: viewport.x,
         y: viewport.y,
-        layout: viewport.layout,
+        layout: viewport.layout.clone(),
         index: ws_index,
         output: viewport.output.clone(),
     }
@@ -120,37 +144,58 @@ impl From<&State> for ManagerState {
             .all()
             .iter()
             .filter(|tag| state.windows.iter().any(|w| w.has_tag(&tag.id)))
-            .map(|t| t.label.clone())
+            .map(|t| t.desktop_name())
             .collect();
         let urgent_tags = state
             .tags
             .all()
             .iter()
             .filter(|tag| state.windows.iter().any(|w| w.has_tag(&tag.id) && w.urgent))
-            .map(|t| t.label.clone())
+            .map(|t| t.desktop_name())
+            .collect();
+        let minimized_windows = state
+            .tags
+            .normal()
+            .iter()
+            .flat_map(|tag| {
+                tag.minimized.iter().filter_map(|handle| {
+                    let window = state.windows.iter().find(|w| &w.handle == handle)?;
+                    Some(MinimizedWindow {
+                        tag: tag.desktop_name(),
+                        title: window.name.clone().unwrap_or_default(),
+                    })
+                })
+            })
             .collect();
         for ws in &state.workspaces {
             let tag_label = ws
                 .tag
-                .map(|tag_id| state.tags.get(tag_id).map(|tag| tag.label.clone()))
+                .map(|tag_id| state.tags.get(tag_id).map(Tag::desktop_name))
                 .unwrap()
                 .unwrap();
 
+            let tags = ws
+                .tags()
+                .iter()
+                .filter_map(|&tag_id| state.tags.get(tag_id))
+                .map(Tag::desktop_name)
+                .collect();
             viewports.push(Viewport {
                 tag: tag_label,
+                tags,
                 x: ws.xyhw.x(),
                 y: ws.xyhw.y(),
                 h: ws.xyhw.h() as u32,
                 w: ws.xyhw.w() as u32,
-                layout: ws.layout,
+                layout: ws.layout.clone(),
                 output: ws.output.clone(),
             });
         }
         let active_desktop = match state.focus_manager.workspace(&state.workspaces) {
             Some(ws) => ws
-                .tag
+                .tags()
                 .iter()
-                .map(|&tag_id| state.tags.get(tag_id).unwrap().label.clone())
+                .map(|&tag_id| state.tags.get(tag_id).unwrap().desktop_name())
                 .collect(),
             None => vec![], // todo ??
         };
@@ -160,16 +205,18 @@ impl From<&State> for ManagerState {
         };
         Self {
             window_title,
-            desktop_names: state
+            desktop_names: state.desktop_names(),
+            desktop_outputs: state
                 .tags
                 .normal()
                 .iter()
-                .map(|t| t.label.clone())
+                .map(|t| t.namespace.clone())
                 .collect(),
             viewports,
             active_desktop,
             urgent_tags,
             working_tags,
+            minimized_windows,
         }
     }
 }
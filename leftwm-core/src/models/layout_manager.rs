@@ -1,5 +1,10 @@
-use super::Tag;
-use crate::{config::Config, layouts::Layout, Workspace};
+use super::{Tag, Tags};
+use crate::{
+    config::{Config, LayoutRule},
+    layouts,
+    layouts::Layout,
+    Window, Workspace,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -20,10 +25,13 @@ pub struct LayoutManager {
     pub mode: LayoutMode,
     pub layouts: Vec<Layout>,
     pub layouts_per_workspaces: HashMap<(String, usize), Vec<Layout>>,
+    pub rules: Vec<LayoutRule>,
 }
 
 impl LayoutManager {
     pub fn new(config: &impl Config) -> Self {
+        layouts::register_configured(config.custom_layouts());
+
         let mut layouts_per_workspaces: HashMap<(String, usize), Vec<Layout>> = HashMap::default();
         config
             .workspaces()
@@ -47,20 +55,21 @@ impl LayoutManager {
             mode: config.layout_mode(),
             layouts: config.layouts(),
             layouts_per_workspaces,
+            rules: config.layout_rules(),
         }
     }
 
     pub fn new_layout(&self, output: &str, id: usize) -> Layout {
-        *self
-            .layouts(output, id)
+        self.layouts(output, id)
             .first()
-            .unwrap_or(&Layout::default())
+            .cloned()
+            .unwrap_or_default()
     }
 
-    pub fn next_layout(&self, workspace: &Workspace) -> Layout {
-        let layouts = self.layouts(&workspace.output, workspace.id);
+    pub fn next_layout(&self, workspace: &Workspace, tag: Option<&Tag>) -> Layout {
+        let layouts = self.allowed_layouts(workspace, tag);
 
-        let next = match layouts.iter().position(|&x| x == workspace.layout) {
+        let next = match layouts.iter().position(|x| x == &workspace.layout) {
             Some(index) if index == layouts.len() - 1 => layouts.first(),
             Some(index) => layouts.get(index + 1),
             None => None,
@@ -68,13 +77,14 @@ impl LayoutManager {
 
         // If no layout was found, return the first in the list, in case of a
         // SoftReload with a new list that does not include the current layout.
-        *next.unwrap_or_else(|| layouts.first().unwrap_or(&workspace.layout))
+        next.unwrap_or_else(|| layouts.first().unwrap_or(&workspace.layout))
+            .clone()
     }
 
-    pub fn previous_layout(&self, workspace: &Workspace) -> Layout {
-        let layouts = self.layouts(&workspace.output, workspace.id);
+    pub fn previous_layout(&self, workspace: &Workspace, tag: Option<&Tag>) -> Layout {
+        let layouts = self.allowed_layouts(workspace, tag);
 
-        let next = match layouts.iter().position(|&x| x == workspace.layout) {
+        let next = match layouts.iter().position(|x| x == &workspace.layout) {
             Some(index) if index == 0 => layouts.last(),
             Some(index) => layouts.get(index - 1),
             None => None,
@@ -82,7 +92,8 @@ impl LayoutManager {
 
         // If no layout was found, return the first in the list, in case of a
         // SoftReload with a new list that does not include the current layout.
-        *next.unwrap_or_else(|| layouts.first().unwrap_or(&workspace.layout))
+        next.unwrap_or_else(|| layouts.first().unwrap_or(&workspace.layout))
+            .clone()
     }
 
     pub fn update_layouts(
@@ -94,10 +105,10 @@ impl LayoutManager {
             let tag = tags.iter_mut().find(|t| Some(t.id) == workspace.tag)?;
             match self.mode {
                 LayoutMode::Workspace => {
-                    tag.set_layout(workspace.layout, workspace.main_width_percentage);
+                    tag.set_layout(workspace.layout.clone(), workspace.main_width_percentage);
                 }
                 LayoutMode::Tag => {
-                    workspace.layout = tag.layout;
+                    workspace.layout = tag.layout.clone();
                     workspace.main_width_percentage = tag.main_width_percentage;
                 }
             }
@@ -105,6 +116,62 @@ impl LayoutManager {
         Some(true)
     }
 
+    /// The layout of the first rule matching the workspace and number of tiled windows.
+    pub fn rule_layout(&self, workspace: &Workspace, window_count: usize) -> Option<&Layout> {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(workspace, window_count))
+            .map(|rule| &rule.layout)
+    }
+
+    /// Applies the layout rules to the tags shown on the workspaces, unless their layout has
+    /// been set by hand. Layouts only change when the number of windows or the shape of the
+    /// workspace leads to another rule.
+    pub fn apply_rules(&self, workspaces: &mut [Workspace], tags: &mut Tags, windows: &[Window]) {
+        if self.rules.is_empty() {
+            return;
+        }
+        let window_counts: Vec<usize> = workspaces
+            .iter()
+            .map(|workspace| {
+                windows
+                    .iter()
+                    .filter(|w| workspace.manages(w, workspaces) && !w.floating())
+                    .count()
+            })
+            .collect();
+        for (workspace, window_count) in workspaces.iter_mut().zip(window_counts) {
+            let Some(tag) = workspace.tag.and_then(|tag_id| tags.get_mut(tag_id)) else {
+                continue;
+            };
+            if tag.layout_pinned {
+                continue;
+            }
+            let Some(layout) = self.rule_layout(workspace, window_count) else {
+                continue;
+            };
+            if tag.layout == *layout {
+                continue;
+            }
+            let main_width = layout.main_width();
+            tag.set_layout(layout.clone(), main_width);
+            workspace.layout = layout.clone();
+            if self.mode == LayoutMode::Workspace {
+                workspace.main_width_percentage = main_width;
+            }
+        }
+    }
+
+    /// The layouts of the tag shown on the workspace, or else the ones of the workspace.
+    fn allowed_layouts<'a>(
+        &'a self,
+        workspace: &Workspace,
+        tag: Option<&'a Tag>,
+    ) -> &'a Vec<Layout> {
+        tag.and_then(|tag| tag.config.layouts())
+            .unwrap_or_else(|| self.layouts(&workspace.output, workspace.id))
+    }
+
     fn layouts(&self, output: &str, id: usize) -> &Vec<Layout> {
         self.layouts_per_workspaces
             .get(&(output.to_owned(), id))
@@ -122,6 +189,7 @@ impl LayoutManager {
 #[cfg(test)]
 mod tests {
     use crate::config::tests::TestConfig;
+    use crate::config::Orientation;
     use crate::models::BBox;
 
     use super::*;
@@ -201,7 +269,10 @@ mod tests {
         let layout_manager = layout_manager();
         let workspace = workspace(1, Layout::CenterMainBalanced);
 
-        assert_eq!(layout_manager.next_layout(&workspace), Layout::MainAndDeck);
+        assert_eq!(
+            layout_manager.next_layout(&workspace, None),
+            Layout::MainAndDeck
+        );
     }
 
     #[test]
@@ -209,7 +280,10 @@ mod tests {
         let layout_manager = layout_manager();
         let workspace = workspace(1, Layout::MainAndDeck);
 
-        assert_eq!(layout_manager.next_layout(&workspace), Layout::CenterMain);
+        assert_eq!(
+            layout_manager.next_layout(&workspace, None),
+            Layout::CenterMain
+        );
     }
 
     #[test]
@@ -218,7 +292,7 @@ mod tests {
 
         let workspace = workspace(2, Layout::EvenVertical);
         assert_eq!(
-            layout_manager.next_layout(&workspace),
+            layout_manager.next_layout(&workspace, None),
             Layout::MainAndHorizontalStack
         );
     }
@@ -228,7 +302,10 @@ mod tests {
         let layout_manager = layout_manager();
         let workspace = workspace(1, Layout::Fibonacci);
 
-        assert_eq!(layout_manager.next_layout(&workspace), Layout::CenterMain);
+        assert_eq!(
+            layout_manager.next_layout(&workspace, None),
+            Layout::CenterMain
+        );
     }
 
     #[test]
@@ -237,7 +314,7 @@ mod tests {
         let workspace = workspace(1, Layout::CenterMainBalanced);
 
         assert_eq!(
-            layout_manager.previous_layout(&workspace),
+            layout_manager.previous_layout(&workspace, None),
             Layout::CenterMain
         );
     }
@@ -248,7 +325,7 @@ mod tests {
         let workspace = workspace(1, Layout::CenterMain);
 
         assert_eq!(
-            layout_manager.previous_layout(&workspace),
+            layout_manager.previous_layout(&workspace, None),
             Layout::MainAndDeck
         );
     }
@@ -258,7 +335,10 @@ mod tests {
         let layout_manager = layout_manager();
         let workspace = workspace(3, Layout::EvenVertical);
 
-        assert_eq!(layout_manager.previous_layout(&workspace), Layout::Monocle);
+        assert_eq!(
+            layout_manager.previous_layout(&workspace, None),
+            Layout::Monocle
+        );
     }
 
     #[test]
@@ -267,8 +347,106 @@ mod tests {
         let workspace = workspace(1, Layout::Fibonacci);
 
         assert_eq!(
-            layout_manager.previous_layout(&workspace),
+            layout_manager.previous_layout(&workspace, None),
             Layout::CenterMain
         );
     }
+
+    #[test]
+    fn layouts_of_the_tag_take_precedence() {
+        let layout_manager = layout_manager();
+        let workspace = workspace(1, Layout::Monocle);
+        let mut tag = Tag::new(1, "1", Layout::Monocle);
+        tag.load_config(crate::config::TagConfig {
+            layouts: Some(vec![Layout::Monocle, Layout::Fibonacci]),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            layout_manager.next_layout(&workspace, Some(&tag)),
+            Layout::Fibonacci
+        );
+        assert_eq!(
+            layout_manager.previous_layout(&workspace, Some(&tag)),
+            Layout::Fibonacci
+        );
+        // Without layouts of its own the tag uses the ones of the workspace.
+        tag.config.layouts = Some(vec![]);
+        assert_eq!(
+            layout_manager.next_layout(&workspace, Some(&tag)),
+            Layout::CenterMain
+        );
+    }
+
+    #[test]
+    fn layout_rules_match_window_count_and_orientation() {
+        let mut layout_manager = layout_manager();
+        layout_manager.rules = vec![
+            LayoutRule {
+                orientation: Some(Orientation::Portrait),
+                layout: Layout::MainAndHorizontalStack,
+                ..LayoutRule::default()
+            },
+            LayoutRule {
+                max_windows: Some(1),
+                layout: Layout::Monocle,
+                ..LayoutRule::default()
+            },
+            LayoutRule {
+                min_windows: Some(2),
+                max_windows: Some(3),
+                layout: Layout::MainAndVertStack,
+                ..LayoutRule::default()
+            },
+        ];
+        let mut workspace = workspace(1, Layout::default());
+        workspace.xyhw.set_w(1920);
+        workspace.xyhw.set_h(1080);
+
+        assert_eq!(
+            layout_manager.rule_layout(&workspace, 1),
+            Some(&Layout::Monocle)
+        );
+        assert_eq!(
+            layout_manager.rule_layout(&workspace, 3),
+            Some(&Layout::MainAndVertStack)
+        );
+        assert_eq!(layout_manager.rule_layout(&workspace, 4), None);
+
+        workspace.xyhw.set_w(1080);
+        workspace.xyhw.set_h(1920);
+        assert_eq!(
+            layout_manager.rule_layout(&workspace, 1),
+            Some(&Layout::MainAndHorizontalStack)
+        );
+    }
+
+    #[test]
+    fn layout_rules_count_the_windows_of_all_shown_tags() {
+        let mut layout_manager = layout_manager();
+        layout_manager.rules = vec![LayoutRule {
+            min_windows: Some(2),
+            layout: Layout::Monocle,
+            ..LayoutRule::default()
+        }];
+        let mut tags = Tags::new();
+        tags.add_new("1", Layout::default());
+        tags.add_new("2", Layout::default());
+        let mut workspace = workspace(1, Layout::default());
+        workspace.tag = Some(1);
+        workspace.extra_tags = vec![2];
+        let windows: Vec<Window> = [(1, 1), (2, 2)]
+            .into_iter()
+            .map(|(handle, tag)| {
+                let mut window =
+                    Window::new(crate::models::WindowHandle::MockHandle(handle), None, None);
+                window.set_tag(Some(tag));
+                window
+            })
+            .collect();
+        let mut workspaces = vec![workspace];
+
+        layout_manager.apply_rules(&mut workspaces, &mut tags, &windows);
+        assert_eq!(workspaces[0].layout, Layout::Monocle);
+    }
 }
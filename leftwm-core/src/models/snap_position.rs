@@ -0,0 +1,99 @@
+use super::Xyhw;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// An edge or corner of a workspace a floating window can be put against.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SnapPosition {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+#[derive(Debug, Error)]
+#[error("Could not parse snap position: {0}")]
+pub struct ParseSnapPositionError(String);
+
+impl FromStr for SnapPosition {
+    type Err = ParseSnapPositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "TopLeft" => Ok(Self::TopLeft),
+            "Top" => Ok(Self::Top),
+            "TopRight" => Ok(Self::TopRight),
+            "Left" => Ok(Self::Left),
+            "Right" => Ok(Self::Right),
+            "BottomLeft" => Ok(Self::BottomLeft),
+            "Bottom" => Ok(Self::Bottom),
+            "BottomRight" => Ok(Self::BottomRight),
+            _ => Err(ParseSnapPositionError(s.to_string())),
+        }
+    }
+}
+
+impl SnapPosition {
+    /// The top left corner of an area of width `w` and height `h` put against this edge or
+    /// corner of `within`. Along an edge the area is centered.
+    #[must_use]
+    pub fn place(self, w: i32, h: i32, within: Xyhw) -> (i32, i32) {
+        let (horizontal, vertical) = match self {
+            Self::TopLeft => (Side::Start, Side::Start),
+            Self::Top => (Side::Center, Side::Start),
+            Self::TopRight => (Side::End, Side::Start),
+            Self::Left => (Side::Start, Side::Center),
+            Self::Right => (Side::End, Side::Center),
+            Self::BottomLeft => (Side::Start, Side::End),
+            Self::Bottom => (Side::Center, Side::End),
+            Self::BottomRight => (Side::End, Side::End),
+        };
+        (
+            horizontal.align(w, within.x(), within.w()),
+            vertical.align(h, within.y(), within.h()),
+        )
+    }
+}
+
+enum Side {
+    Start,
+    Center,
+    End,
+}
+
+impl Side {
+    const fn align(&self, size: i32, start: i32, available: i32) -> i32 {
+        match self {
+            Self::Start => start,
+            Self::Center => start + (available - size) / 2,
+            Self::End => start + available - size,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::XyhwBuilder;
+
+    #[test]
+    fn windows_are_placed_against_edges_and_corners() {
+        let area = XyhwBuilder {
+            x: 100,
+            y: 50,
+            h: 600,
+            w: 800,
+            ..XyhwBuilder::default()
+        }
+        .into();
+        assert_eq!(SnapPosition::TopLeft.place(200, 100, area), (100, 50));
+        assert_eq!(SnapPosition::Right.place(200, 100, area), (700, 300));
+        assert_eq!(SnapPosition::Bottom.place(200, 100, area), (400, 550));
+        assert_eq!(SnapPosition::BottomRight.place(200, 100, area), (700, 550));
+    }
+}
@@ -0,0 +1,482 @@
+//! The split tree behind the `Bsp` layout.
+use super::{Direction, WindowHandle, Xyhw, XyhwBuilder};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use thiserror::Error;
+
+/// The smallest share, in percent, either side of a split can be given.
+const MIN_RATIO: u8 = 10;
+
+/// How a split divides its area between its two children.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitAxis {
+    /// The children are placed next to each other, the first one on the left.
+    Columns,
+    /// The children are placed above each other, the first one on top.
+    Rows,
+}
+
+#[derive(Debug, Error)]
+#[error("Could not parse split axis: {0}")]
+pub struct ParseSplitAxisError(String);
+
+impl FromStr for SplitAxis {
+    type Err = ParseSplitAxisError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Columns" => Ok(Self::Columns),
+            "Rows" => Ok(Self::Rows),
+            _ => Err(ParseSplitAxisError(s.to_string())),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Window(WindowHandle),
+    Split {
+        axis: SplitAxis,
+        /// Share of the area given to `first`, in percent.
+        ratio: u8,
+        first: Box<Node>,
+        second: Box<Node>,
+    },
+}
+
+impl Node {
+    fn windows(&self, handles: &mut Vec<WindowHandle>) {
+        match self {
+            Self::Window(handle) => handles.push(*handle),
+            Self::Split { first, second, .. } => {
+                first.windows(handles);
+                second.windows(handles);
+            }
+        }
+    }
+
+    fn count(&self) -> usize {
+        match self {
+            Self::Window(_) => 1,
+            Self::Split { first, second, .. } => first.count() + second.count(),
+        }
+    }
+
+    fn find_mut(&mut self, handle: WindowHandle) -> Option<&mut Self> {
+        match self {
+            Self::Window(h) if *h == handle => Some(self),
+            Self::Window(_) => None,
+            Self::Split { first, second, .. } => first
+                .find_mut(handle)
+                .or_else(move || second.find_mut(handle)),
+        }
+    }
+
+    /// The tree without `handle`, the sibling of the removed window takes the place of their
+    /// split.
+    fn without(self, handle: WindowHandle) -> Option<Self> {
+        match self {
+            Self::Window(h) if h == handle => None,
+            Self::Window(_) => Some(self),
+            Self::Split {
+                axis,
+                ratio,
+                first,
+                second,
+            } => match (first.without(handle), second.without(handle)) {
+                (Some(first), Some(second)) => Some(Self::Split {
+                    axis,
+                    ratio,
+                    first: Box::new(first),
+                    second: Box::new(second),
+                }),
+                (Some(only), None) | (None, Some(only)) => Some(only),
+                (None, None) => None,
+            },
+        }
+    }
+
+    fn arrange(&self, area: Xyhw, geometry: &mut Vec<(WindowHandle, Xyhw)>) {
+        match self {
+            Self::Window(handle) => geometry.push((*handle, area)),
+            Self::Split {
+                axis,
+                ratio,
+                first,
+                second,
+            } => {
+                let (first_area, second_area) = split_area(area, *axis, *ratio);
+                first.arrange(first_area, geometry);
+                second.arrange(second_area, geometry);
+            }
+        }
+    }
+
+    fn swap(&mut self, a: WindowHandle, b: WindowHandle) {
+        match self {
+            Self::Window(handle) if *handle == a => *handle = b,
+            Self::Window(handle) if *handle == b => *handle = a,
+            Self::Window(_) => {}
+            Self::Split { first, second, .. } => {
+                first.swap(a, b);
+                second.swap(a, b);
+            }
+        }
+    }
+
+    fn set_ratio(&mut self, handle: WindowHandle, percent: u8) -> bool {
+        let Self::Split {
+            ratio,
+            first,
+            second,
+            ..
+        } = self
+        else {
+            return false;
+        };
+        if **first == Self::Window(handle) {
+            *ratio = percent;
+            true
+        } else if **second == Self::Window(handle) {
+            *ratio = 100 - percent;
+            true
+        } else {
+            first.set_ratio(handle, percent) || second.set_ratio(handle, percent)
+        }
+    }
+
+    fn rotate(&mut self) {
+        if let Self::Split {
+            axis,
+            ratio,
+            first,
+            second,
+        } = self
+        {
+            // Turning clockwise, the left child ends up on top while the top child ends up on
+            // the right.
+            if *axis == SplitAxis::Rows {
+                std::mem::swap(first, second);
+                *ratio = 100 - *ratio;
+            }
+            *axis = match axis {
+                SplitAxis::Columns => SplitAxis::Rows,
+                SplitAxis::Rows => SplitAxis::Columns,
+            };
+            first.rotate();
+            second.rotate();
+        }
+    }
+
+    fn balance(&mut self) {
+        if let Self::Split {
+            ratio,
+            first,
+            second,
+            ..
+        } = self
+        {
+            let first_count = first.count();
+            let total = first_count + second.count();
+            *ratio = clamp_ratio((first_count * 100 / total) as u8);
+            first.balance();
+            second.balance();
+        }
+    }
+
+    fn flip(&mut self, flip_axis: SplitAxis) {
+        if let Self::Split {
+            axis,
+            ratio,
+            first,
+            second,
+        } = self
+        {
+            if *axis == flip_axis {
+                std::mem::swap(first, second);
+                *ratio = 100 - *ratio;
+            }
+            first.flip(flip_axis);
+            second.flip(flip_axis);
+        }
+    }
+}
+
+/// A binary tree of splits per tag, each leaf holding one tiled window.
+///
+/// New windows are inserted by splitting the leaf of the focused window, either in the
+/// preselected direction or along the longest side of that window.
+#[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SplitTree {
+    root: Option<Node>,
+    /// Side of the focused window the next window will be placed on.
+    pub preselection: Option<Direction>,
+    /// The window new windows are placed next to when the focused window is not in the tree.
+    last_focused: Option<WindowHandle>,
+}
+
+impl SplitTree {
+    /// The windows in the tree, from left to right and top to bottom.
+    pub fn windows(&self) -> Vec<WindowHandle> {
+        let mut handles = vec![];
+        if let Some(root) = &self.root {
+            root.windows(&mut handles);
+        }
+        handles
+    }
+
+    /// Whether the window is part of the tree.
+    pub fn contains(&self, handle: WindowHandle) -> bool {
+        self.windows().contains(&handle)
+    }
+
+    /// Updates the tree so it holds exactly the given windows. Windows which are new to the tree
+    /// are inserted next to the focused window.
+    pub fn sync(&mut self, handles: &[WindowHandle], focused: Option<WindowHandle>, area: Xyhw) {
+        for handle in self.windows() {
+            if !handles.contains(&handle) {
+                self.remove(handle);
+            }
+        }
+        if let Some(focused) = focused.filter(|&h| self.contains(h)) {
+            self.last_focused = Some(focused);
+        }
+        for handle in handles {
+            if !self.contains(*handle) {
+                self.insert(*handle, area);
+            }
+        }
+    }
+
+    /// Inserts a window next to the last focused window of the tree.
+    pub fn insert(&mut self, handle: WindowHandle, area: Xyhw) {
+        let direction = self.preselection.take();
+        let geometry = self.geometry(area);
+        let target = self
+            .last_focused
+            .filter(|h| geometry.iter().any(|(g, _)| g == h))
+            .or_else(|| geometry.last().map(|(h, _)| *h));
+        let Some((target, target_area)) =
+            target.and_then(|t| geometry.into_iter().find(|(h, _)| *h == t))
+        else {
+            self.root = Some(Node::Window(handle));
+            return;
+        };
+        let direction = direction.unwrap_or(if target_area.w() >= target_area.h() {
+            Direction::Right
+        } else {
+            Direction::Down
+        });
+        let Some(leaf) = self.root.as_mut().and_then(|root| root.find_mut(target)) else {
+            return;
+        };
+        let (axis, new_first) = match direction {
+            Direction::Left => (SplitAxis::Columns, true),
+            Direction::Right => (SplitAxis::Columns, false),
+            Direction::Up => (SplitAxis::Rows, true),
+            Direction::Down => (SplitAxis::Rows, false),
+        };
+        let existing = Box::new(Node::Window(target));
+        let new = Box::new(Node::Window(handle));
+        let (first, second) = if new_first {
+            (new, existing)
+        } else {
+            (existing, new)
+        };
+        *leaf = Node::Split {
+            axis,
+            ratio: 50,
+            first,
+            second,
+        };
+    }
+
+    /// Removes a window, its sibling takes over the space of their split.
+    pub fn remove(&mut self, handle: WindowHandle) {
+        self.root = self.root.take().and_then(|root| root.without(handle));
+        if self.last_focused == Some(handle) {
+            self.last_focused = None;
+        }
+    }
+
+    /// Puts a window in the place of another one. Returns false if `old` isn't part of the tree.
+    pub fn replace(&mut self, old: WindowHandle, new: WindowHandle) -> bool {
+        let Some(leaf) = self.root.as_mut().and_then(|root| root.find_mut(old)) else {
+            return false;
+        };
+        *leaf = Node::Window(new);
+        if self.last_focused == Some(old) {
+            self.last_focused = Some(new);
+        }
+        true
+    }
+
+    /// Lets two windows of the tree trade places.
+    pub fn swap(&mut self, a: WindowHandle, b: WindowHandle) {
+        if let Some(root) = &mut self.root {
+            root.swap(a, b);
+        }
+    }
+
+    /// The area of each window in the tree when it is laid out in `area`.
+    pub fn geometry(&self, area: Xyhw) -> Vec<(WindowHandle, Xyhw)> {
+        let mut geometry = vec![];
+        if let Some(root) = &self.root {
+            root.arrange(area, &mut geometry);
+        }
+        geometry
+    }
+
+    /// Sets the split directly holding the window, so the window gets `percent` of its area.
+    /// Returns false if the window isn't split from anything.
+    pub fn set_ratio(&mut self, handle: WindowHandle, percent: u8) -> bool {
+        let percent = clamp_ratio(percent);
+        self.root
+            .as_mut()
+            .map_or(false, |root| root.set_ratio(handle, percent))
+    }
+
+    /// Rotates the whole tree by 90 degrees clockwise.
+    pub fn rotate(&mut self) {
+        if let Some(root) = &mut self.root {
+            root.rotate();
+        }
+    }
+
+    /// Adjusts all split ratios so every window gets the same amount of space.
+    pub fn balance(&mut self) {
+        if let Some(root) = &mut self.root {
+            root.balance();
+        }
+    }
+
+    /// Mirrors the tree by swapping the children of every split along the axis. Flipping the
+    /// columns mirrors the tree from left to right, flipping the rows from top to bottom.
+    pub fn flip(&mut self, axis: SplitAxis) {
+        if let Some(root) = &mut self.root {
+            root.flip(axis);
+        }
+    }
+}
+
+fn clamp_ratio(percent: u8) -> u8 {
+    percent.clamp(MIN_RATIO, 100 - MIN_RATIO)
+}
+
+fn split_area(area: Xyhw, axis: SplitAxis, ratio: u8) -> (Xyhw, Xyhw) {
+    let build = |x, y, w, h| -> Xyhw {
+        XyhwBuilder {
+            x,
+            y,
+            h,
+            w,
+            ..XyhwBuilder::default()
+        }
+        .into()
+    };
+    match axis {
+        SplitAxis::Columns => {
+            let width = area.w() * i32::from(ratio) / 100;
+            (
+                build(area.x(), area.y(), width, area.h()),
+                build(area.x() + width, area.y(), area.w() - width, area.h()),
+            )
+        }
+        SplitAxis::Rows => {
+            let height = area.h() * i32::from(ratio) / 100;
+            (
+                build(area.x(), area.y(), area.w(), height),
+                build(area.x(), area.y() + height, area.w(), area.h() - height),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area() -> Xyhw {
+        XyhwBuilder {
+            h: 600,
+            w: 1000,
+            ..XyhwBuilder::default()
+        }
+        .into()
+    }
+
+    fn handle(id: i32) -> WindowHandle {
+        WindowHandle::MockHandle(id)
+    }
+
+    fn sizes(tree: &SplitTree) -> Vec<(i32, i32, i32, i32)> {
+        tree.geometry(area())
+            .iter()
+            .map(|(_, a)| (a.x(), a.y(), a.w(), a.h()))
+            .collect()
+    }
+
+    #[test]
+    fn windows_are_split_along_their_longest_side() {
+        let mut tree = SplitTree::default();
+        tree.sync(&[handle(1), handle(2), handle(3)], Some(handle(1)), area());
+        assert_eq!(tree.windows(), vec![handle(1), handle(2), handle(3)]);
+        // Nothing was focused yet, so each window splits the one inserted before it.
+        assert_eq!(
+            sizes(&tree),
+            vec![(0, 0, 500, 600), (500, 0, 500, 300), (500, 300, 500, 300)]
+        );
+    }
+
+    #[test]
+    fn preselection_is_used_once() {
+        let mut tree = SplitTree::default();
+        tree.sync(&[handle(1)], Some(handle(1)), area());
+        tree.preselection = Some(Direction::Up);
+        tree.sync(&[handle(1), handle(2)], Some(handle(1)), area());
+        assert_eq!(tree.preselection, None);
+        assert_eq!(tree.windows(), vec![handle(2), handle(1)]);
+        assert_eq!(sizes(&tree), vec![(0, 0, 1000, 300), (0, 300, 1000, 300)]);
+    }
+
+    #[test]
+    fn removing_a_window_gives_its_space_to_the_sibling() {
+        let mut tree = SplitTree::default();
+        tree.sync(&[handle(1), handle(2), handle(3)], Some(handle(1)), area());
+        tree.sync(&[handle(1), handle(3)], Some(handle(1)), area());
+        assert_eq!(sizes(&tree), vec![(0, 0, 500, 600), (500, 0, 500, 600)]);
+    }
+
+    #[test]
+    fn ratio_rotate_and_balance() {
+        let mut tree = SplitTree::default();
+        tree.sync(&[handle(1), handle(2), handle(3)], Some(handle(1)), area());
+
+        assert!(tree.set_ratio(handle(1), 95));
+        assert_eq!(sizes(&tree)[0], (0, 0, 900, 600));
+
+        tree.balance();
+        assert_eq!(sizes(&tree)[0], (0, 0, 330, 600));
+
+        tree.rotate();
+        assert_eq!(tree.windows(), vec![handle(1), handle(3), handle(2)]);
+        assert_eq!(sizes(&tree)[0], (0, 0, 1000, 198));
+    }
+
+    #[test]
+    fn flipping_mirrors_the_splits_of_the_axis() {
+        let mut tree = SplitTree::default();
+        tree.sync(&[handle(1), handle(2), handle(3)], Some(handle(1)), area());
+        assert!(tree.set_ratio(handle(1), 70));
+        let before = sizes(&tree);
+
+        tree.flip(SplitAxis::Columns);
+        assert_eq!(tree.windows(), vec![handle(2), handle(3), handle(1)]);
+        assert_eq!(sizes(&tree)[2], (300, 0, 700, 600));
+
+        tree.flip(SplitAxis::Rows);
+        tree.flip(SplitAxis::Rows);
+        tree.flip(SplitAxis::Columns);
+        assert_eq!(sizes(&tree), before);
+    }
+}
@@ -0,0 +1,30 @@
+use super::{WindowHandle, Xyhw};
+
+/// The titles of the windows on a workspace using the `Tabbed` or `Stacked` layout.
+/// The display server draws the bar, clicking a tab focuses its window.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TabBar {
+    /// Position of the bar on the screen.
+    pub xyhw: Xyhw,
+    pub tabs: Vec<Tab>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tab {
+    pub window: WindowHandle,
+    pub title: String,
+    /// Whether this is the window shown by the layout.
+    pub active: bool,
+    /// Position of the tab inside the bar.
+    pub xyhw: Xyhw,
+}
+
+impl TabBar {
+    /// The window of the tab at a point relative to the bar.
+    pub fn window_at(&self, x: i32, y: i32) -> Option<WindowHandle> {
+        self.tabs
+            .iter()
+            .find(|tab| tab.xyhw.contains_point(x, y))
+            .map(|tab| tab.window)
+    }
+}
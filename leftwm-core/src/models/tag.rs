@@ -1,5 +1,9 @@
-use super::TagId;
-use crate::{layouts::Layout, Window, Workspace};
+use super::{SplitTree, TabBar, TagId, Viewport, WindowHandle, XyhwBuilder};
+use crate::{
+    config::TagConfig,
+    layouts::{self, Layout},
+    Window, Workspace,
+};
 use serde::{Deserialize, Serialize};
 
 /// Wrapper struct holding all the tags.
@@ -72,11 +76,43 @@ impl Tags {
         self.add_new(next_id.to_string().as_str(), layout)
     }
 
+    /// Create a new tag for every label, belonging to the output if given,
+    /// and append them to the list of normal tags.
+    pub fn add_new_for_output(&mut self, labels: &[String], output: Option<&str>) {
+        for label in labels {
+            let id = self.add_new(label, Layout::default());
+            if let Some(tag) = self.get_mut(id) {
+                tag.namespace = output.map(str::to_owned);
+            }
+        }
+    }
+
     // todo: add_new_at(position, label, layout)
     // -> shifting all one to the right and re-number them (vec.insert)
 
-    // todo: remove(id)
-    // -> shifting all right of the removed tag one to the left and re-number them
+    /// Remove a normal tag and return it.
+    /// The tags to the right of it shift one to the left and are renumbered.
+    pub fn remove(&mut self, id: TagId) -> Option<Tag> {
+        if !(1..=self.normal.len()).contains(&id) {
+            return None;
+        }
+        let tag = self.normal.remove(id - 1);
+        self.renumber();
+        Some(tag)
+    }
+
+    /// Move a normal tag to another position, counted from 1 like the IDs.
+    /// The tags in between shift by one and are renumbered.
+    pub fn move_to(&mut self, id: TagId, position: usize) -> Option<()> {
+        let range = 1..=self.normal.len();
+        if !range.contains(&id) || !range.contains(&position) {
+            return None;
+        }
+        let tag = self.normal.remove(id - 1);
+        self.normal.insert(position - 1, tag);
+        self.renumber();
+        Some(())
+    }
 
     /// Create a new hidden tag with the provided label,
     /// and append it to the list of hidden tags.
@@ -162,6 +198,13 @@ impl Tags {
     pub fn len_normal(&self) -> usize {
         self.normal.len()
     }
+
+    /// Number the normal tags by their position again.
+    fn renumber(&mut self) {
+        for (index, tag) in self.normal.iter_mut().enumerate() {
+            tag.id = index + 1;
+        }
+    }
 }
 
 impl Default for Tags {
@@ -178,7 +221,8 @@ impl Default for Tags {
 /// the same set of tags and windows are shared among
 /// all Workspaces, this means there aren't multiple instances of
 /// the same Tag on different Screens.
-#[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct Tag {
     /// Unique identifier for the tag,
     /// this is automatically assigned by `LeftWM`.
@@ -219,6 +263,48 @@ pub struct Tag {
     /// to the secondary column(s).
     pub main_width_percentage: u8,
 
+    /// The number of windows which
+    /// are placed in the "main" area
+    /// of the layout.
+    #[serde(default = "default_main_count")]
+    pub main_count: usize,
+
+    /// Arrangement of the windows
+    /// for the `Bsp` layout.
+    #[serde(default)]
+    pub split_tree: SplitTree,
+
+    /// Part of the window strip shown
+    /// by the `Scrolling` layout.
+    #[serde(default)]
+    pub viewport: Viewport,
+
+    /// The window shown by the `Tabbed`
+    /// and `Stacked` layouts.
+    #[serde(default)]
+    pub active_tab: Option<WindowHandle>,
+
+    /// Whether the layout was set by hand,
+    /// layout rules leave it alone then.
+    #[serde(default)]
+    pub layout_pinned: bool,
+
+    /// The minimized windows of this tag,
+    /// the most recently minimized one last.
+    #[serde(default)]
+    pub minimized: Vec<WindowHandle>,
+
+    /// The output this tag belongs to when every
+    /// output has tags of its own, `None` if the
+    /// tag can be shown on any output.
+    #[serde(default)]
+    pub namespace: Option<String>,
+
+    /// The settings of this tag from the config,
+    /// taking precedence over the global ones.
+    #[serde(default)]
+    pub config: TagConfig,
+
     pub flipped_horizontal: bool,
     pub flipped_vertical: bool,
     pub layout_rotation: usize,
@@ -231,18 +317,55 @@ impl Tag {
             id,
             label: label.to_owned(),
             hidden: false,
-            layout,
             main_width_percentage: layout.main_width(),
+            main_count: 1,
+            split_tree: SplitTree::default(),
+            viewport: Viewport::default(),
+            active_tab: None,
+            layout_pinned: false,
+            minimized: vec![],
+            namespace: None,
+            config: TagConfig::default(),
+            layout,
             flipped_horizontal: false,
             flipped_vertical: false,
             layout_rotation: 0,
         }
     }
 
-    pub fn update_windows(&self, windows: &mut [Window], workspace: &Workspace) {
+    /// Whether the tag can be shown on a workspace of the output.
+    #[must_use]
+    pub fn belongs_to(&self, output: &str) -> bool {
+        self.namespace
+            .as_ref()
+            .map_or(true, |namespace| namespace == output)
+    }
+
+    /// The name of the tag as EWMH desktop, prefixed with its output when every output has tags
+    /// of its own, to keep the names apart.
+    #[must_use]
+    pub fn desktop_name(&self) -> String {
+        match &self.namespace {
+            Some(output) => format!("{output}_{}", self.label),
+            None => self.label.clone(),
+        }
+    }
+
+    /// Whether the window is placed by the layout of this tag, which also arranges the windows
+    /// of the other tags shown on the workspace.
+    fn tiles(window: &Window, workspace: &Workspace, workspaces: &[Workspace]) -> bool {
+        workspace.manages(window, workspaces) && !window.floating()
+    }
+
+    pub fn update_windows(
+        &self,
+        windows: &mut [Window],
+        workspace: &Workspace,
+        workspaces: &[Workspace],
+    ) {
         if let Some(window) = windows
             .iter_mut()
-            .find(|w| w.has_tag(&self.id) && w.is_fullscreen())
+            .find(|w| workspace.owns(w, workspaces) && w.is_fullscreen() && !w.is_minimized())
         {
             window.set_visible(true);
             window.normal = workspace.xyhw;
@@ -250,7 +373,7 @@ impl Tag {
             windows
                 .iter_mut()
                 .filter(|w| {
-                    w.has_tag(&self.id)
+                    workspace.is_displaying(w)
                         && w.transient.unwrap_or_else(|| 0.into()) == handle
                         && w.is_managed()
                 })
@@ -260,13 +383,15 @@ impl Tag {
         } else {
             // Don't bother updating the other windows when a window is fullscreen.
             // Mark all windows for this workspace as visible.
-            let mut all_mine: Vec<&mut Window> =
-                windows.iter_mut().filter(|w| w.has_tag(&self.id)).collect();
+            let mut all_mine: Vec<&mut Window> = windows
+                .iter_mut()
+                .filter(|w| workspace.is_displaying(w) && !w.is_minimized())
+                .collect();
             all_mine.iter_mut().for_each(|w| w.set_visible(true));
             // Update the location of all non-floating windows.
             let mut managed_nonfloat: Vec<&mut Window> = windows
                 .iter_mut()
-                .filter(|w| w.has_tag(&self.id) && w.is_managed() && !w.floating())
+                .filter(|w| Self::tiles(w, workspace, workspaces))
                 .collect();
             self.layout
                 .update_windows(workspace, &mut managed_nonfloat, self);
@@ -276,8 +401,22 @@ impl Tag {
             // Update the location of all floating windows.
             windows
                 .iter_mut()
-                .filter(|w| w.has_tag(&self.id) && w.is_managed() && w.floating())
+                .filter(|w| workspace.manages(w, workspaces) && w.floating())
                 .for_each(|w| w.normal = workspace.xyhw);
+            // Maximized windows fill the workspace, their place in the layout is kept for when
+            // they are restored.
+            let area = XyhwBuilder {
+                x: workspace.x(),
+                y: workspace.y(),
+                w: workspace.width(),
+                h: workspace.height(),
+                ..XyhwBuilder::default()
+            }
+            .into();
+            windows
+                .iter_mut()
+                .filter(|w| workspace.manages(w, workspaces) && w.is_maximized())
+                .for_each(|w| w.normal = area);
         }
     }
 
@@ -304,6 +443,80 @@ impl Tag {
         f32::from(self.main_width_percentage)
     }
 
+    /// Changes the number of windows in the main area by the provided delta.
+    /// Result is sanitized, so there is always at least one main window.
+    ///
+    /// ## Arguments
+    /// * `delta` - increase/decrease main window count by this amount
+    pub fn change_main_count(&mut self, delta: i8) {
+        self.main_count = (self.main_count as isize + delta as isize).max(1) as usize;
+    }
+
+    /// Brings the split tree up to date with the tiled windows of this tag.
+    /// New windows are placed next to the focused window.
+    pub fn update_split_tree(
+        &mut self,
+        windows: &[Window],
+        focused: Option<WindowHandle>,
+        workspace: &Workspace,
+        workspaces: &[Workspace],
+    ) {
+        let tiled: Vec<WindowHandle> = windows
+            .iter()
+            .filter(|w| Self::tiles(w, workspace, workspaces))
+            .map(|w| w.handle)
+            .collect();
+        self.split_tree
+            .sync(&tiled, focused, layouts::bsp_area(workspace));
+    }
+
+    /// The tiled windows of this tag as columns of the `Scrolling` layout.
+    pub fn columns(
+        &self,
+        windows: &[Window],
+        workspace: &Workspace,
+        workspaces: &[Workspace],
+    ) -> Vec<(WindowHandle, u8)> {
+        windows
+            .iter()
+            .filter(|w| Self::tiles(w, workspace, workspaces))
+            .map(|w| (w.handle, w.column_width))
+            .collect()
+    }
+
+    /// The tab bar for the tiled windows of this tag, if its layout has one.
+    /// There is no tab bar while a window is fullscreen.
+    pub fn tab_bar(
+        &self,
+        windows: &[Window],
+        workspace: &Workspace,
+        workspaces: &[Workspace],
+    ) -> Option<TabBar> {
+        if windows
+            .iter()
+            .any(|w| workspace.owns(w, workspaces) && w.is_fullscreen() && !w.is_minimized())
+        {
+            return None;
+        }
+        let tiled: Vec<&Window> = windows
+            .iter()
+            .filter(|w| Self::tiles(w, workspace, workspaces))
+            .collect();
+        self.layout.tab_bar(workspace, self, &tiled)
+    }
+
+    /// Takes over the settings of the tag from the config and sets its initial layout and main
+    /// width.
+    pub fn load_config(&mut self, config: TagConfig) {
+        if let Some(layout) = config.initial_layout() {
+            let main_width = config.main_width.unwrap_or_else(|| layout.main_width());
+            self.set_layout(layout.clone(), main_width);
+        } else if let Some(main_width) = config.main_width {
+            self.set_main_width(main_width);
+        }
+        self.config = config;
+    }
+
     pub fn set_layout(&mut self, layout: Layout, main_width_percentage: u8) {
         self.layout = layout;
         self.set_main_width(main_width_percentage);
@@ -323,9 +536,13 @@ impl Tag {
     }
 }
 
+const fn default_main_count() -> usize {
+    1
+}
+
 #[cfg(test)]
 mod tests {
-    use super::Tags;
+    use super::{Tag, Tags};
     use crate::layouts::Layout;
 
     #[test]
@@ -445,4 +662,52 @@ mod tests {
         let second_retrieve = tags.get_mut(2).unwrap();
         assert_eq!(second_retrieve.label, String::from("code"));
     }
+
+    #[test]
+    fn main_count_cannot_go_below_one() {
+        let mut tag = Tag::new(1, "home", Layout::default());
+        tag.change_main_count(2);
+        assert_eq!(tag.main_count, 3);
+        tag.change_main_count(-5);
+        assert_eq!(tag.main_count, 1);
+    }
+
+    #[test]
+    fn removed_and_moved_tags_are_renumbered() {
+        let mut tags = Tags::new();
+        for label in ["home", "chat", "surf", "code"] {
+            tags.add_new(label, Layout::default());
+        }
+        let labels = |tags: &Tags| -> Vec<(usize, String)> {
+            tags.normal()
+                .iter()
+                .map(|tag| (tag.id, tag.label.clone()))
+                .collect()
+        };
+
+        assert_eq!(
+            tags.remove(2).map(|tag| tag.label),
+            Some("chat".to_string())
+        );
+        assert_eq!(
+            labels(&tags),
+            vec![
+                (1, "home".to_string()),
+                (2, "surf".to_string()),
+                (3, "code".to_string())
+            ]
+        );
+        assert!(tags.remove(4).is_none());
+
+        assert!(tags.move_to(3, 1).is_some());
+        assert_eq!(
+            labels(&tags),
+            vec![
+                (1, "code".to_string()),
+                (2, "home".to_string()),
+                (3, "surf".to_string())
+            ]
+        );
+        assert!(tags.move_to(1, 4).is_none());
+    }
 }
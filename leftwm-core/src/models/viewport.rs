@@ -0,0 +1,114 @@
+//! The viewport of the `Scrolling` layout.
+use super::WindowHandle;
+use serde::{Deserialize, Serialize};
+
+/// Part of an unbounded strip of columns which is shown in the workspace.
+///
+/// Positions and widths are in percent of the workspace width, so the viewport always spans
+/// `offset..offset + 100`.
+#[derive(Default, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Viewport {
+    /// Start of the viewport on the strip.
+    pub offset: u32,
+    /// The window the viewport was last moved to.
+    followed: Option<WindowHandle>,
+}
+
+impl Viewport {
+    /// Moves the viewport so the focused column is in view, if focus moved to another column
+    /// since the last call. Scrolling away from the focused column is left alone otherwise.
+    pub fn follow(&mut self, columns: &[(WindowHandle, u8)], focused: Option<WindowHandle>) {
+        match focused {
+            Some(handle) if focused != self.followed => self.show(columns, handle),
+            _ => self.offset = self.clamped_offset(columns),
+        }
+    }
+
+    /// Moves the viewport as little as possible to bring the column of the window into view.
+    pub fn show(&mut self, columns: &[(WindowHandle, u8)], handle: WindowHandle) {
+        if let Some((start, end)) = bounds(columns, handle) {
+            if start < self.offset {
+                self.offset = start;
+            } else if end > self.offset + 100 {
+                self.offset = end - 100;
+            }
+            self.followed = Some(handle);
+        }
+        self.offset = self.clamped_offset(columns);
+    }
+
+    /// Scrolls by one column, aligning the viewport with the start of the next or previous
+    /// column.
+    pub fn scroll(&mut self, columns: &[(WindowHandle, u8)], forward: bool) {
+        let mut starts = columns.iter().scan(0, |start, (_, width)| {
+            let current = *start;
+            *start += u32::from(*width);
+            Some(current)
+        });
+        let offset = self.clamped_offset(columns);
+        let target = if forward {
+            starts.find(|&start| start > offset)
+        } else {
+            starts.take_while(|&start| start < offset).last()
+        };
+        if let Some(target) = target {
+            self.offset = target;
+            self.offset = self.clamped_offset(columns);
+        }
+    }
+
+    /// The offset, limited so the viewport doesn't scroll past the last column.
+    pub fn clamped_offset(&self, columns: &[(WindowHandle, u8)]) -> u32 {
+        let total: u32 = columns.iter().map(|(_, width)| u32::from(*width)).sum();
+        self.offset.min(total.saturating_sub(100))
+    }
+}
+
+/// Start and end of the column holding the window.
+fn bounds(columns: &[(WindowHandle, u8)], handle: WindowHandle) -> Option<(u32, u32)> {
+    let mut start = 0;
+    for (h, width) in columns {
+        let end = start + u32::from(*width);
+        if *h == handle {
+            return Some((start, end));
+        }
+        start = end;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn columns() -> Vec<(WindowHandle, u8)> {
+        (1..=4).map(|i| (WindowHandle::MockHandle(i), 50)).collect()
+    }
+
+    #[test]
+    fn viewport_follows_focus_once() {
+        let columns = columns();
+        let mut viewport = Viewport::default();
+        viewport.follow(&columns, Some(WindowHandle::MockHandle(4)));
+        assert_eq!(viewport.offset, 100);
+
+        viewport.scroll(&columns, false);
+        viewport.follow(&columns, Some(WindowHandle::MockHandle(4)));
+        assert_eq!(viewport.offset, 50);
+
+        viewport.follow(&columns, Some(WindowHandle::MockHandle(1)));
+        assert_eq!(viewport.offset, 0);
+    }
+
+    #[test]
+    fn scrolling_stops_at_the_ends() {
+        let columns = columns();
+        let mut viewport = Viewport::default();
+        viewport.scroll(&columns, false);
+        assert_eq!(viewport.offset, 0);
+        for _ in 0..5 {
+            viewport.scroll(&columns, true);
+        }
+        assert_eq!(viewport.offset, 100);
+    }
+}
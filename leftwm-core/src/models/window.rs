@@ -2,7 +2,9 @@
 #![allow(clippy::module_name_repetitions)]
 use super::WindowState;
 use super::WindowType;
+use crate::config::{ActivationPolicy, FloatingGeometry, FloatingPlacement, InsertBehavior};
 use crate::models::Margins;
+use crate::models::ScratchPadName;
 use crate::models::TagId;
 use crate::models::Xyhw;
 use crate::models::XyhwBuilder;
@@ -55,9 +57,51 @@ pub struct Window {
     pub pid: Option<u32>,
     pub r#type: WindowType,
     pub tag: Option<TagId>,
+    /// Other tags the window shows up on. The window is arranged and minimized with `tag`.
+    #[serde(default)]
+    pub extra_tags: Vec<TagId>,
     pub border: i32,
     pub margin: Margins,
     pub margin_multiplier: f32,
+    /// Share of the column or row this window gets relative to the other tiled windows.
+    #[serde(default = "default_size_weight")]
+    pub size_weight: f32,
+    /// Width of the column of this window in the `Scrolling` layout, in percent of the workspace.
+    #[serde(default = "default_column_width")]
+    pub column_width: u8,
+    /// Windows launched from this terminal take its place until they are closed.
+    #[serde(default)]
+    pub is_terminal: bool,
+    /// Never take the place of the terminal this window was launched from.
+    #[serde(default)]
+    pub no_swallow: bool,
+    /// The terminal hidden while this window takes its place.
+    #[serde(default)]
+    pub swallowed: Option<WindowHandle>,
+    /// Overrides `floating_placement` of the config for this window.
+    #[serde(default)]
+    pub floating_placement: Option<FloatingPlacement>,
+    /// Size and position given by a window rule for when the window floats.
+    #[serde(default)]
+    pub floating_geometry: Option<FloatingGeometry>,
+    /// Overrides `insert_behavior` of the config for this window.
+    #[serde(default)]
+    pub insert_behavior: Option<InsertBehavior>,
+    /// Overrides `border_width` of the config for this window.
+    #[serde(default)]
+    pub border_width: Option<i32>,
+    /// Border colour of this window while it is not focused, instead of the one of the theme.
+    #[serde(default)]
+    pub border_color: Option<String>,
+    /// Left out when the focus is cycled through the windows of a tag.
+    #[serde(default)]
+    pub skip_focus_cycle: bool,
+    /// The scratchpad the window is attached to once it is managed.
+    #[serde(default)]
+    pub spawn_scratchpad: Option<ScratchPadName>,
+    /// Overrides `activation_policy` of the config for this window.
+    #[serde(default)]
+    pub activation_policy: Option<ActivationPolicy>,
     states: Vec<WindowState>,
     pub requested: Option<Xyhw>,
     pub normal: Xyhw,
@@ -67,6 +111,9 @@ pub struct Window {
     // Two strings that are within a XClassHint, kept separate for simpler comparing.
     pub res_name: Option<String>,
     pub res_class: Option<String>,
+    /// `WM_WINDOW_ROLE` in X11, telling apart the windows of an application.
+    #[serde(default)]
+    pub role: Option<String>,
 }
 
 impl Window {
@@ -87,9 +134,23 @@ impl Window {
             legacy_name: None,
             r#type: WindowType::Normal,
             tag: None,
+            extra_tags: vec![],
             border: 1,
             margin: Margins::new(10),
             margin_multiplier: 1.0,
+            size_weight: 1.0,
+            column_width: 50,
+            is_terminal: false,
+            no_swallow: false,
+            swallowed: None,
+            floating_placement: None,
+            floating_geometry: None,
+            insert_behavior: None,
+            border_width: None,
+            border_color: None,
+            skip_focus_cycle: false,
+            spawn_scratchpad: None,
+            activation_policy: None,
             states: vec![],
             normal: XyhwBuilder::default().into(),
             requested: None,
@@ -99,6 +160,7 @@ impl Window {
             strut: None,
             res_name: None,
             res_class: None,
+            role: None,
         }
     }
 
@@ -161,6 +223,18 @@ impl Window {
         self.states.contains(&WindowState::Sticky)
     }
 
+    #[must_use]
+    pub fn is_minimized(&self) -> bool {
+        self.states.contains(&WindowState::Hidden)
+    }
+
+    /// Whether the window is maximized in both directions, filling its workspace.
+    #[must_use]
+    pub fn is_maximized(&self) -> bool {
+        self.states.contains(&WindowState::MaximizedVert)
+            && self.states.contains(&WindowState::MaximizedHorz)
+    }
+
     #[must_use]
     pub fn must_float(&self) -> bool {
         self.must_float
@@ -219,12 +293,24 @@ impl Window {
         self.margin_multiplier
     }
 
+    /// Changes the size weight by the provided delta.
+    /// Result is sanitized, so the weight stays between 0.25 and 4.
+    pub fn change_size_weight(&mut self, delta: f32) {
+        self.size_weight = (self.size_weight + delta).clamp(0.25, 4.0);
+    }
+
+    /// Changes the column width by the provided delta.
+    /// Result is sanitized, so the width stays between 10 and 100 percent.
+    pub fn change_column_width(&mut self, delta: i8) {
+        self.column_width = (i16::from(self.column_width) + i16::from(delta)).clamp(10, 100) as u8;
+    }
+
     #[must_use]
     pub fn width(&self) -> i32 {
         let mut value;
         if self.is_fullscreen() {
             value = self.normal.w();
-        } else if self.floating() && self.floating.is_some() {
+        } else if self.floating() && self.floating.is_some() && !self.is_maximized() {
             let relative = self.normal + self.floating.unwrap_or_default();
             value = relative.w() - (self.border * 2);
         } else {
@@ -247,7 +333,7 @@ impl Window {
         let mut value;
         if self.is_fullscreen() {
             value = self.normal.h();
-        } else if self.floating() && self.floating.is_some() {
+        } else if self.floating() && self.floating.is_some() && !self.is_maximized() {
             let relative = self.normal + self.floating.unwrap_or_default();
             value = relative.h() - (self.border * 2);
         } else {
@@ -285,7 +371,7 @@ impl Window {
     pub fn x(&self) -> i32 {
         if self.is_fullscreen() {
             self.normal.x()
-        } else if self.floating() && self.floating.is_some() {
+        } else if self.floating() && self.floating.is_some() && !self.is_maximized() {
             let relative = self.normal + self.floating.unwrap_or_default();
             relative.x()
         } else {
@@ -297,7 +383,7 @@ impl Window {
     pub fn y(&self) -> i32 {
         if self.is_fullscreen() {
             self.normal.y()
-        } else if self.floating() && self.floating.is_some() {
+        } else if self.floating() && self.floating.is_some() && !self.is_maximized() {
             let relative = self.normal + self.floating.unwrap_or_default();
             relative.y()
         } else {
@@ -319,7 +405,7 @@ impl Window {
 
     #[must_use]
     pub fn exact_xyhw(&self) -> Xyhw {
-        if self.floating() && self.floating.is_some() {
+        if self.floating() && self.floating.is_some() && !self.is_maximized() {
             self.normal + self.floating.unwrap_or_default()
         } else {
             self.normal
@@ -337,11 +423,24 @@ impl Window {
 
     #[must_use]
     pub fn has_tag(&self, tag: &TagId) -> bool {
-        self.tag == Some(*tag)
+        self.tag == Some(*tag) || self.extra_tags.contains(tag)
+    }
+
+    /// All tags of the window, starting with `tag`.
+    #[must_use]
+    pub fn tags(&self) -> Vec<TagId> {
+        self.tag.iter().chain(&self.extra_tags).copied().collect()
+    }
+
+    /// Puts the window on the tag only, taking it off its other tags.
+    pub fn set_tag(&mut self, tag: Option<TagId>) {
+        self.extra_tags.clear();
+        self.tag = tag;
     }
 
     pub fn untag(&mut self) {
         self.tag = None;
+        self.extra_tags.clear();
     }
 
     #[must_use]
@@ -353,8 +452,8 @@ impl Window {
         self.set_floating(false);
 
         // We are reparenting.
-        if self.tag != workspace.tag {
-            self.tag = workspace.tag;
+        if !workspace.is_displaying(self) {
+            self.set_tag(workspace.tag);
             let mut offset = self.get_floating_offsets().unwrap_or_default();
             let mut start_loc = self.start_loc.unwrap_or_default();
             let x = offset.x() + self.normal.x();
@@ -373,6 +472,14 @@ impl Window {
     }
 }
 
+const fn default_size_weight() -> f32 {
+    1.0
+}
+
+const fn default_column_width() -> u8 {
+    50
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
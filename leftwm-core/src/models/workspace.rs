@@ -10,7 +10,11 @@ use std::fmt;
 pub struct Workspace {
     pub layout: Layout,
     pub main_width_percentage: u8,
-    pub tag: Option<TagId>, // TODO: Make this a list.
+    /// The tag whose layout arranges the windows of the workspace.
+    pub tag: Option<TagId>,
+    /// Tags whose windows are shown together with the ones of `tag`.
+    #[serde(default)]
+    pub extra_tags: Vec<TagId>,
     pub margin: Margins,
     pub margin_multiplier: f32,
     pub gutters: Vec<Gutter>,
@@ -19,12 +23,19 @@ pub struct Workspace {
     pub xyhw: Xyhw,
     xyhw_avoided: Xyhw,
     pub max_window_width: Option<Size>,
+    /// Height of a tab in the tab bar of the `Tabbed` and `Stacked` layouts.
+    #[serde(default = "default_tab_bar_height")]
+    pub tab_bar_height: i32,
     /// Output (monitor) the workspace is linked to.
     pub output: String,
     /// ID of workspace on output. Starts with 1.
     pub id: usize,
 }
 
+const fn default_tab_bar_height() -> i32 {
+    20
+}
+
 impl fmt::Debug for Workspace {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
@@ -32,7 +43,7 @@ impl fmt::Debug for Workspace {
             "Workspace {{ output: {:?}, id: {}, tags: {:?}, x: {}, y: {} }}",
             self.output,
             self.id,
-            self.tag,
+            self.tags(),
             self.xyhw.x(),
             self.xyhw.y()
         )
@@ -55,9 +66,10 @@ impl Workspace {
         id: usize,
     ) -> Self {
         Self {
-            layout,
             main_width_percentage: layout.main_width(),
+            layout,
             tag: None,
+            extra_tags: vec![],
             margin: Margins::new(10),
             margin_multiplier: 1.0,
             gutters: vec![],
@@ -79,6 +91,7 @@ impl Workspace {
             }
             .into(),
             max_window_width,
+            tab_bar_height: default_tab_bar_height(),
             output,
             id,
         }
@@ -87,6 +100,7 @@ impl Workspace {
     pub fn load_config(&mut self, config: &impl Config) {
         self.margin = config.workspace_margin().unwrap_or_else(|| Margins::new(0));
         self.gutters = self.get_gutters_for_theme(config);
+        self.tab_bar_height = config.tab_height();
     }
 
     pub fn get_gutters_for_theme(&mut self, config: &impl Config) -> Vec<Gutter> {
@@ -113,6 +127,13 @@ impl Workspace {
 
     pub fn show_tag(&mut self, tag: &TagId) {
         self.tag = Some(*tag);
+        self.extra_tags.clear();
+    }
+
+    /// All tags shown on the workspace, starting with `tag`.
+    #[must_use]
+    pub fn tags(&self) -> Vec<TagId> {
+        self.tag.iter().chain(&self.extra_tags).copied().collect()
     }
 
     #[must_use]
@@ -122,22 +143,41 @@ impl Workspace {
 
     #[must_use]
     pub fn has_tag(&self, tag: &TagId) -> bool {
-        self.tag == Some(*tag)
+        self.tag == Some(*tag) || self.extra_tags.contains(tag)
     }
 
     /// Returns true if the workspace is displays a given window.
     #[must_use]
     pub fn is_displaying(&self, window: &Window) -> bool {
-        if let Some(tag) = &window.tag {
-            return self.has_tag(tag);
-        }
-        false
+        window
+            .tag
+            .iter()
+            .chain(&window.extra_tags)
+            .any(|tag| self.has_tag(tag))
     }
 
     /// Returns true if the workspace is to update the locations info of this window.
     #[must_use]
     pub fn is_managed(&self, window: &Window) -> bool {
-        self.is_displaying(window) && window.is_managed()
+        self.is_displaying(window) && window.is_managed() && !window.is_minimized()
+    }
+
+    /// Returns true if the workspace arranges the window. A window whose tags are shown on
+    /// several workspaces belongs to the one showing its tag, or else to the first one showing
+    /// another of its tags.
+    #[must_use]
+    pub fn owns(&self, window: &Window, workspaces: &[Workspace]) -> bool {
+        let owner = window
+            .tag
+            .and_then(|tag| workspaces.iter().find(|ws| ws.has_tag(&tag)))
+            .or_else(|| workspaces.iter().find(|ws| ws.is_displaying(window)));
+        owner.map_or_else(|| self.is_displaying(window), |owner| owner == self)
+    }
+
+    /// Like `is_managed`, leaving out the windows arranged by another workspace.
+    #[must_use]
+    pub fn manages(&self, window: &Window, workspaces: &[Workspace]) -> bool {
+        self.owns(window, workspaces) && window.is_managed() && !window.is_minimized()
     }
 
     /// Returns the original x position of the workspace,
@@ -1,11 +1,13 @@
 //! Save and restore manager state.
 
 use crate::child_process::ChildID;
-use crate::config::{Config, InsertBehavior, ScratchPad};
-use crate::layouts::Layout;
+use crate::config::{
+    ActivationPolicy, Config, FloatingPlacement, InsertBehavior, ScratchPad, TagConfig,
+};
+use crate::layouts::{self, Layout};
 use crate::models::{
-    FocusManager, LayoutManager, Mode, ScratchPadName, Screen, Size, Tags, Window, WindowHandle,
-    WindowType, Workspace,
+    FocusManager, LayoutManager, Mode, ScratchPadName, Screen, Size, Tag, TagId, Tags, Window,
+    WindowHandle, WindowType, Workspace,
 };
 use crate::DisplayAction;
 use serde::{Deserialize, Serialize};
@@ -29,7 +31,13 @@ pub struct State {
     pub default_width: i32,
     pub default_height: i32,
     pub disable_tile_drag: bool,
+    pub reorder_tile_drag: bool,
+    /// Where the tiled window being dragged with `reorder_tile_drag` would be dropped.
+    #[serde(skip)]
+    pub tile_drop: Option<(i32, i32)>,
     pub insert_behavior: InsertBehavior,
+    pub floating_placement: FloatingPlacement,
+    pub activation_policy: ActivationPolicy,
     pub single_window_border: bool,
 }
 
@@ -37,12 +45,35 @@ impl State {
     pub(crate) fn new(config: &impl Config) -> Self {
         let layout_manager = LayoutManager::new(config);
         let mut tags = Tags::new();
-        config.create_list_of_tag_labels().iter().for_each(|label| {
-            tags.add_new(label.as_str(), Layout::default());
-        });
+        // With independent tags every output gets its own copy of the tags.
+        let mut outputs: Vec<String> = vec![];
+        if config.independent_tags() {
+            for workspace in config.workspaces().unwrap_or_default() {
+                if !outputs.contains(&workspace.output) {
+                    outputs.push(workspace.output);
+                }
+            }
+            if outputs.is_empty() {
+                tracing::warn!("Independent tags need the outputs to be listed under workspaces. All outputs share the tags.");
+            }
+        }
+        let labels = config.create_list_of_tag_labels();
+        for output in &outputs {
+            tags.add_new_for_output(&labels, Some(output));
+        }
+        if outputs.is_empty() {
+            tags.add_new_for_output(&labels, None);
+        }
         tags.add_new_hidden("NSP");
+        tags.add_new_hidden("SWALLOWED");
+
+        let mut actions = VecDeque::new();
+        if !outputs.is_empty() {
+            let names = tags.normal().iter().map(Tag::desktop_name).collect();
+            actions.push_back(DisplayAction::SetDesktopNames(names));
+        }
 
-        Self {
+        let mut state = Self {
             focus_manager: FocusManager::new(config),
             layout_manager,
             scratchpads: config.create_list_of_scratchpads(),
@@ -52,16 +83,22 @@ impl State {
             workspaces: Default::default(),
             mode: Default::default(),
             active_scratchpads: Default::default(),
-            actions: Default::default(),
+            actions,
             tags,
             max_window_width: config.max_window_width(),
             mousekey: config.mousekey(),
             default_width: config.default_width(),
             default_height: config.default_height(),
             disable_tile_drag: config.disable_tile_drag(),
+            reorder_tile_drag: config.reorder_tile_drag(),
+            tile_drop: None,
             insert_behavior: config.insert_behavior(),
+            floating_placement: config.floating_placement(),
+            activation_policy: config.activation_policy(),
             single_window_border: config.single_window_border(),
-        }
+        };
+        state.load_tag_configs(&config.tag_configs());
+        state
     }
 
     // Sorts the windows and puts them in order of importance.
@@ -111,8 +148,17 @@ impl State {
         ]
         .concat();
 
+        // Maximized windows are stacked above the other tiled windows, without changing their
+        // place in the layout.
+        let (maximized, level5): (Vec<WindowHandle>, Vec<WindowHandle>) =
+            level5.into_iter().partition(|h| {
+                self.windows
+                    .iter()
+                    .any(|w| &w.handle == h && w.is_maximized())
+            });
+
         let fullscreen: Vec<WindowHandle> = [level1, level2].concat();
-        let handles: Vec<WindowHandle> = [level3, level4, level5, level6].concat();
+        let handles: Vec<WindowHandle> = [level3, level4, maximized, level5, level6].concat();
         let act = DisplayAction::SetWindowOrder(fullscreen, handles);
         self.actions.push_back(act);
     }
@@ -143,7 +189,7 @@ impl State {
 
             windows_on_tag
                 .iter_mut()
-                .for_each(|w| w.border = border_width);
+                .for_each(|w| w.border = w.border_width.unwrap_or(border_width));
         }
     }
 
@@ -166,7 +212,7 @@ impl State {
                     None => w.calculated_xyhw().center(),
                 };
                 if let Some(ws) = workspaces.iter().find(|ws| ws.contains_point(x, y)) {
-                    w.tag = ws.tag;
+                    w.set_tag(ws.tag);
                 }
             });
     }
@@ -174,29 +220,102 @@ impl State {
     pub(crate) fn load_config(&mut self, config: &impl Config) {
         self.mousekey = config.mousekey();
         self.max_window_width = config.max_window_width();
+        layouts::register_configured(config.custom_layouts());
         for win in &mut self.windows {
             config.load_window(win);
         }
         for ws in &mut self.workspaces {
             ws.load_config(config);
         }
+        self.load_tag_configs(&config.tag_configs());
+        // The display server falls back to the configured labels.
+        let act = DisplayAction::SetDesktopNames(self.desktop_names());
+        self.actions.push_back(act);
     }
 
-    /// Apply saved state to a running manager.
-    pub fn restore_state(&mut self, old_state: &Self) {
-        // Restore tags.
+    /// Gives every tag the settings at its position in the list, counting the tags of each
+    /// output from the start. Tags whose settings changed start over with their layout, as do
+    /// the workspaces showing them.
+    pub(crate) fn load_tag_configs(&mut self, tag_configs: &[TagConfig]) {
+        let mut positions: HashMap<Option<String>, usize> = HashMap::new();
+        let mut reconfigured = vec![];
+        for tag in self.tags.all_mut().into_iter().filter(|tag| !tag.hidden) {
+            let position = positions.entry(tag.namespace.clone()).or_default();
+            let tag_config = tag_configs.get(*position).cloned().unwrap_or_default();
+            *position += 1;
+            if tag.config != tag_config {
+                tag.load_config(tag_config);
+                reconfigured.push(tag.id);
+            }
+        }
+        self.reload_workspace_layouts(&reconfigured);
+    }
+
+    /// Shows the layout of the tags on the workspaces they are shown on.
+    fn reload_workspace_layouts(&mut self, tags: &[TagId]) {
+        for workspace in &mut self.workspaces {
+            let Some(tag) = workspace.tag.filter(|tag| tags.contains(tag)) else {
+                continue;
+            };
+            if let Some(tag) = self.tags.get(tag) {
+                workspace.layout = tag.layout.clone();
+                workspace.main_width_percentage = tag.main_width_percentage;
+            }
+        }
+    }
+
+    /// The output a tag is on, if it belongs to one or is shown on a workspace.
+    fn output_of(&self, tag: TagId) -> Option<String> {
+        self.tags
+            .get(tag)
+            .and_then(|tag| tag.namespace.clone())
+            .or_else(|| {
+                let workspace = self.workspaces.iter().find(|ws| ws.has_tag(&tag));
+                workspace.map(|ws| ws.output.clone())
+            })
+    }
+
+    /// Restores the runtime settings of the tags and returns the tags whose settings changed,
+    /// which keep the layout of their new settings.
+    fn restore_tags(&mut self, old_state: &Self) -> Vec<TagId> {
+        let mut reconfigured = vec![];
         for old_tag in old_state.tags.all() {
-            if let Some(tag) = self.tags.get_mut(old_tag.id) {
+            let output = old_tag.namespace.as_deref();
+            let id = restored_tag(&self.tags, &old_state.tags, old_tag.id, output);
+            if let Some(tag) = id.and_then(|id| self.tags.get_mut(id)) {
                 tag.hidden = old_tag.hidden;
-                tag.layout = old_tag.layout;
-                tag.layout_rotation = old_tag.layout_rotation;
+                if tag.config == old_tag.config {
+                    tag.layout = old_tag.layout.clone();
+                    tag.layout_rotation = old_tag.layout_rotation;
+                    tag.main_width_percentage = old_tag.main_width_percentage;
+                } else {
+                    reconfigured.push(tag.id);
+                }
                 tag.flipped_vertical = old_tag.flipped_vertical;
                 tag.flipped_horizontal = old_tag.flipped_horizontal;
-                tag.main_width_percentage = old_tag.main_width_percentage;
+                tag.main_count = old_tag.main_count;
+                tag.split_tree = old_tag.split_tree.clone();
+                tag.viewport = old_tag.viewport.clone();
+                tag.active_tab = old_tag.active_tab;
+                tag.layout_pinned = old_tag.layout_pinned;
+                tag.minimized.clone_from(&old_tag.minimized);
             }
         }
+        reconfigured
+    }
 
-        let are_tags_equal = self.tags.all().eq(&old_state.tags.all());
+    /// Whether both states know the same tags. The settings of the tags do not matter.
+    fn has_same_tags(&self, other: &Self) -> bool {
+        let tag_key = |tag: &&Tag| (tag.id, tag.label.clone(), tag.hidden, tag.namespace.clone());
+        let tags = self.tags.all();
+        let other_tags = other.tags.all();
+        tags.iter().map(tag_key).eq(other_tags.iter().map(tag_key))
+    }
+
+    /// Apply saved state to a running manager.
+    pub fn restore_state(&mut self, old_state: &Self) {
+        let reconfigured = self.restore_tags(old_state);
+        let are_tags_equal = self.has_same_tags(old_state);
 
         // Restore windows.
         let mut ordered = vec![];
@@ -214,19 +333,32 @@ impl State {
                 new_window.set_floating(old_window.floating());
                 new_window.set_floating_offsets(old_window.get_floating_offsets());
                 new_window.apply_margin_multiplier(old_window.margin_multiplier);
+                new_window.size_weight = old_window.size_weight;
+                new_window.column_width = old_window.column_width;
+                new_window.swallowed = old_window.swallowed;
                 new_window.pid = old_window.pid;
                 new_window.normal = old_window.normal;
                 if are_tags_equal {
-                    new_window.tag = old_window.tag;
+                    new_window.set_tag(old_window.tag);
+                    new_window.extra_tags.clone_from(&old_window.extra_tags);
                 } else {
-                    let mut new_tag = old_window.tag;
-                    // Only retain the tag if it still exists, otherwise default to tag 1
-                    match new_tag {
-                        Some(tag) if self.tags.get(tag).is_some() => {}
-                        _ => new_tag = Some(1),
+                    // Only retain the tags which still exist on the output of the window,
+                    // otherwise default to the first tag of the output.
+                    let output = old_window.tag.and_then(|tag| old_state.output_of(tag));
+                    let output = output.as_deref();
+                    let restore =
+                        |tag: &TagId| restored_tag(&self.tags, &old_state.tags, *tag, output);
+                    let new_tag = old_window
+                        .tag
+                        .and_then(|tag| restore(&tag))
+                        .or_else(|| Some(first_tag(&self.tags, output)));
+                    new_window.set_tag(new_tag);
+                    new_window.extra_tags = vec![];
+                    for tag in old_window.extra_tags.iter().filter_map(restore) {
+                        if !new_window.has_tag(&tag) {
+                            new_window.extra_tags.push(tag);
+                        }
                     }
-                    new_window.untag();
-                    new_tag.iter().for_each(|&tag_id| new_window.tag(&tag_id));
                 }
                 new_window.strut = old_window.strut;
                 new_window.set_states(old_window.states());
@@ -234,7 +366,11 @@ impl State {
                 self.windows.remove(index);
 
                 // Make the x server aware of any tag changes for the window.
-                let act = DisplayAction::SetWindowTag(new_window.handle, new_window.tag);
+                let act = if new_window.extra_tags.is_empty() {
+                    DisplayAction::SetWindowTag(new_window.handle, new_window.tag)
+                } else {
+                    DisplayAction::SetWindowTags(new_window.handle, new_window.tags())
+                };
                 self.actions.push_back(act);
             }
         });
@@ -249,21 +385,21 @@ impl State {
         // Restore workspaces.
         for workspace in &mut self.workspaces {
             if let Some(old_workspace) = old_state.workspaces.iter().find(|&w| w == workspace) {
-                workspace.layout = old_workspace.layout;
+                workspace.layout = old_workspace.layout.clone();
                 workspace.main_width_percentage = old_workspace.main_width_percentage;
                 workspace.margin_multiplier = old_workspace.margin_multiplier;
                 if are_tags_equal {
                     workspace.tag = old_workspace.tag;
+                    workspace.extra_tags.clone_from(&old_workspace.extra_tags);
                 } else {
-                    let mut new_tag = old_workspace.tag;
-                    // Only retain the tag if it still exists, otherwise default to tag 1
-                    match new_tag {
-                        Some(tag) if all_tags.get(tag).is_some() => {}
-                        _ => new_tag = Some(1),
-                    }
-                    new_tag
-                        .iter()
-                        .for_each(|&tag_id| workspace.tag = Some(tag_id));
+                    // Only retain the tag if the output can still show it, otherwise default to
+                    // the first tag of the output.
+                    let output = Some(workspace.output.as_str());
+                    let new_tag = old_workspace
+                        .tag
+                        .and_then(|tag| restored_tag(all_tags, &old_state.tags, tag, output))
+                        .unwrap_or_else(|| first_tag(all_tags, output));
+                    workspace.tag = Some(new_tag);
                 }
             }
         }
@@ -275,15 +411,19 @@ impl State {
         }
 
         // Restore focus.
-        self.focus_manager.tags_last_window = old_state.focus_manager.tags_last_window.clone();
-        self.focus_manager
+        let restore = |tag: TagId| {
+            let output = old_state.output_of(tag);
+            restored_tag(all_tags, &old_state.tags, tag, output.as_deref())
+        };
+        self.focus_manager.tags_last_window = old_state
+            .focus_manager
             .tags_last_window
-            .retain(|&id, _| all_tags.get(id).is_some());
+            .iter()
+            .filter_map(|(&tag, handle)| Some((restore(tag)?, *handle)))
+            .collect();
         let tag_id = match old_state.focus_manager.tag(0) {
-            // If the tag still exists it should be displayed on a workspace.
-            Some(tag_id) if self.tags.get(tag_id).is_some() => tag_id,
-            // If the tag doesn't exist, tag 1 should be displayed on a workspace.
-            Some(_) => 1,
+            // If the tag still exists it should be displayed on a workspace, otherwise tag 1.
+            Some(tag_id) => restore(tag_id).unwrap_or(1),
             // If we don't have any tag history (We should), focus the tag on workspace 1.
             None => match self.workspaces.first() {
                 Some(ws) => ws.tag.unwrap_or(1),
@@ -291,6 +431,7 @@ impl State {
                 _ => 1,
             },
         };
+        self.reload_workspace_layouts(&reconfigured);
         self.focus_tag(&tag_id);
     }
 }
@@ -323,3 +464,211 @@ where
     windows.fold((), extend(f, &mut handles, &mut left, &mut right));
     (handles, left, right)
 }
+
+/// The tag taking the place of a tag of the old state on the output.
+///
+/// Tags added, removed or moved at runtime change the IDs of the other tags, so tags are found
+/// by label among the tags of the output, the second tag with a label taking the place of the
+/// second old tag with it. A tag which was renamed keeps its ID, as long as no old tag has the
+/// label of the tag with that ID. Hidden tags keep their ID.
+fn restored_tag(
+    tags: &Tags,
+    old_tags: &Tags,
+    old_tag: TagId,
+    output: Option<&str>,
+) -> Option<TagId> {
+    let old = old_tags.get(old_tag)?;
+    if old.hidden {
+        return tags.get(old_tag).filter(|tag| tag.hidden).map(|tag| tag.id);
+    }
+    let fits = |tag: &&Tag| output.map_or(true, |output| tag.belongs_to(output));
+    let old_tags: Vec<&Tag> = old_tags.normal().iter().filter(fits).collect();
+    let tags: Vec<&Tag> = tags.normal().iter().filter(fits).collect();
+    let nth = old_tags
+        .iter()
+        .take_while(|tag| tag.id != old_tag)
+        .filter(|tag| tag.label == old.label)
+        .count();
+    tags.iter()
+        .filter(|tag| tag.label == old.label)
+        .nth(nth)
+        .or_else(|| {
+            tags.iter()
+                .find(|tag| tag.id == old_tag && old_tags.iter().all(|old| old.label != tag.label))
+        })
+        .map(|tag| tag.id)
+}
+
+/// The first tag the output can show.
+fn first_tag(tags: &Tags, output: Option<&str>) -> TagId {
+    tags.normal()
+        .iter()
+        .find(|tag| output.map_or(true, |output| tag.belongs_to(output)))
+        .map_or(1, |tag| tag.id)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::tests::TestConfig;
+    use crate::config::TagConfig;
+    use crate::display_servers::MockDisplayServer;
+    use crate::layouts::Layout;
+    use crate::models::{Screen, Window, WindowHandle};
+    use crate::Manager;
+
+    #[test]
+    fn restoring_the_state_keeps_the_tags_of_windows() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut old = Manager::new_test(tags.clone());
+        old.screen_create_handler(Screen::default());
+        old.window_created_handler(Window::new(WindowHandle::MockHandle(1), None, None), -1, -1);
+        assert!(old
+            .state
+            .set_window_tags(&WindowHandle::MockHandle(1), &[1, 3]));
+
+        let mut manager = Manager::new_test(tags);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.restore_state(&old.state);
+        assert_eq!(manager.state.windows[0].tags(), vec![1, 3]);
+
+        // Tags which are gone are dropped.
+        let mut manager = Manager::new_test(vec!["1".to_string(), "2".to_string()]);
+        manager.screen_create_handler(Screen::default());
+        manager.window_created_handler(
+            Window::new(WindowHandle::MockHandle(1), None, None),
+            -1,
+            -1,
+        );
+        manager.state.restore_state(&old.state);
+        assert_eq!(manager.state.windows[0].tags(), vec![1]);
+    }
+
+    #[test]
+    fn restoring_the_state_applies_changed_tag_settings() {
+        let tags = ["1", "2", "3"].map(String::from).to_vec();
+        let mut old = Manager::new_test(tags.clone());
+        old.screen_create_handler(Screen::default());
+        for id in [1, 2] {
+            old.state
+                .tags
+                .get_mut(id)
+                .unwrap()
+                .set_layout(Layout::Fibonacci, 60);
+        }
+        old.state.workspaces[0].layout = Layout::Fibonacci;
+        old.state.workspaces[0].extra_tags = vec![2];
+
+        let config = TestConfig {
+            tags,
+            tag_configs: vec![TagConfig {
+                layout: Some(Layout::Monocle),
+                ..TagConfig::default()
+            }],
+            ..TestConfig::default()
+        };
+        let mut manager: Manager<_, MockDisplayServer> = Manager::new(config);
+        manager.screen_create_handler(Screen::default());
+        manager.state.restore_state(&old.state);
+
+        // The tag with new settings starts over with their layout, the others keep theirs.
+        assert_eq!(manager.state.tags.get(1).unwrap().layout, Layout::Monocle);
+        assert_eq!(manager.state.workspaces[0].layout, Layout::Monocle);
+        let tag = manager.state.tags.get(2).unwrap();
+        assert_eq!(tag.layout, Layout::Fibonacci);
+        assert_eq!(tag.main_width_percentage, 60);
+        assert_eq!(manager.state.workspaces[0].extra_tags, vec![2]);
+    }
+
+    #[test]
+    fn restoring_the_state_moves_windows_to_the_tags_of_their_output() {
+        let outputs = ["A", "B"];
+        let config = |independent_tags| TestConfig {
+            tags: ["1", "2", "3"].map(String::from).to_vec(),
+            independent_tags,
+            workspaces: Some(
+                outputs
+                    .map(|output| crate::config::Workspace {
+                        output: output.to_string(),
+                        ..Default::default()
+                    })
+                    .to_vec(),
+            ),
+            ..TestConfig::default()
+        };
+        let manager = |independent_tags| {
+            let mut manager: Manager<_, MockDisplayServer> = Manager::new(config(independent_tags));
+            for output in outputs {
+                manager.screen_create_handler(Screen {
+                    output: output.to_string(),
+                    ..Screen::default()
+                });
+            }
+            manager.window_created_handler(
+                Window::new(WindowHandle::MockHandle(1), None, None),
+                -1,
+                -1,
+            );
+            manager
+        };
+
+        // Tag 2 on output B becomes the second tag of B.
+        let mut old = manager(false);
+        old.state.windows[0].set_tag(Some(2));
+        let mut new = manager(true);
+        new.state.restore_state(&old.state);
+        assert_eq!(new.state.windows[0].tag, Some(5));
+        assert_eq!(new.state.workspaces[1].tag, Some(5));
+
+        // And back again.
+        let mut old = manager(false);
+        old.state.restore_state(&new.state);
+        assert_eq!(old.state.windows[0].tag, Some(2));
+        assert_eq!(old.state.workspaces[1].tag, Some(2));
+    }
+
+    #[test]
+    fn restoring_the_state_finds_tags_changed_at_runtime_by_label() {
+        let tags = ["1", "2", "3", "4"].map(String::from).to_vec();
+        let manager = || {
+            let mut manager = Manager::new_test(tags.clone());
+            manager.screen_create_handler(Screen::default());
+            for i in 1..=4 {
+                manager.window_created_handler(
+                    Window::new(WindowHandle::MockHandle(i), None, None),
+                    -1,
+                    -1,
+                );
+            }
+            manager
+        };
+        let mut old = manager();
+        for (window, tag) in old.state.windows.iter_mut().zip(1..) {
+            window.set_tag(Some(tag));
+        }
+        old.state.tags.get_mut(3).unwrap().main_count = 2;
+        assert!(old.state.remove_tag(2, None));
+        assert!(old.state.move_tag(3, 1));
+        assert_eq!(old.state.desktop_names(), vec!["4", "1", "3"]);
+
+        let mut new = manager();
+        new.state.restore_state(&old.state);
+        let window_tags = |manager: &Manager<_, _>| -> Vec<Option<usize>> {
+            manager.state.windows.iter().map(|w| w.tag).collect()
+        };
+        assert_eq!(window_tags(&new), vec![Some(1), Some(1), Some(3), Some(4)]);
+        assert_eq!(new.state.tags.get(3).unwrap().main_count, 2);
+        assert_eq!(new.state.tags.get(2).unwrap().main_count, 1);
+
+        // A renamed tag keeps its windows.
+        let mut old = new;
+        assert!(old.state.rename_tag(3, "web"));
+        let mut new = manager();
+        new.state.restore_state(&old.state);
+        assert_eq!(window_tags(&new), vec![Some(1), Some(1), Some(3), Some(4)]);
+    }
+}
@@ -1,6 +1,6 @@
 //! Creates a pipe to listen for external commands.
 use crate::layouts::Layout;
-use crate::models::TagId;
+use crate::models::{Direction, SnapPosition, SplitAxis, TagId};
 use crate::{Command, ReleaseScratchPadOption};
 use std::env;
 use std::path::{Path, PathBuf};
@@ -106,10 +106,12 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         "MoveWindowToNextWorkspace" => Ok(Command::MoveWindowToNextWorkspace),
         "MoveWindowToPreviousWorkspace" => Ok(Command::MoveWindowToPreviousWorkspace),
         "SendWindowToTag" => build_send_window_to_tag(rest),
+        "MoveWindowDirection" => build_move_window_direction(rest),
         // Focus Navigation
         "FocusWindowDown" => Ok(Command::FocusWindowDown),
         "FocusWindowTop" => build_focus_window_top(rest),
         "FocusWindowUp" => Ok(Command::FocusWindowUp),
+        "FocusWindowDirection" => build_focus_window_direction(rest),
         "FocusNextTag" => Ok(Command::FocusNextTag),
         "FocusPreviousTag" => Ok(Command::FocusPreviousTag),
         "FocusWorkspaceNext" => Ok(Command::FocusWorkspaceNext),
@@ -117,10 +119,26 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         // Layout
         "DecreaseMainWidth" => build_decrease_main_width(rest),
         "IncreaseMainWidth" => build_increase_main_width(rest),
+        "DecreaseMainCount" => Ok(Command::DecreaseMainCount),
+        "IncreaseMainCount" => Ok(Command::IncreaseMainCount),
+        "DecreaseWindowWeight" => build_decrease_window_weight(rest),
+        "IncreaseWindowWeight" => build_increase_window_weight(rest),
+        "PreselectSplit" => build_preselect_split(rest),
+        "SetSplitRatio" => build_set_split_ratio(rest),
+        "RotateSplitTree" => Ok(Command::RotateSplitTree),
+        "BalanceSplitTree" => Ok(Command::BalanceSplitTree),
+        "FlipSplitTree" => build_flip_split_tree(rest),
+        "ScrollLeft" => Ok(Command::ScrollLeft),
+        "ScrollRight" => Ok(Command::ScrollRight),
+        "MoveColumnLeft" => Ok(Command::MoveColumnLeft),
+        "MoveColumnRight" => Ok(Command::MoveColumnRight),
+        "IncreaseColumnWidth" => build_increase_column_width(rest),
+        "DecreaseColumnWidth" => build_decrease_column_width(rest),
         "NextLayout" => Ok(Command::NextLayout),
         "PreviousLayout" => Ok(Command::PreviousLayout),
         "RotateTag" => Ok(Command::RotateTag),
         "SetLayout" => build_set_layout(rest),
+        "ResetLayout" => Ok(Command::ResetLayout),
         "SetMarginMultiplier" => build_set_margin_multiplier(rest),
         // Scratchpad
         "ToggleScratchPad" => build_toggle_scratchpad(rest),
@@ -136,13 +154,29 @@ fn parse_command(s: &str) -> Result<Command, Box<dyn std::error::Error>> {
         "FloatingToTile" => Ok(Command::FloatingToTile),
         "TileToFloating" => Ok(Command::TileToFloating),
         "ToggleFloating" => Ok(Command::ToggleFloating),
+        "MoveFloatingBy" => build_move_floating_by(rest),
+        "ResizeFloatingBy" => build_resize_floating_by(rest),
+        "CenterFloating" => Ok(Command::CenterFloating),
+        "SnapFloating" => build_snap_floating(rest),
         // Workspace/Tag
         "GoToTag" => build_go_to_tag(rest),
         "ReturnToLastTag" => Ok(Command::ReturnToLastTag),
+        "ToggleWindowTag" => Ok(Command::ToggleWindowTag(build_tag_id(rest)?)),
+        "SetWindowTags" => build_set_window_tags(rest),
+        "ToggleTagView" => Ok(Command::ToggleTagView(build_tag_id(rest)?)),
+        "ViewOnlyTag" => Ok(Command::ViewOnlyTag(build_tag_id(rest)?)),
+        "AddTag" => build_add_tag(rest),
+        "RemoveTag" => build_remove_tag(rest),
+        "RenameTag" => build_rename_tag(rest),
+        "MoveTag" => build_move_tag(rest),
         "SendWorkspaceToTag" => build_send_workspace_to_tag(rest),
         "SwapScreens" => Ok(Command::SwapScreens),
         "ToggleFullScreen" => Ok(Command::ToggleFullScreen),
         "ToggleSticky" => Ok(Command::ToggleSticky),
+        "ToggleMaximize" => Ok(Command::ToggleMaximize),
+        "MinimizeWindow" => Ok(Command::MinimizeWindow),
+        "RestoreLastMinimized" => Ok(Command::RestoreLastMinimized),
+        "RestoreWindow" => build_restore_window(rest),
         // General
         "CloseWindow" => Ok(Command::CloseWindow),
         "CloseAllOtherWindows" => Ok(Command::CloseAllOtherWindows),
@@ -191,6 +225,13 @@ fn build_toggle_scratchpad(raw: &str) -> Result<Command, Box<dyn std::error::Err
     Ok(Command::ToggleScratchPad(name.into()))
 }
 
+fn build_restore_window(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument window index or class".into());
+    }
+    Ok(Command::RestoreWindow(raw.to_owned()))
+}
+
 fn build_go_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let headless = without_head(raw, "GoToTag ");
     let mut parts = headless.split(' ');
@@ -199,6 +240,62 @@ fn build_go_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     Ok(Command::GoToTag { tag, swap })
 }
 
+fn build_tag_id(raw: &str) -> Result<TagId, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument tag_id".into());
+    }
+    Ok(TagId::from_str(raw)?)
+}
+
+fn build_set_window_tags(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let tags = raw
+        .split_whitespace()
+        .map(TagId::from_str)
+        .collect::<Result<Vec<TagId>, _>>()?;
+    if tags.is_empty() {
+        return Err("missing argument tag_ids".into());
+    }
+    Ok(Command::SetWindowTags(tags))
+}
+
+fn build_add_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument label".into());
+    }
+    Ok(Command::AddTag(raw.to_owned()))
+}
+
+fn build_remove_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    if raw.is_empty() {
+        return Err("missing argument tag_id".into());
+    }
+    let mut parts = raw.split(' ');
+    let tag: TagId = parts
+        .next()
+        .expect("split() always returns an array of at least 1 element")
+        .parse()?;
+    let target = parts.next().map(TagId::from_str).transpose()?;
+    Ok(Command::RemoveTag { tag, target })
+}
+
+fn build_rename_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let (tag, label) = raw.split_once(' ').ok_or("missing argument label")?;
+    if label.is_empty() {
+        return Err("missing argument label".into());
+    }
+    Ok(Command::RenameTag {
+        tag: tag.parse()?,
+        label: label.to_owned(),
+    })
+}
+
+fn build_move_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let mut parts = raw.split(' ');
+    let tag: TagId = parts.next().ok_or("missing argument tag_id")?.parse()?;
+    let position: usize = parts.next().ok_or("missing argument position")?.parse()?;
+    Ok(Command::MoveTag { tag, position })
+}
+
 fn build_send_window_to_tag(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
     let tag_id = if raw.is_empty() {
         return Err("missing argument tag_id".into());
@@ -301,6 +398,116 @@ fn build_decrease_main_width(raw: &str) -> Result<Command, Box<dyn std::error::E
     Ok(Command::DecreaseMainWidth(change))
 }
 
+fn build_increase_window_weight(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    Ok(Command::IncreaseWindowWeight(parse_weight_change(raw)?))
+}
+
+fn build_decrease_window_weight(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    Ok(Command::DecreaseWindowWeight(parse_weight_change(raw)?))
+}
+
+fn parse_weight_change(raw: &str) -> Result<f32, Box<dyn std::error::Error>> {
+    let change = if raw.is_empty() {
+        return Err("missing argument change".into());
+    } else {
+        f32::from_str(raw)?
+    };
+    if !change.is_finite() {
+        return Err("argument change must be a finite number".into());
+    }
+    Ok(change)
+}
+
+fn build_preselect_split(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let direction = if raw.is_empty() {
+        return Err("missing argument direction".into());
+    } else {
+        Direction::from_str(raw)?
+    };
+    Ok(Command::PreselectSplit(direction))
+}
+
+fn build_focus_window_direction(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let direction = if raw.is_empty() {
+        return Err("missing argument direction".into());
+    } else {
+        Direction::from_str(raw)?
+    };
+    Ok(Command::FocusWindowDirection(direction))
+}
+
+fn build_move_window_direction(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let direction = if raw.is_empty() {
+        return Err("missing argument direction".into());
+    } else {
+        Direction::from_str(raw)?
+    };
+    Ok(Command::MoveWindowDirection(direction))
+}
+
+fn build_set_split_ratio(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let percent = if raw.is_empty() {
+        return Err("missing argument ratio".into());
+    } else {
+        u8::from_str(raw)?
+    };
+    Ok(Command::SetSplitRatio(percent))
+}
+
+fn build_flip_split_tree(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let axis = if raw.is_empty() {
+        return Err("missing argument axis".into());
+    } else {
+        SplitAxis::from_str(raw)?
+    };
+    Ok(Command::FlipSplitTree(axis))
+}
+
+fn build_increase_column_width(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let change = if raw.is_empty() {
+        return Err("missing argument change".into());
+    } else {
+        i8::from_str(raw)?
+    };
+    Ok(Command::IncreaseColumnWidth(change))
+}
+
+fn build_decrease_column_width(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let change = if raw.is_empty() {
+        return Err("missing argument change".into());
+    } else {
+        i8::from_str(raw)?
+    };
+    Ok(Command::DecreaseColumnWidth(change))
+}
+
+fn build_move_floating_by(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let (dx, dy) = parse_offsets(raw)?;
+    Ok(Command::MoveFloatingBy(dx, dy))
+}
+
+fn build_resize_floating_by(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let (dw, dh) = parse_offsets(raw)?;
+    Ok(Command::ResizeFloatingBy(dw, dh))
+}
+
+fn build_snap_floating(raw: &str) -> Result<Command, Box<dyn std::error::Error>> {
+    let position = if raw.is_empty() {
+        return Err("missing argument position".into());
+    } else {
+        SnapPosition::from_str(raw)?
+    };
+    Ok(Command::SnapFloating(position))
+}
+
+/// Parses two offsets separated by whitespace, eg. "-10 20".
+fn parse_offsets(raw: &str) -> Result<(i32, i32), Box<dyn std::error::Error>> {
+    let mut parts = raw.split_whitespace();
+    let first = parts.next().ok_or("missing argument horizontal offset")?;
+    let second = parts.next().ok_or("missing argument vertical offset")?;
+    Ok((first.parse()?, second.parse()?))
+}
+
 fn without_head<'a>(s: &'a str, head: &'a str) -> &'a str {
     if !s.starts_with(head) {
         return s;
@@ -390,6 +597,16 @@ mod test {
         assert!(build_toggle_scratchpad("").is_err());
     }
 
+    #[test]
+    fn build_tag_commands_without_parameter() {
+        assert!(build_tag_id("").is_err());
+        assert!(build_set_window_tags("").is_err());
+        assert!(build_add_tag("").is_err());
+        assert!(build_remove_tag("").is_err());
+        assert!(build_rename_tag("2").is_err());
+        assert!(build_move_tag("2").is_err());
+    }
+
     #[test]
     fn build_send_window_to_tag_without_parameter() {
         assert!(build_send_window_to_tag("").is_err());
@@ -400,6 +617,11 @@ mod test {
         assert!(build_send_workspace_to_tag("").is_err());
     }
 
+    #[test]
+    fn build_restore_window_without_parameter() {
+        assert!(build_restore_window("").is_err());
+    }
+
     #[test]
     fn build_set_layout_without_parameter() {
         assert!(build_set_layout("").is_err());
@@ -410,6 +632,72 @@ mod test {
         assert!(build_set_margin_multiplier("").is_err());
     }
 
+    #[test]
+    fn build_increase_window_weight_without_parameter() {
+        assert!(build_increase_window_weight("").is_err());
+    }
+
+    #[test]
+    fn build_decrease_window_weight_without_parameter() {
+        assert!(build_decrease_window_weight("").is_err());
+    }
+
+    #[test]
+    fn build_window_weight_with_non_finite_parameter() {
+        for raw in ["NaN", "inf", "-inf"] {
+            assert!(build_increase_window_weight(raw).is_err());
+            assert!(build_decrease_window_weight(raw).is_err());
+        }
+        assert!(build_increase_window_weight("0.5").is_ok());
+    }
+
+    #[test]
+    fn build_preselect_split_without_parameter() {
+        assert!(build_preselect_split("").is_err());
+    }
+
+    #[test]
+    fn build_window_direction_without_parameter() {
+        assert!(build_focus_window_direction("").is_err());
+        assert!(build_move_window_direction("").is_err());
+    }
+
+    #[test]
+    fn build_floating_commands_without_parameter() {
+        assert!(build_move_floating_by("").is_err());
+        assert!(build_resize_floating_by("10").is_err());
+        assert!(build_snap_floating("").is_err());
+        assert_eq!(
+            build_move_floating_by("-10 20").unwrap(),
+            Command::MoveFloatingBy(-10, 20)
+        );
+    }
+
+    #[test]
+    fn build_set_split_ratio_without_parameter() {
+        assert!(build_set_split_ratio("").is_err());
+    }
+
+    #[test]
+    fn build_flip_split_tree_parses_the_axis() {
+        assert!(build_flip_split_tree("").is_err());
+        assert!(build_flip_split_tree("Diagonal").is_err());
+        assert_eq!(
+            build_flip_split_tree("Rows").unwrap(),
+            Command::FlipSplitTree(SplitAxis::Rows)
+        );
+    }
+
+    #[test]
+    fn build_increase_column_width_without_parameter() {
+        assert!(build_increase_column_width("").is_err());
+    }
+
+    #[test]
+    fn build_decrease_column_width_without_parameter() {
+        assert!(build_decrease_column_width("").is_err());
+    }
+
     #[test]
     fn build_move_window_top_without_parameter() {
         assert_eq!(
@@ -1,5 +1,6 @@
 use crate::config::Config;
 use crate::display_servers::DisplayServer;
+use crate::layouts::Layout;
 use crate::models::Manager;
 
 impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
@@ -14,11 +15,39 @@ impl<C: Config, SERVER: DisplayServer> Manager<C, SERVER> {
             .iter_mut()
             .for_each(|w| w.set_visible(w.tag.is_none()));
 
+        self.state.layout_manager.apply_rules(
+            &mut self.state.workspaces,
+            &mut self.state.tags,
+            &self.state.windows,
+        );
+
+        let focused = self
+            .state
+            .focus_manager
+            .window(&self.state.windows)
+            .map(|w| w.handle);
         for ws in &self.state.workspaces {
+            if let Some(tag) = ws.tag.and_then(|tag_id| self.state.tags.get_mut(tag_id)) {
+                if tag.layout == Layout::Bsp {
+                    tag.update_split_tree(&self.state.windows, focused, ws, &self.state.workspaces);
+                } else if tag.layout == Layout::Scrolling {
+                    let columns = tag.columns(&self.state.windows, ws, &self.state.workspaces);
+                    tag.viewport.follow(&columns, focused);
+                } else if tag.layout.is_tabbed() {
+                    let shows_focused = self.state.windows.iter().any(|w| {
+                        Some(w.handle) == focused
+                            && ws.manages(w, &self.state.workspaces)
+                            && !w.floating()
+                    });
+                    if shows_focused {
+                        tag.active_tab = focused;
+                    }
+                }
+            }
             let windows = &mut self.state.windows;
             let all_tags = &self.state.tags;
             if let Some(Some(tag)) = ws.tag.map(|tag_id| all_tags.get(tag_id)) {
-                tag.update_windows(windows, ws);
+                tag.update_windows(windows, ws, &self.state.workspaces);
             }
         }
     }
@@ -52,6 +52,9 @@ fn print_commandlist() {
         SoftReload
         ToggleFullScreen
         ToggleSticky
+        ToggleMaximize
+        MinimizeWindow
+        RestoreLastMinimized
         SwapScreens
         MoveWindowToNextTag
         MoveWindowToPreviousTag
@@ -61,6 +64,7 @@ fn print_commandlist() {
         FloatingToTile
         TileToFloating
         ToggleFloating
+        CenterFloating
         MoveWindowUp
         MoveWindowDown
         MoveWindowTop
@@ -73,7 +77,16 @@ fn print_commandlist() {
         FocusWorkspacePrevious
         NextLayout
         PreviousLayout
+        ResetLayout
         RotateTag
+        IncreaseMainCount
+        DecreaseMainCount
+        RotateSplitTree
+        BalanceSplitTree
+        ScrollLeft
+        ScrollRight
+        MoveColumnLeft
+        MoveColumnRight
         ReturnToLastTag
         CloseWindow
 
@@ -90,9 +103,31 @@ fn print_commandlist() {
         ToggleScratchPad       Args: <ScratchpadName>
         SendWorkspaceToTag     Args: <workspaxe_index> <tag_index> (int)
         SendWindowToTag        Args: <tag_index> (int)
+        ToggleTagView          Args: <tag_index> (int)
+        ToggleWindowTag        Args: <tag_index> (int)
+        SetWindowTags          Args: <tag_index> [tag_index ...] (int)
+        ViewOnlyTag            Args: <tag_index> (int)
+        AddTag                 Args: <label>
+        RemoveTag              Args: <tag_index> [target_tag_index] (int)
+        RenameTag              Args: <tag_index> (int) <label>
+        MoveTag                Args: <tag_index> <position> (int)
+            Note: changes made by `AddTag`, `RemoveTag`, `RenameTag` and `MoveTag` are dropped on reload
         SetLayout              Args: <LayoutName>
         SetMarginMultiplier    Args: <multiplier-value> (float)
+        IncreaseWindowWeight   Args: <weight-change> (float)
+        DecreaseWindowWeight   Args: <weight-change> (float)
+        PreselectSplit         Args: <Left|Right|Up|Down>
+        FocusWindowDirection   Args: <Left|Right|Up|Down>
+        MoveWindowDirection    Args: <Left|Right|Up|Down>
+        SetSplitRatio          Args: <percentage> (int)
+        FlipSplitTree          Args: <Columns|Rows>
+        MoveFloatingBy         Args: <x-offset> <y-offset> (int)
+        ResizeFloatingBy       Args: <width-change> <height-change> (int)
+        SnapFloating           Args: <TopLeft|Top|TopRight|Left|Right|BottomLeft|Bottom|BottomRight>
+        IncreaseColumnWidth    Args: <width-change> (int)
+        DecreaseColumnWidth    Args: <width-change> (int)
         FocusWindow            Args: <WindowClass> or <visible-window-index> (int)
+        RestoreWindow          Args: <WindowClass> or <minimized-window-index> (int)
 
         For more information please visit:
         https://github.com/leftwm/leftwm/wiki/External-Commands
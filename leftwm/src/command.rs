@@ -25,24 +25,42 @@ pub enum BaseCommand {
     ToggleScratchPad,
     ToggleFullScreen,
     ToggleSticky,
+    ToggleMaximize,
+    MinimizeWindow,
+    RestoreLastMinimized,
+    RestoreWindow,
     GotoTag,
     ReturnToLastTag,
+    ToggleTagView,
+    ViewOnlyTag,
+    AddTag,
+    RemoveTag,
+    RenameTag,
+    MoveTag,
     FloatingToTile,
     TileToFloating,
     ToggleFloating,
+    MoveFloatingBy,
+    ResizeFloatingBy,
+    CenterFloating,
+    SnapFloating,
     MoveWindowUp,
     MoveWindowDown,
     MoveWindowTop,
     SwapWindowTop,
+    MoveWindowDirection,
     FocusNextTag,
     FocusPreviousTag,
     FocusWindow,
     FocusWindowUp,
     FocusWindowDown,
     FocusWindowTop,
+    FocusWindowDirection,
     FocusWorkspaceNext,
     FocusWorkspacePrevious,
     MoveToTag,
+    ToggleWindowTag,
+    SetWindowTags,
     MoveWindowToNextTag,
     MoveWindowToPreviousTag,
     MoveToLastWorkspace,
@@ -51,9 +69,25 @@ pub enum BaseCommand {
     NextLayout,
     PreviousLayout,
     SetLayout,
+    ResetLayout,
     RotateTag,
     IncreaseMainWidth,
     DecreaseMainWidth,
+    IncreaseMainCount,
+    DecreaseMainCount,
+    IncreaseWindowWeight,
+    DecreaseWindowWeight,
+    PreselectSplit,
+    SetSplitRatio,
+    RotateSplitTree,
+    BalanceSplitTree,
+    FlipSplitTree,
+    ScrollLeft,
+    ScrollRight,
+    MoveColumnLeft,
+    MoveColumnRight,
+    IncreaseColumnWidth,
+    DecreaseColumnWidth,
     SetMarginMultiplier,
     // Custom commands
     UnloadTheme,
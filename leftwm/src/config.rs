@@ -13,9 +13,15 @@ use super::ThemeSetting;
 use crate::config::keybind::Keybind;
 use anyhow::Result;
 use leftwm_core::{
-    config::{InsertBehavior, ScratchPad, Workspace},
-    layouts::{Layout, LAYOUTS},
-    models::{FocusBehaviour, Gutter, LayoutMode, Margins, Size, Window, WindowState, WindowType},
+    config::{
+        ActivationPolicy, FloatingGeometry, FloatingPlacement, InsertBehavior, LayoutRule,
+        ScratchPad, TagConfig, Workspace,
+    },
+    layouts::{CustomLayout, Layout, LAYOUTS},
+    models::{
+        FocusBehaviour, Gutter, LayoutMode, Margins, ScratchPadName, Size, Window, WindowState,
+        WindowType,
+    },
     state::State,
     DisplayAction, DisplayServer, Manager,
 };
@@ -25,10 +31,12 @@ use ron::{
     ser::{to_string_pretty, PrettyConfig},
     Options,
 };
+use serde::de::{self, value::MapAccessDeserializer, MapAccess, Visitor};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::convert::TryInto;
 use std::default::Default;
 use std::env;
+use std::fmt;
 use std::fs;
 use std::fs::File;
 use std::io::prelude::Write;
@@ -38,6 +46,85 @@ use xdg::BaseDirectories;
 /// Path to file where state will be dumped upon soft reload.
 const STATE_FILE: &str = "/tmp/leftwm.state";
 
+/// Declares a struct with the conditions a window is matched by, followed by its own fields.
+///
+/// The conditions are not a `#[serde(flatten)]` field as RON only reads and writes flattened
+/// structs as maps, which would break the `(window_class: ..)` syntax of the rules.
+macro_rules! with_window_conditions {
+    ($(#[$attr:meta])* pub struct $name:ident { $($fields:tt)* }) => {
+        $(#[$attr])*
+        pub struct $name {
+            // Use serde default field attribute to fallback to None option in case of missing field in
+            // config. Without this attribute deserializer will fail on missing field due to it's inability
+            // to treat missing value as Option::None
+            /// `WM_CLASS` in X11
+            #[serde(
+                default,
+                deserialize_with = "from_regex",
+                serialize_with = "to_config_string"
+            )]
+            pub window_class: Option<Regex>,
+            /// `_NET_WM_NAME` in X11
+            #[serde(
+                default,
+                deserialize_with = "from_regex",
+                serialize_with = "to_config_string"
+            )]
+            pub window_title: Option<Regex>,
+            /// The instance part of `WM_CLASS` in X11
+            #[serde(
+                default,
+                deserialize_with = "from_regex",
+                serialize_with = "to_config_string"
+            )]
+            pub window_instance: Option<Regex>,
+            /// `WM_WINDOW_ROLE` in X11
+            #[serde(
+                default,
+                deserialize_with = "from_regex",
+                serialize_with = "to_config_string"
+            )]
+            pub window_role: Option<Regex>,
+            /// `_NET_WM_WINDOW_TYPE` in X11
+            pub window_type: Option<WindowType>,
+            /// The command line of the process owning the window, the arguments separated by spaces
+            #[serde(
+                default,
+                deserialize_with = "from_regex",
+                serialize_with = "to_config_string"
+            )]
+            pub window_command: Option<Regex>,
+            /// Whether the window belongs to another window, like dialogs do
+            pub is_transient: Option<bool>,
+            /// Every one of these conditions has to match as well
+            pub all: Option<Vec<WindowMatch>>,
+            /// At least one of these conditions has to match as well
+            pub any: Option<Vec<WindowMatch>>,
+            /// This condition must not match
+            pub not: Option<Box<WindowMatch>>,
+            $($fields)*
+        }
+
+        impl $name {
+            fn conditions(&self) -> Conditions<'_> {
+                Conditions {
+                    class: self.window_class.as_ref(),
+                    title: self.window_title.as_ref(),
+                    instance: self.window_instance.as_ref(),
+                    role: self.window_role.as_ref(),
+                    r#type: self.window_type.as_ref(),
+                    command: self.window_command.as_ref(),
+                    transient: self.is_transient,
+                    all: self.all.as_deref().unwrap_or_default(),
+                    any: self.any.as_deref().unwrap_or_default(),
+                    not: self.not.as_deref(),
+                }
+            }
+        }
+    };
+}
+
+with_window_conditions! {
 /// Selecting by `WM_CLASS` and/or window title, allow the user to define if a
 /// window should spawn on a specified tag and/or its floating state.
 ///
@@ -64,24 +151,13 @@ const STATE_FILE: &str = "/tmp/leftwm.state";
 /// windows whose `WM_CLASS` is "krita" will spawn on tag 3 (1-indexed) and not floating.
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct WindowHook {
-    // Use serde default field attribute to fallback to None option in case of missing field in
-    // config. Without this attribute deserializer will fail on missing field due to it's inability
-    // to treat missing value as Option::None
-    /// `WM_CLASS` in X11
-    #[serde(
-        default,
-        deserialize_with = "from_regex",
-        serialize_with = "to_config_string"
-    )]
-    pub window_class: Option<Regex>,
-    /// `_NET_WM_NAME` in X11
-    #[serde(
-        default,
-        deserialize_with = "from_regex",
-        serialize_with = "to_config_string"
-    )]
-    pub window_title: Option<Regex>,
+    /// Rules with a higher priority win over better matching rules with a lower priority
+    pub priority: Option<i32>,
+    /// Apply the rule again once the title or class of the window change to match it
+    pub reapply_on_change: Option<bool>,
     pub spawn_on_tag: Option<usize>,
+    /// Put the window on all of these tags, the first of which arranges it
+    pub spawn_on_tags: Option<Vec<usize>>,
     pub spawn_on_workspace: Option<String>,
     pub spawn_on_workspace_id: Option<usize>,
     pub spawn_floating: Option<bool>,
@@ -89,34 +165,155 @@ pub struct WindowHook {
     pub spawn_fullscreen: Option<bool>,
     /// Handle the window as if it was of this `_NET_WM_WINDOW_TYPE`
     pub spawn_as_type: Option<WindowType>,
+    /// Windows launched from this terminal take its place until they are closed
+    pub is_terminal: Option<bool>,
+    /// Never take the place of the terminal the window was launched from
+    pub no_swallow: Option<bool>,
+    /// Where the window is put when it spawns floating, overrides `floating_placement`
+    pub floating_placement: Option<FloatingPlacement>,
+    /// Size and position of the window when it floats, relative to its workspace
+    pub floating_geometry: Option<FloatingGeometry>,
+    /// Overrides `border_width` for this window
+    pub border_width: Option<i32>,
+    /// Colour of the border while the window is not focused
+    pub border_color: Option<String>,
+    /// Never give the input focus to the window
+    pub never_focus: Option<bool>,
+    /// Attach the window to the scratchpad of this name
+    pub spawn_on_scratchpad: Option<ScratchPadName>,
+    /// Where the window goes in the stack, `Top` being the main slot, overrides `insert_behavior`
+    pub insert_behavior: Option<InsertBehavior>,
+    /// Leave the window out when cycling the focus with `FocusWindowUp` and `FocusWindowDown`
+    pub skip_focus_cycle: Option<bool>,
+    /// What happens when the window asks to be activated, overrides `activation_policy`
+    pub activation_policy: Option<ActivationPolicy>,
+}
 }
 
-impl WindowHook {
-    /// Score the similarity between a [`leftwm_core::models::Window`] and a [`WindowHook`].
+with_window_conditions! {
+/// Conditions on a window, combined by the `all`, `any` and `not` fields of a [`WindowHook`].
+/// The fields match the same way as the ones of the rule.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct WindowMatch {}
+}
+
+/// The conditions of a [`WindowHook`] or a [`WindowMatch`].
+struct Conditions<'a> {
+    class: Option<&'a Regex>,
+    title: Option<&'a Regex>,
+    instance: Option<&'a Regex>,
+    role: Option<&'a Regex>,
+    r#type: Option<&'a WindowType>,
+    command: Option<&'a Regex>,
+    transient: Option<bool>,
+    all: &'a [WindowMatch],
+    any: &'a [WindowMatch],
+    not: Option<&'a WindowMatch>,
+}
+
+impl Conditions<'_> {
+    /// Score the similarity between a [`leftwm_core::models::Window`] and the conditions, 0 if
+    /// they do not match.
     ///
-    /// Multiple [`WindowHook`]s might match a `WM_CLASS` but we want the most
-    /// specific one to apply: matches by title are scored greater than by `WM_CLASS`.
-    fn score_window(&self, window: &Window) -> u8 {
+    /// Matching either the `WM_CLASS` or the title is enough, all the other conditions which are
+    /// set have to match. Matches by title or role are scored greater than the other ones, so the
+    /// most specific rule applies.
+    fn score(&self, window: &Window) -> u32 {
         // returns true if any of the items in the provided `Vec<&Option<String>>` is Some and matches the `&Regex`
         let matches_any = |re: &Regex, strs: Vec<&Option<String>>| {
             strs.iter()
                 .any(|str| str.as_ref().map_or(false, |s| re.replace(s, "") == ""))
         };
 
-        let class_score = self.window_class.as_ref().map_or(0, |re| {
-            u8::from(matches_any(re, vec![&window.res_class, &window.res_name]))
+        let class_score = self.class.map_or(0, |re| {
+            u32::from(matches_any(re, vec![&window.res_class, &window.res_name]))
         });
 
-        let title_score = self.window_title.as_ref().map_or(0, |re| {
-            u8::from(matches_any(re, vec![&window.legacy_name, &window.name]))
+        let title_score = self.title.map_or(0, |re| {
+            u32::from(matches_any(re, vec![&window.legacy_name, &window.name]))
         });
 
-        class_score + 2 * title_score
+        let mut score = class_score + 2 * title_score;
+        if (self.class.is_some() || self.title.is_some()) && score == 0 {
+            return 0;
+        }
+
+        let required = [
+            self.instance
+                .map(|re| (matches_any(re, vec![&window.res_name]), 1)),
+            self.role.map(|re| (matches_any(re, vec![&window.role]), 2)),
+            self.r#type.map(|r#type| (&window.r#type == r#type, 1)),
+            self.command
+                .map(|re| (matches_any(re, vec![&command_line(window.pid)]), 1)),
+            self.transient
+                .map(|transient| (window.transient.is_some() == transient, 1)),
+        ];
+        for (matches, weight) in required.into_iter().flatten() {
+            if !matches {
+                return 0;
+            }
+            score += weight;
+        }
+
+        if !self.all.is_empty() {
+            let scores: Vec<u32> = self
+                .all
+                .iter()
+                .map(|m| m.conditions().score(window))
+                .collect();
+            if scores.contains(&0) {
+                return 0;
+            }
+            score += scores.iter().sum::<u32>();
+        }
+        if !self.any.is_empty() {
+            let best = self.any.iter().map(|m| m.conditions().score(window)).max();
+            match best {
+                Some(best) if best > 0 => score += best,
+                _ => return 0,
+            }
+        }
+        if let Some(not) = self.not {
+            if not.conditions().score(window) > 0 {
+                return 0;
+            }
+            score += 1;
+        }
+        score
+    }
+}
+
+/// The command line of a process, its arguments separated by spaces.
+fn command_line(pid: Option<u32>) -> Option<String> {
+    let cmdline = fs::read(format!("/proc/{}/cmdline", pid?)).ok()?;
+    let args: Vec<String> = cmdline
+        .split(|b| *b == 0)
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| String::from_utf8_lossy(arg).into_owned())
+        .collect();
+    Some(args.join(" "))
+}
+
+impl WindowHook {
+    /// Score the similarity between a [`leftwm_core::models::Window`] and a [`WindowHook`].
+    ///
+    /// Multiple [`WindowHook`]s might match a `WM_CLASS` but we want the most
+    /// specific one to apply: matches by title are scored greater than by `WM_CLASS`.
+    fn score_window(&self, window: &Window) -> u32 {
+        self.conditions().score(window)
     }
 
     fn apply(&self, state: &mut State, window: &mut Window) {
         if let Some(tag) = self.spawn_on_tag {
-            window.tag = Some(tag);
+            window.set_tag(Some(tag));
+        }
+        if let Some((tag, others)) = self
+            .spawn_on_tags
+            .as_deref()
+            .and_then(<[usize]>::split_first)
+        {
+            window.set_tag(Some(*tag));
+            window.extra_tags = others.iter().filter(|t| *t != tag).copied().collect();
         }
         if self.spawn_on_workspace.is_some() {
             if let Some(workspace) = state.workspaces.iter().find(|ws| {
@@ -132,9 +329,8 @@ impl WindowHook {
                         None => 1.0,
                     };
 
-                    window.untag();
                     window.set_floating(self.spawn_floating.unwrap_or_default());
-                    window.tag(&tag);
+                    window.set_tag(Some(tag));
                     window.apply_margin_multiplier(margin_multiplier);
                     let act = DisplayAction::SetWindowTag(window.handle, Some(tag));
                     state.actions.push_back(act);
@@ -162,6 +358,88 @@ impl WindowHook {
         if let Some(w_type) = self.spawn_as_type.clone() {
             window.r#type = w_type;
         }
+        if let Some(is_terminal) = self.is_terminal {
+            window.is_terminal = is_terminal;
+        }
+        if let Some(no_swallow) = self.no_swallow {
+            window.no_swallow = no_swallow;
+        }
+        if let Some(placement) = self.floating_placement {
+            window.floating_placement = Some(placement);
+        }
+        if let Some(geometry) = self.floating_geometry {
+            window.floating_geometry = Some(geometry);
+        }
+        if let Some(width) = self.border_width {
+            window.border_width = Some(width);
+        }
+        if let Some(color) = &self.border_color {
+            window.border_color = Some(color.clone());
+        }
+        if let Some(never_focus) = self.never_focus {
+            window.never_focus = never_focus;
+        }
+        if let Some(scratchpad) = &self.spawn_on_scratchpad {
+            window.spawn_scratchpad = Some(scratchpad.clone());
+        }
+        if let Some(insert_behavior) = self.insert_behavior {
+            window.insert_behavior = Some(insert_behavior);
+        }
+        if let Some(skip) = self.skip_focus_cycle {
+            window.skip_focus_cycle = skip;
+        }
+        if let Some(policy) = self.activation_policy {
+            window.activation_policy = Some(policy);
+        }
+    }
+}
+
+/// A tag given by its label alone, or by a block of settings.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum TagSetting {
+    Label(String),
+    Config(TagConfig),
+}
+
+// Deserialized by hand, as an untagged enum would not let RON read the layouts and other enums
+// inside of the settings.
+impl<'de> Deserialize<'de> for TagSetting {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct TagSettingVisitor;
+
+        impl<'de> Visitor<'de> for TagSettingVisitor {
+            type Value = TagSetting;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a tag label or a block of tag settings")
+            }
+
+            fn visit_str<E: de::Error>(self, label: &str) -> Result<Self::Value, E> {
+                Ok(TagSetting::Label(label.to_string()))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Self::Value, A::Error> {
+                TagConfig::deserialize(MapAccessDeserializer::new(map)).map(TagSetting::Config)
+            }
+        }
+
+        deserializer.deserialize_any(TagSettingVisitor)
+    }
+}
+
+impl TagSetting {
+    pub fn config(&self) -> TagConfig {
+        match self {
+            Self::Label(label) => TagConfig {
+                label: label.clone(),
+                ..TagConfig::default()
+            },
+            Self::Config(config) => config.clone(),
+        }
     }
 }
 
@@ -173,16 +451,22 @@ pub struct Config {
     pub modkey: String,
     pub mousekey: Option<Modifier>,
     pub workspaces: Option<Vec<Workspace>>,
-    pub tags: Option<Vec<String>>,
+    pub tags: Option<Vec<TagSetting>>,
+    pub independent_tags: bool,
     pub max_window_width: Option<Size>,
     pub layouts: Vec<Layout>,
+    pub custom_layouts: Vec<CustomLayout>,
+    pub layout_rules: Vec<LayoutRule>,
     pub layout_mode: LayoutMode,
     pub insert_behavior: InsertBehavior,
+    pub floating_placement: FloatingPlacement,
+    pub activation_policy: ActivationPolicy,
     pub scratchpad: Option<Vec<ScratchPad>>,
     pub window_rules: Option<Vec<WindowHook>>,
     // If you are on tag "1" and you goto tag "1" this takes you to the previous tag
     pub disable_current_tag_swap: bool,
     pub disable_tile_drag: bool,
+    pub reorder_tile_drag: bool,
     pub disable_window_snap: bool,
     pub focus_behaviour: FocusBehaviour,
     pub focus_new_windows: bool,
@@ -373,12 +657,26 @@ impl lefthk_core::config::Config for Config {
 
 impl leftwm_core::Config for Config {
     fn create_list_of_tag_labels(&self) -> Vec<String> {
+        self.tag_configs()
+            .into_iter()
+            .map(|config| config.label)
+            .collect()
+    }
+
+    fn tag_configs(&self) -> Vec<TagConfig> {
         if let Some(tags) = &self.tags {
-            return tags.clone();
+            return tags.iter().map(TagSetting::config).collect();
         }
         Self::default()
             .tags
             .expect("we created it in the Default impl; qed")
+            .iter()
+            .map(TagSetting::config)
+            .collect()
+    }
+
+    fn independent_tags(&self) -> bool {
+        self.independent_tags
     }
 
     fn workspaces(&self) -> Option<Vec<Workspace>> {
@@ -408,6 +706,14 @@ impl leftwm_core::Config for Config {
         self.layouts.clone()
     }
 
+    fn custom_layouts(&self) -> Vec<CustomLayout> {
+        self.custom_layouts.clone()
+    }
+
+    fn layout_rules(&self) -> Vec<LayoutRule> {
+        self.layout_rules.clone()
+    }
+
     fn layout_mode(&self) -> LayoutMode {
         self.layout_mode
     }
@@ -416,6 +722,14 @@ impl leftwm_core::Config for Config {
         self.insert_behavior
     }
 
+    fn floating_placement(&self) -> FloatingPlacement {
+        self.floating_placement
+    }
+
+    fn activation_policy(&self) -> ActivationPolicy {
+        self.activation_policy
+    }
+
     fn single_window_border(&self) -> bool {
         self.single_window_border
     }
@@ -509,6 +823,31 @@ impl leftwm_core::Config for Config {
             .unwrap_or_else(|| "#333333".to_string())
     }
 
+    fn tab_height(&self) -> i32 {
+        self.theme_setting.tab_height.unwrap_or(20)
+    }
+
+    fn tab_color(&self) -> String {
+        self.theme_setting
+            .tab_color
+            .clone()
+            .unwrap_or_else(|| "#222222".to_string())
+    }
+
+    fn tab_focused_color(&self) -> String {
+        self.theme_setting
+            .tab_focused_color
+            .clone()
+            .unwrap_or_else(|| "#005577".to_string())
+    }
+
+    fn tab_text_color(&self) -> String {
+        self.theme_setting
+            .tab_text_color
+            .clone()
+            .unwrap_or_else(|| "#EEEEEE".to_string())
+    }
+
     fn disable_window_snap(&self) -> bool {
         self.disable_window_snap
     }
@@ -548,6 +887,10 @@ impl leftwm_core::Config for Config {
         self.disable_tile_drag
     }
 
+    fn reorder_tile_drag(&self) -> bool {
+        self.reorder_tile_drag
+    }
+
     fn save_state(&self, state: &State) {
         let path = self.state_file();
         let state_file = match File::create(path) {
@@ -581,24 +924,17 @@ impl leftwm_core::Config for Config {
 
     /// Pick the best matching [`WindowHook`], if any, and apply its config.
     fn setup_predefined_window(&self, state: &mut State, window: &mut Window) -> bool {
-        if let Some(window_rules) = &self.window_rules {
-            let best_match = window_rules
-                .iter()
-                // map first instead of using max_by_key directly...
-                .map(|wh| (wh, wh.score_window(window)))
-                // ...since this filter is required (0 := non-match)
-                .filter(|(_wh, score)| score != &0)
-                .max_by_key(|(_wh, score)| *score);
-            if let Some((hook, _)) = best_match {
+        if self.window_rules.is_some() {
+            if let Some(hook) = self.best_window_rule(window, |_| true) {
                 hook.apply(state, window);
                 tracing::debug!(
-                    "Window [[ TITLE={:?}, {:?}; WM_CLASS={:?}, {:?} ]] spawned in tag={:?} on workspace={:?} as type={:?} with floating={:?}, sticky={:?} and fullscreen={:?}",
+                    "Window [[ TITLE={:?}, {:?}; WM_CLASS={:?}, {:?} ]] spawned in tags={:?} on workspace={:?} as type={:?} with floating={:?}, sticky={:?} and fullscreen={:?}",
                     window.name,
                     window.legacy_name,
                     window.res_name,
                     window.res_class,
                     hook.spawn_as_type,
-                    hook.spawn_on_tag,
+                    window.tags(),
                     hook.spawn_on_workspace,
                     hook.spawn_floating,
                     hook.spawn_sticky,
@@ -611,6 +947,28 @@ impl leftwm_core::Config for Config {
         false
     }
 
+    /// Apply the best matching [`WindowHook`] with `reapply_on_change` once it starts to match.
+    fn reapply_predefined_window(
+        &self,
+        state: &mut State,
+        window: &mut Window,
+        previous: &Window,
+    ) -> bool {
+        let Some(hook) = self.reapplied_window_rule(window, previous) else {
+            return false;
+        };
+        hook.apply(state, window);
+        tracing::debug!(
+            "Window [[ TITLE={:?}; WM_CLASS={:?}, {:?} ]] changed and moved to tags={:?} with floating={:?}",
+            window.name,
+            window.res_name,
+            window.res_class,
+            window.tags(),
+            hook.spawn_floating,
+        );
+        true
+    }
+
     fn sloppy_mouse_follows_focus(&self) -> bool {
         self.sloppy_mouse_follows_focus
     }
@@ -621,6 +979,38 @@ impl leftwm_core::Config for Config {
 }
 
 impl Config {
+    /// The window rule with the highest priority, and among those the best matching one, out of
+    /// the rules passing `filter`.
+    fn best_window_rule(
+        &self,
+        window: &Window,
+        filter: impl Fn(&WindowHook) -> bool,
+    ) -> Option<&WindowHook> {
+        self.window_rules
+            .as_ref()?
+            .iter()
+            .filter(|wh| filter(wh))
+            // map first instead of using max_by_key directly...
+            .map(|wh| (wh, wh.score_window(window)))
+            // ...since this filter is required (0 := non-match)
+            .filter(|(_wh, score)| score != &0)
+            .max_by_key(|(wh, score)| (wh.priority.unwrap_or_default(), *score))
+            .map(|(wh, _)| wh)
+    }
+
+    /// The best matching rule with `reapply_on_change`, if it did not match the window before the
+    /// change already. Applying the rule on every change of the title would keep undoing what
+    /// the user did with the window.
+    fn reapplied_window_rule(&self, window: &Window, previous: &Window) -> Option<&WindowHook> {
+        let reapplied = |wh: &WindowHook| wh.reapply_on_change == Some(true);
+        let hook = self.best_window_rule(window, reapplied)?;
+        let matched = self.best_window_rule(previous, reapplied);
+        match matched {
+            Some(matched) if std::ptr::eq(matched, hook) => None,
+            _ => Some(hook),
+        }
+    }
+
     #[cfg(feature = "lefthk")]
     pub fn clear_keybinds(&mut self) {
         self.keybind.clear();
@@ -667,4 +1057,132 @@ mod tests {
         let ron_config = ron::from_str::<'_, Config>(ron.unwrap().as_str());
         assert!(ron_config.is_ok(), "Could not deserialize default config");
     }
+
+    #[test]
+    fn custom_layouts_deserialize_from_ron() {
+        let ron = Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+        let config: Config = ron
+            .from_str(
+                r#"(
+                    layouts: [MainAndVertStack, Custom("MainDeck")],
+                    custom_layouts: [
+                        (name: "MainDeck", main_width: 60, max_rows: 3, overflow: Deck),
+                    ],
+                )"#,
+            )
+            .unwrap();
+
+        assert_eq!(config.layouts[1], Layout::Custom("MainDeck".to_string()));
+        assert_eq!(config.custom_layouts[0].max_rows, Some(3));
+        assert!(config.is_custom_layout("MainDeck"));
+    }
+
+    #[test]
+    fn tags_deserialize_from_labels_and_settings() {
+        use leftwm_core::Config as _;
+
+        let ron = Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+        let config: Config = ron
+            .from_str(
+                r#"(
+                    tags: [
+                        "1",
+                        (label: "web", layouts: [Monocle, MainAndDeck], main_width: 60),
+                        (label: "chat", insert_behavior: Top, output: "HDMI-1"),
+                    ],
+                )"#,
+            )
+            .unwrap();
+        let tag_configs = config.tag_configs();
+
+        assert_eq!(config.create_list_of_tag_labels(), vec!["1", "web", "chat"]);
+        assert_eq!(
+            tag_configs[1].layouts,
+            Some(vec![Layout::Monocle, Layout::MainAndDeck])
+        );
+        assert_eq!(tag_configs[1].main_width, Some(60));
+        assert_eq!(tag_configs[2].insert_behavior, Some(InsertBehavior::Top));
+        assert_eq!(tag_configs[2].output.as_deref(), Some("HDMI-1"));
+    }
+
+    #[test]
+    fn window_rules_match_with_combinators_and_priority() {
+        use leftwm_core::models::WindowHandle;
+
+        let ron = Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+        let config: Config = ron
+            .from_str(
+                r#"(
+                    window_rules: [
+                        (window_class: "firefox", spawn_on_tag: 2),
+                        (
+                            all: [(window_class: "firefox"), (window_role: "PictureInPicture")],
+                            spawn_sticky: true,
+                        ),
+                        (window_type: Dialog, not: (window_class: "firefox"), priority: 1),
+                        (window_instance: "Navigator", is_transient: true, spawn_on_tag: 3),
+                    ],
+                )"#,
+            )
+            .unwrap();
+        let rules = config.window_rules.as_ref().unwrap();
+        let window = |class: &str, role: Option<&str>| {
+            let mut window = Window::new(WindowHandle::MockHandle(1), None, None);
+            window.res_class = Some(class.to_string());
+            window.res_name = Some("Navigator".to_string());
+            window.role = role.map(str::to_string);
+            window
+        };
+        let best = |window: &Window| {
+            let best = config.best_window_rule(window, |_| true);
+            best.and_then(|hook| rules.iter().position(|r| std::ptr::eq(r, hook)))
+        };
+
+        assert_eq!(best(&window("firefox", None)), Some(0));
+        assert_eq!(best(&window("firefox", Some("PictureInPicture"))), Some(1));
+        assert_eq!(best(&window("mpv", Some("PictureInPicture"))), None);
+
+        let mut dialog = window("gimp", None);
+        dialog.r#type = WindowType::Dialog;
+        dialog.transient = Some(WindowHandle::MockHandle(2));
+        // The dialog rule wins over the better matching one through its priority.
+        assert_eq!(best(&dialog), Some(2));
+        dialog.res_class = Some("firefox".to_string());
+        assert_eq!(best(&dialog), Some(3));
+    }
+
+    #[test]
+    fn reapplied_window_rules_apply_once_they_start_to_match() {
+        use leftwm_core::models::WindowHandle;
+
+        let ron = Options::default().with_default_extension(Extensions::IMPLICIT_SOME);
+        let config: Config = ron
+            .from_str(
+                r#"(
+                    window_rules: [
+                        (window_class: "Spotify", spawn_on_tag: 4, reapply_on_change: true),
+                        (window_title: "Spotify", spawn_on_tag: 5),
+                    ],
+                )"#,
+            )
+            .unwrap();
+        let rules = config.window_rules.as_ref().unwrap();
+        let previous = Window::new(
+            WindowHandle::MockHandle(1),
+            Some("Spotify".to_string()),
+            None,
+        );
+        let mut window = previous.clone();
+        window.res_class = Some("Spotify".to_string());
+
+        let reapplied = |window: &Window, previous: &Window| {
+            let hook = config.reapplied_window_rule(window, previous);
+            hook.and_then(|hook| rules.iter().position(|r| std::ptr::eq(r, hook)))
+        };
+
+        assert_eq!(reapplied(&window, &previous), Some(0));
+        // Only rules with `reapply_on_change` run, and only once.
+        assert_eq!(reapplied(&window, &window), None);
+        assert_eq!(reapplied(&previous, &previous), None);
+    }
 }
@@ -1,6 +1,7 @@
 use super::Config;
 #[cfg(feature = "lefthk")]
 use lefthk_core::xkeysym_lookup;
+use leftwm_core::layouts::Layout;
 #[cfg(feature = "lefthk")]
 use std::collections::HashSet;
 
@@ -23,6 +24,62 @@ impl Config {
         }
     }
 
+    /// Check that the custom layouts are well formed and that every `Custom` layout referenced
+    /// in `layouts`, `workspaces` or `layout_rules` is defined.
+    pub fn check_layouts(&self, verbose: bool) {
+        println!("\x1b[0;94m::\x1b[0m Checking layouts . . .");
+        let mut returns = Vec::new();
+        for (i, custom_layout) in self.custom_layouts.iter().enumerate() {
+            if verbose {
+                println!("Custom layout: {custom_layout:?}");
+            }
+            returns.append(&mut custom_layout.validate());
+            if self.custom_layouts[..i]
+                .iter()
+                .any(|other| other.name == custom_layout.name)
+            {
+                returns.push(format!(
+                    "Custom layout `{}` is defined more than once",
+                    custom_layout.name
+                ));
+            }
+        }
+
+        let workspace_layouts = self
+            .workspaces
+            .iter()
+            .flatten()
+            .filter_map(|ws| ws.layouts.as_ref())
+            .flatten();
+        let rule_layouts = self.layout_rules.iter().map(|rule| &rule.layout);
+        for layout in self
+            .layouts
+            .iter()
+            .chain(workspace_layouts)
+            .chain(rule_layouts)
+        {
+            if let Layout::Custom(name) = layout {
+                if !self.is_custom_layout(name) {
+                    returns.push(format!(
+                        "Layout `{name}` is not defined in `custom_layouts`"
+                    ));
+                }
+            }
+        }
+
+        if returns.is_empty() {
+            println!("\x1b[0;92m    -> All layouts OK\x1b[0m");
+        } else {
+            for error in returns {
+                println!("\x1b[1;91mERROR: {error} \x1b[0m");
+            }
+        }
+    }
+
+    pub fn is_custom_layout(&self, name: &str) -> bool {
+        self.custom_layouts.iter().any(|layout| layout.name == name)
+    }
+
     /// Check all keybinds to ensure that required values are provided
     /// Checks to see if value is provided (if required)
     /// Checks to see if keys are valid against Xkeysym
@@ -2,7 +2,7 @@ use leftwm_core::models::{ScratchPad, Size};
 
 #[cfg(feature = "lefthk")]
 use super::{default_terminal, exit_strategy, BaseCommand, Keybind};
-use super::{Config, Default, FocusBehaviour, LayoutMode, ThemeSetting, LAYOUTS};
+use super::{Config, Default, FocusBehaviour, LayoutMode, TagSetting, ThemeSetting, LAYOUTS};
 
 impl Default for Config {
     // We allow this because this function would be difficult to reduce. If someone would like to
@@ -198,7 +198,7 @@ impl Default for Config {
 
         let tags = vec!["1", "2", "3", "4", "5", "6", "7", "8", "9"]
             .iter()
-            .map(|s| (*s).to_string())
+            .map(|s| TagSetting::Label((*s).to_string()))
             .collect();
 
         let scratchpad = ScratchPad {
@@ -213,7 +213,10 @@ impl Default for Config {
         Self {
             workspaces: Some(vec![]),
             tags: Some(tags),
+            independent_tags: false,
             layouts: LAYOUTS.to_vec(),
+            custom_layouts: vec![],
+            layout_rules: vec![],
             layout_mode: LayoutMode::Tag,
             // TODO: add sane default for scratchpad config.
             // Currently default values are set in sane_dimension fn.
@@ -221,11 +224,14 @@ impl Default for Config {
             window_rules: Some(vec![]),
             disable_current_tag_swap: false,
             disable_tile_drag: false,
+            reorder_tile_drag: false,
             disable_window_snap: true,
             focus_behaviour: FocusBehaviour::Sloppy, // default behaviour: mouse move auto-focuses window
             focus_new_windows: true, // default behaviour: focuses windows on creation
             single_window_border: true,
             insert_behavior: leftwm_core::config::InsertBehavior::Bottom,
+            floating_placement: leftwm_core::config::FloatingPlacement::Center,
+            activation_policy: leftwm_core::config::ActivationPolicy::Urgent,
             modkey: "Mod4".to_owned(),     //win key
             mousekey: Some("Mod4".into()), //win key
             #[cfg(feature = "lefthk")]
@@ -6,6 +6,8 @@ use crate::Config;
 use anyhow::{ensure, Context, Result};
 #[cfg(feature = "lefthk")]
 use leftwm_core::layouts::Layout;
+#[cfg(feature = "lefthk")]
+use leftwm_core::models::{Direction, SnapPosition, SplitAxis};
 use serde::{Deserialize, Serialize};
 #[cfg(feature = "lefthk")]
 use std::fmt::Write;
@@ -50,19 +52,60 @@ impl Keybind {
                     "Value should be empty, a window number or a valid scratchpad name"
                 );
             }
+            BaseCommand::RestoreWindow | BaseCommand::AddTag => {
+                ensure!(value_is_some, "value must not be empty");
+            }
+            BaseCommand::RemoveTag => {
+                let ids: Vec<&str> = self.value.split_whitespace().collect();
+                ensure!(
+                    (1..=2).contains(&ids.len()) && ids.iter().all(|id| usize::from_str(id).is_ok()),
+                    "Value should be a tag index, optionally followed by the index of the tag to move its windows to"
+                );
+            }
+            BaseCommand::RenameTag => {
+                let (id, label) = self.value.split_once(' ').unwrap_or_default();
+                usize::from_str(id).context("invalid index value for RenameTag")?;
+                ensure!(
+                    !label.is_empty(),
+                    "Value should be a tag index followed by a label"
+                );
+            }
+            BaseCommand::MoveTag => {
+                let ids: Vec<&str> = self.value.split_whitespace().collect();
+                ensure!(
+                    ids.len() == 2 && ids.iter().all(|id| usize::from_str(id).is_ok()),
+                    "Value should be a tag index followed by its new position"
+                );
+            }
             BaseCommand::GotoTag => {
                 usize::from_str(&self.value).context("invalid index value for GotoTag")?;
             }
+            BaseCommand::ToggleTagView => {
+                usize::from_str(&self.value).context("invalid index value for ToggleTagView")?;
+            }
+            BaseCommand::ViewOnlyTag => {
+                usize::from_str(&self.value).context("invalid index value for ViewOnlyTag")?;
+            }
             BaseCommand::FocusWindowTop if value_is_some => {
                 bool::from_str(&self.value).context("invalid boolean value for FocusWindowTop")?;
             }
             BaseCommand::SwapWindowTop if value_is_some => {
                 bool::from_str(&self.value).context("invalid boolean value for SwapWindowTop")?;
             }
+            BaseCommand::ToggleWindowTag => {
+                usize::from_str(&self.value).context("invalid index value for ToggleWindowTag")?;
+            }
+            BaseCommand::SetWindowTags => {
+                let ids: Vec<&str> = self.value.split_whitespace().collect();
+                ensure!(
+                    !ids.is_empty() && ids.iter().all(|id| usize::from_str(id).is_ok()),
+                    "Value should be one or more tag indexes, like \"1 3\""
+                );
+            }
             BaseCommand::MoveToTag => {
                 usize::from_str(&self.value).context("invalid index value for SendWindowToTag")?;
             }
-            BaseCommand::SetLayout => {
+            BaseCommand::SetLayout if !config.is_custom_layout(&self.value) => {
                 Layout::from_str(&self.value)
                     .context("could not parse layout for command SetLayout")?;
             }
@@ -72,6 +115,49 @@ impl Keybind {
             BaseCommand::DecreaseMainWidth => {
                 i8::from_str(&self.value).context("invalid width value for DecreaseMainWidth")?;
             }
+            BaseCommand::IncreaseWindowWeight => {
+                let change = f32::from_str(&self.value)
+                    .context("invalid weight value for IncreaseWindowWeight")?;
+                ensure!(change.is_finite(), "Weight value should be a finite number");
+            }
+            BaseCommand::DecreaseWindowWeight => {
+                let change = f32::from_str(&self.value)
+                    .context("invalid weight value for DecreaseWindowWeight")?;
+                ensure!(change.is_finite(), "Weight value should be a finite number");
+            }
+            BaseCommand::PreselectSplit => {
+                Direction::from_str(&self.value).context("invalid direction for PreselectSplit")?;
+            }
+            BaseCommand::FocusWindowDirection => {
+                Direction::from_str(&self.value)
+                    .context("invalid direction for FocusWindowDirection")?;
+            }
+            BaseCommand::MoveWindowDirection => {
+                Direction::from_str(&self.value)
+                    .context("invalid direction for MoveWindowDirection")?;
+            }
+            BaseCommand::MoveFloatingBy | BaseCommand::ResizeFloatingBy => {
+                let offsets: Vec<&str> = self.value.split_whitespace().collect();
+                ensure!(
+                    offsets.len() == 2 && offsets.iter().all(|o| i32::from_str(o).is_ok()),
+                    "Value should be two offsets in pixels, like \"-10 20\""
+                );
+            }
+            BaseCommand::SnapFloating => {
+                SnapPosition::from_str(&self.value).context("invalid position for SnapFloating")?;
+            }
+            BaseCommand::SetSplitRatio => {
+                u8::from_str(&self.value).context("invalid ratio for SetSplitRatio")?;
+            }
+            BaseCommand::FlipSplitTree => {
+                SplitAxis::from_str(&self.value).context("invalid axis for FlipSplitTree")?;
+            }
+            BaseCommand::IncreaseColumnWidth => {
+                i8::from_str(&self.value).context("invalid width value for IncreaseColumnWidth")?;
+            }
+            BaseCommand::DecreaseColumnWidth => {
+                i8::from_str(&self.value).context("invalid width value for DecreaseColumnWidth")?;
+            }
             BaseCommand::SetMarginMultiplier => {
                 f32::from_str(&self.value)
                     .context("invalid margin multiplier for SetMarginMultiplier")?;
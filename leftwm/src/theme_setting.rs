@@ -18,6 +18,10 @@ pub struct ThemeSetting {
     pub floating_border_color: Option<String>,
     pub focused_border_color: Option<String>,
     pub background_color: Option<String>,
+    pub tab_height: Option<i32>,
+    pub tab_color: Option<String>,
+    pub tab_focused_color: Option<String>,
+    pub tab_text_color: Option<String>,
     #[serde(rename = "on_new_window")]
     pub on_new_window_cmd: Option<String>,
 }
@@ -48,6 +52,10 @@ impl Default for ThemeSetting {
             floating_border_color: Some("#000000".to_owned()),
             focused_border_color: Some("#FF0000".to_owned()),
             background_color: Some("#333333".to_owned()),
+            tab_height: Some(20),
+            tab_color: Some("#222222".to_owned()),
+            tab_focused_color: Some("#005577".to_owned()),
+            tab_text_color: Some("#EEEEEE".to_owned()),
             on_new_window_cmd: None,
         }
     }
@@ -114,6 +122,10 @@ default_border_color = '#222222'
 floating_border_color = '#005500'
 focused_border_color = '#FFB53A'
 background_color = '#333333'
+tab_height = 24
+tab_color = '#222222'
+tab_focused_color = '#FFB53A'
+tab_text_color = '#FFFFFF'
 on_new_window = 'echo Hello World'
 
 [[gutter]]
@@ -141,6 +153,10 @@ value = 0
                 floating_border_color: Some("#005500".to_string()),
                 focused_border_color: Some("#FFB53A".to_string()),
                 background_color: Some("#333333".to_owned()),
+                tab_height: Some(24),
+                tab_color: Some("#222222".to_string()),
+                tab_focused_color: Some("#FFB53A".to_string()),
+                tab_text_color: Some("#FFFFFF".to_string()),
                 on_new_window_cmd: Some("echo Hello World".to_string()),
             }
         );
@@ -189,6 +205,10 @@ value = 0
                 floating_border_color: Some("#005500".to_string()),
                 focused_border_color: Some("#FFB53A".to_string()),
                 background_color: Some("#333333".to_owned()),
+                tab_height: None,
+                tab_color: None,
+                tab_focused_color: None,
+                tab_text_color: None,
                 on_new_window_cmd: Some("echo Hello World".to_string()),
             }
         );